@@ -1,18 +1,75 @@
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 use criterion::{criterion_group, criterion_main, Criterion};
 use emerald::DefaultEmerald;
 
+const SYNTHETIC_NOTE_COUNT: usize = 5000;
+const SYNTHETIC_FOLDER_COUNT: usize = 50;
+
 fn vault_load() {
     let vault_path = PathBuf::from("./tests/test_vault");
     let _emerald = DefaultEmerald::new(&vault_path).unwrap();
 }
 
+/// Writes a vault with frontmatter, headings, tags and links between the notes.
+fn create_synthetic_vault(vault_path: &Path) {
+    let _ = fs::remove_dir_all(vault_path);
+    for folder in 0..SYNTHETIC_FOLDER_COUNT {
+        fs::create_dir_all(vault_path.join(format!("folder_{}", folder))).unwrap();
+    }
+    for i in 0..SYNTHETIC_NOTE_COUNT {
+        let mut content = format!(
+            "---\nuid: {}\naliases: [alias {}]\ntags: [synthetic, group{}]\n---\n# Note {}\n",
+            i,
+            i,
+            i % 10,
+            i
+        );
+        for j in 1..=10 {
+            let tgt = (i * 7 + j * 13) % SYNTHETIC_NOTE_COUNT;
+            content.push_str(&format!(
+                "\n## Section {}\n\nSee [[note_{}]], [[alias {}|the alias]] and [[note_{}#Section {}]].\n- [ ] task {} #todo\n",
+                j, tgt, (tgt + 1) % SYNTHETIC_NOTE_COUNT, (tgt + 2) % SYNTHETIC_NOTE_COUNT, j, j
+            ));
+        }
+        let folder = format!("folder_{}", i % SYNTHETIC_FOLDER_COUNT);
+        fs::write(
+            vault_path.join(folder).join(format!("note_{}.md", i)),
+            content,
+        )
+        .unwrap();
+    }
+}
+
+fn load_with_threads(vault_path: &Path, num_threads: usize) {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(num_threads)
+        .build()
+        .unwrap();
+    pool.install(|| DefaultEmerald::new(vault_path).unwrap());
+}
+
 pub fn criterion_benchmark(c: &mut Criterion) {
     let mut group = c.benchmark_group("sample-size-2000");
     group.sample_size(2000);
-    group.bench_function("vault_load", |b| b.iter(|| vault_load()));
+    group.bench_function("vault_load", |b| b.iter(vault_load));
     group.finish();
+
+    let vault_path = std::env::temp_dir().join(format!("emerald_bench_{}", std::process::id()));
+    create_synthetic_vault(&vault_path);
+
+    let mut group = c.benchmark_group("synthetic-vault");
+    group.sample_size(10);
+    group.bench_function("vault_load_sequential", |b| {
+        b.iter(|| load_with_threads(&vault_path, 1))
+    });
+    group.bench_function("vault_load_parallel", |b| {
+        b.iter(|| load_with_threads(&vault_path, 0))
+    });
+    group.finish();
+
+    fs::remove_dir_all(&vault_path).unwrap();
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -0,0 +1,156 @@
+use super::adapters_to_rid_and_link_2_tgt::{resolve_link, split_link};
+use super::adapters_to_rid_and_links::adapter_to_rid_and_links;
+use crate::model::resource_id_resolver;
+use crate::{markdown, types, utils};
+use std::iter::once;
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+/// Maximum number of suggestions per broken link.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Returns the (line, column) of a byte position. Both start with 1 and the
+/// column is counted in chars.
+pub(super) fn line_and_column(buf: &str, pos: usize) -> (usize, usize) {
+    let line_start = buf[..pos].rfind('\n').map_or(0, |idx| idx + 1);
+    let line = buf[..pos].matches('\n').count() + 1;
+    let column = buf[line_start..pos].chars().count() + 1;
+    (line, column)
+}
+
+/// Returns the name of the note the link points to.
+fn target_name(link_comps: &types::LinkComps) -> &str {
+    let name = link_comps.name.trim();
+    name.strip_suffix(".md").unwrap_or(name)
+}
+
+/// Finds all links of the given markdown contents whose target doesn't exist.
+///
+/// # Arguments
+///
+/// * `it_src` - An iterator over the resource ids and contents of the notes.
+/// * `md_analyzer` - Analyzer used to find the links.
+/// * `rid_resolver` - Resolver used to find the targets of the links.
+/// * `note_names` - Names of all notes. The suggestions are taken from them.
+pub fn adapter_to_broken_links<'a, I: markdown::MarkdownAnalyzer<'a> + 'a + Copy>(
+    it_src: impl IntoIterator<Item = (types::ResourceId, &'a types::Content)> + 'a,
+    md_analyzer: I,
+    rid_resolver: &'a impl resource_id_resolver::ResourceIdResolver,
+    note_names: &'a [String],
+) -> impl Iterator<Item = types::BrokenLink> + 'a {
+    it_src.into_iter().flat_map(move |(rid, content)| {
+        let buf = content.0.as_str();
+        md_analyzer.analyze(buf).filter_map(move |md_block| {
+            let raw = match md_block {
+                types::MdBlock::WikiLink(raw)
+                | types::MdBlock::Link(raw)
+                | types::MdBlock::Embed(raw) => raw,
+                _ => return None,
+            };
+
+            // external links are skipped by the adapter
+            let (_, link, kind) = adapter_to_rid_and_links(once((rid.clone(), md_block))).next()?;
+
+            let link_comps = split_link(&rid, &link, kind).ok();
+            if let Some(link_comps) = &link_comps {
+                if resolve_link(&rid, &link, link_comps, kind, rid_resolver).is_ok() {
+                    return None;
+                }
+            }
+
+            let suggestions = link_comps.map_or(vec![], |link_comps| {
+                let names = note_names.iter().map(|f| f.as_str());
+                utils::closest_matches(target_name(&link_comps), names, MAX_SUGGESTIONS)
+                    .into_iter()
+                    .map(|f| f.to_owned())
+                    .collect()
+            });
+
+            // the link is a sub slice of the buffer
+            let start = raw.as_ptr() as usize - buf.as_ptr() as usize;
+            let (line, column) = line_and_column(buf, start);
+            trace!("Broken link {} in {:?} at {}:{}", raw, &rid, line, column);
+
+            Some(types::BrokenLink {
+                src: rid.clone(),
+                link: raw.to_owned(),
+                kind,
+                line,
+                column,
+                suggestions,
+            })
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{adapter_to_broken_links, line_and_column};
+    use crate::markdown::MarkdownAnalyzerImpl;
+    use crate::model::resource_id_resolver::{DefaultResourceIdResolverModel, ResourceLoc};
+    use crate::types::{BrokenLink, Content, LinkKind, ResourceId};
+
+    fn create_resolver() -> DefaultResourceIdResolverModel {
+        let locs = ["note.md", "Café.md"].map(|name| ResourceLoc {
+            rid: name.into(),
+            norm_filename: name.to_lowercase().into(),
+            dir_path: "".into(),
+        });
+        DefaultResourceIdResolverModel::new(locs, vec![])
+    }
+
+    #[test]
+    fn test_line_and_column() {
+        let buf = "first\nsecönd [[link]]";
+        assert_eq!(line_and_column(buf, 0), (1, 1));
+        assert_eq!(line_and_column(buf, buf.find("[[").unwrap()), (2, 8));
+    }
+
+    #[test]
+    fn test_broken_links_are_found_with_position_and_suggestions() {
+        let resolver = create_resolver();
+        let names = vec!["note".to_string(), "Café".to_string()];
+        let rid: ResourceId = "src.md".into();
+        let content: Content =
+            "[[note]] [[nte]]\n  ![img](missing.png) [web](https://example.com)\n[[cafe]]".into();
+
+        let out: Vec<_> = adapter_to_broken_links(
+            vec![(rid.clone(), &content)],
+            MarkdownAnalyzerImpl::new(),
+            &resolver,
+            &names,
+        )
+        .collect();
+
+        assert_eq!(
+            out,
+            vec![
+                BrokenLink {
+                    src: rid.clone(),
+                    link: "[[nte]]".into(),
+                    kind: LinkKind::WikiLink,
+                    line: 1,
+                    column: 10,
+                    suggestions: vec!["note".into()],
+                },
+                BrokenLink {
+                    src: rid.clone(),
+                    link: "![img](missing.png)".into(),
+                    kind: LinkKind::MdEmbed,
+                    line: 2,
+                    column: 3,
+                    suggestions: vec![],
+                },
+                BrokenLink {
+                    src: rid.clone(),
+                    link: "[[cafe]]".into(),
+                    kind: LinkKind::WikiLink,
+                    line: 3,
+                    column: 1,
+                    suggestions: vec!["Café".into()],
+                },
+            ]
+        );
+    }
+}
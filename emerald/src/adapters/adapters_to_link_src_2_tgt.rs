@@ -1,13 +1,17 @@
 use super::adapters_to_rid_and_link_2_tgt::adapter_to_rid_and_link_2_tgt;
 use super::adapters_to_rid_and_links::adapter_to_rid_and_links;
-use crate::{model::resource_id_resolver, types};
+use crate::model::{block_id, heading, resource_id_resolver};
+use crate::types;
 
 pub fn adapter_to_link_src_2_tgt<'a>(
     it_src: impl IntoIterator<Item = (types::ResourceId, types::MdBlock<'a>)> + 'a,
     rid_resolver: &'a impl resource_id_resolver::ResourceIdResolver,
+    heading_retriever: &'a impl heading::HeadingRetriever,
+    block_id_retriever: &'a impl block_id::BlockIdRetriever,
 ) -> impl Iterator<Item = types::LinkSrc2Tgt> + 'a {
     let it1 = adapter_to_rid_and_links(it_src);
-    let it2 = adapter_to_rid_and_link_2_tgt(it1, rid_resolver);
+    let it2 =
+        adapter_to_rid_and_link_2_tgt(it1, rid_resolver, heading_retriever, block_id_retriever);
 
     it2.into_iter()
         .map(|(rid, link_2_tgt)| types::LinkSrc2Tgt::from_link_to_target(rid.clone(), link_2_tgt))
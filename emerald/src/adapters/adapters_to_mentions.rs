@@ -0,0 +1,301 @@
+use super::adapters_to_broken_links::line_and_column;
+use crate::model::resource_id_resolver::{self, Hint};
+use crate::{markdown, types};
+use std::iter::once;
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+/// Returns the byte ranges of the parts of the markdown which can't contain
+/// mentions: links, embeds, code, tags, block ids and the frontmatter.
+fn skipped_spans<'a, I: markdown::MarkdownAnalyzer<'a>>(
+    buf: &'a str,
+    md_analyzer: I,
+) -> Vec<(usize, usize)> {
+    md_analyzer
+        .analyze(buf)
+        .filter_map(|md_block| match md_block {
+            types::MdBlock::WikiLink(raw)
+            | types::MdBlock::Link(raw)
+            | types::MdBlock::Embed(raw)
+            | types::MdBlock::CodeBlock(raw)
+            | types::MdBlock::Tag(raw)
+            | types::MdBlock::BlockId(raw)
+            | types::MdBlock::YamlFrontmatter(raw) => {
+                // the block is a sub slice of the buffer
+                let start = raw.as_ptr() as usize - buf.as_ptr() as usize;
+                Some((start, start + raw.len()))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+/// Returns the length in bytes of `name` at the start of `text` if it matches
+/// case insensitively.
+fn match_len(text: &str, name: &str) -> Option<usize> {
+    let mut text_chars = text.char_indices();
+    for name_ch in name.chars() {
+        let (_, text_ch) = text_chars.next()?;
+        if !text_ch.to_lowercase().eq(name_ch.to_lowercase()) {
+            return None;
+        }
+    }
+    Some(text_chars.next().map_or(text.len(), |(idx, _)| idx))
+}
+
+/// Finds the whole word occurrences of the names within `buf[start..end]`.
+/// Longer names take precedence over shorter ones starting at the same position.
+fn find_in_range(
+    buf: &str,
+    (start, end): (usize, usize),
+    names: &[&String],
+    found: &mut Vec<(usize, usize)>,
+) {
+    let mut pos = start;
+    while pos < end {
+        let at_word_start = !buf[..pos].chars().next_back().is_some_and(is_word_char);
+        let matched = at_word_start
+            .then(|| {
+                names.iter().find_map(|name| {
+                    let len = match_len(&buf[pos..end], name)?;
+                    let next = buf[pos + len..].chars().next();
+                    (!next.is_some_and(is_word_char)).then_some(len)
+                })
+            })
+            .flatten();
+
+        match matched {
+            Some(len) => {
+                found.push((pos, pos + len));
+                pos += len;
+            }
+            None => pos += buf[pos..].chars().next().map_or(1, |f| f.len_utf8()),
+        }
+    }
+}
+
+/// Finds the whole word occurrences of the names outside of the skipped spans.
+///
+/// # Arguments
+///
+/// * `buf` - Markdown to search.
+/// * `skipped` - Byte ranges to skip in document order.
+/// * `names` - Names to find.
+fn find_mentions(buf: &str, skipped: &[(usize, usize)], names: &[String]) -> Vec<(usize, usize)> {
+    let mut names: Vec<_> = names.iter().filter(|f| !f.trim().is_empty()).collect();
+    names.sort_by_key(|f| std::cmp::Reverse(f.len()));
+
+    let mut found = vec![];
+    let mut free_start = 0;
+    for &(skip_start, skip_end) in skipped.iter().chain(once(&(buf.len(), buf.len()))) {
+        if skip_start > free_start {
+            find_in_range(buf, (free_start, skip_start), &names, &mut found);
+        }
+        free_start = free_start.max(skip_end);
+    }
+    found
+}
+
+/// Finds the mentions of the names in the given notes which aren't part of a link.
+///
+/// # Arguments
+///
+/// * `it_src` - An iterator over the resource ids and contents of the notes.
+/// * `md_analyzer` - Analyzer used to find the links and code blocks.
+/// * `names` - Title and aliases of the mentioned note.
+pub fn adapter_to_mentions<'a, I: markdown::MarkdownAnalyzer<'a> + 'a + Copy>(
+    it_src: impl IntoIterator<Item = (types::ResourceId, &'a types::Content)> + 'a,
+    md_analyzer: I,
+    names: &'a [String],
+) -> impl Iterator<Item = types::Mention> + 'a {
+    it_src.into_iter().flat_map(move |(src, content)| {
+        let buf = content.0.as_str();
+        let skipped = skipped_spans(buf, md_analyzer);
+        find_mentions(buf, &skipped, names)
+            .into_iter()
+            .map(move |(start, end)| {
+                let (line, column) = line_and_column(buf, start);
+                types::Mention {
+                    src: src.clone(),
+                    text: buf[start..end].to_owned(),
+                    line,
+                    column,
+                    span: (start, end),
+                }
+            })
+    })
+}
+
+/// Returns the wiki link replacing a mention of `rid` in the note `src`. The
+/// file name is used as target if it resolves to `rid`, otherwise its path.
+fn link_for_mention(
+    src: &types::ResourceId,
+    rid: &types::ResourceId,
+    text: &str,
+    rid_resolver: &impl resource_id_resolver::ResourceIdResolver,
+) -> String {
+    let path = rid.0.strip_suffix(".md").unwrap_or(&rid.0);
+    let name = path.rsplit('/').next().unwrap_or(path);
+
+    let bare_link = types::Link(format!("[[{}]]", name));
+    let tgt = match rid_resolver.resolve_with_hint(&bare_link, Hint::from_source(src)) {
+        Ok(tgt) if tgt == rid => name,
+        _ => path,
+    };
+
+    if text == tgt {
+        format!("[[{}]]", text)
+    } else {
+        format!("[[{}|{}]]", tgt, text)
+    }
+}
+
+/// Replaces the mentions of a note by wiki links pointing to it. Only notes
+/// whose content changes are returned.
+///
+/// # Arguments
+///
+/// * `it_src` - An iterator over the resource ids and contents of the notes.
+/// * `md_analyzer` - Analyzer used to find the links and code blocks.
+/// * `rid_resolver` - Resolver used to check whether the file name of the note
+///   is unique.
+/// * `rid` - Resource id of the mentioned note.
+/// * `names` - Title and aliases of the mentioned note.
+pub fn adapter_to_linked_mentions<'a, I: markdown::MarkdownAnalyzer<'a> + 'a + Copy>(
+    it_src: impl IntoIterator<Item = (types::ResourceId, &'a types::Content)> + 'a,
+    md_analyzer: I,
+    rid_resolver: &'a impl resource_id_resolver::ResourceIdResolver,
+    rid: &'a types::ResourceId,
+    names: &'a [String],
+) -> impl Iterator<Item = (types::ResourceId, String)> + 'a {
+    it_src.into_iter().filter_map(move |(src, content)| {
+        let buf = content.0.as_str();
+        let skipped = skipped_spans(buf, md_analyzer);
+        let mentions = find_mentions(buf, &skipped, names);
+        if mentions.is_empty() {
+            return None;
+        }
+
+        let mut out = String::with_capacity(buf.len());
+        let mut last = 0;
+        for (start, end) in mentions {
+            out.push_str(&buf[last..start]);
+            out.push_str(&link_for_mention(&src, rid, &buf[start..end], rid_resolver));
+            last = end;
+        }
+        out.push_str(&buf[last..]);
+        debug!("Link mentions of {:?} in {:?}", rid, &src);
+        Some((src, out))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{adapter_to_linked_mentions, adapter_to_mentions, find_mentions, match_len};
+    use crate::markdown::MarkdownAnalyzerImpl;
+    use crate::model::resource_id_resolver::{DefaultResourceIdResolverModel, ResourceLoc};
+    use crate::types::{Content, ResourceId};
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|f| f.to_string()).collect()
+    }
+
+    fn mentions(content: &str, names: &[String]) -> Vec<(String, usize, usize)> {
+        let content: Content = content.into();
+        adapter_to_mentions(
+            [(ResourceId::from("src.md"), &content)],
+            MarkdownAnalyzerImpl::new(),
+            names,
+        )
+        .map(|f| (f.text, f.line, f.column))
+        .collect()
+    }
+
+    #[test]
+    fn test_match_len() {
+        assert_eq!(match_len("Note rest", "note"), Some(4));
+        assert_eq!(match_len("Ärger", "ärger"), Some(6));
+        assert_eq!(match_len("No", "note"), None);
+        assert_eq!(match_len("Nope", "note"), None);
+    }
+
+    #[test]
+    fn test_find_whole_words() {
+        let res = find_mentions("note, notes, a_note, note", &[], &names(&["note"]));
+        assert_eq!(res, [(0, 4), (21, 25)]);
+    }
+
+    #[test]
+    fn test_find_longest_name() {
+        let res = find_mentions("my note title", &[], &names(&["note", "note title"]));
+        assert_eq!(res, [(3, 13)]);
+    }
+
+    #[test]
+    fn test_find_outside_of_skipped_spans() {
+        let res = find_mentions("note [[note]] note", &[(5, 13)], &names(&["note"]));
+        assert_eq!(res, [(0, 4), (14, 18)]);
+    }
+
+    #[test]
+    fn test_mentions_skip_links_and_code() {
+        let content = "---\naliases: [Note]\n---\n[[note]] [note](note.md) `note` #note\nA Note.";
+        assert_eq!(
+            mentions(content, &names(&["note"])),
+            [("Note".into(), 5, 3)]
+        );
+    }
+
+    #[test]
+    fn test_mentions_in_headings_and_tasks() {
+        let content = "# About note\n- [ ] write note";
+        assert_eq!(
+            mentions(content, &names(&["note"])),
+            [("note".into(), 1, 9), ("note".into(), 2, 13)]
+        );
+    }
+
+    #[test]
+    fn test_link_mentions() {
+        let locs = ["a/note.md", "b/note.md", "a/src.md", "b/src.md"].map(|rid| {
+            let (dir, name) = rid.rsplit_once('/').unwrap_or(("", rid));
+            ResourceLoc {
+                rid: rid.into(),
+                norm_filename: name.into(),
+                dir_path: dir.into(),
+            }
+        });
+        let resolver = DefaultResourceIdResolverModel::new(locs, vec![]);
+        let content: Content = "note and Note".into();
+        let rid = ResourceId::from("a/note.md");
+        let names = names(&["note"]);
+
+        let res: Vec<_> = adapter_to_linked_mentions(
+            [
+                (ResourceId::from("a/src.md"), &content),
+                (ResourceId::from("b/src.md"), &content),
+            ],
+            MarkdownAnalyzerImpl::new(),
+            &resolver,
+            &rid,
+            &names,
+        )
+        .collect();
+
+        assert_eq!(
+            res,
+            [
+                ("a/src.md".into(), "[[note]] and [[note|Note]]".into()),
+                (
+                    "b/src.md".into(),
+                    "[[a/note|note]] and [[a/note|Note]]".into()
+                ),
+            ]
+        );
+    }
+}
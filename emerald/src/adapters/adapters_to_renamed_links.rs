@@ -0,0 +1,186 @@
+use super::adapters_to_rid_and_link_2_tgt::{resolve_link, split_link};
+use super::adapters_to_rid_and_links::adapter_to_rid_and_links;
+use crate::model::resource_id_resolver;
+use crate::{markdown, types};
+use std::iter::once;
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+/// Returns the link pointing to the new target or None if it doesn't change.
+fn retarget_link(
+    src: &types::ResourceId,
+    new_src: &types::ResourceId,
+    link: &types::Link,
+    kind: types::LinkKind,
+    rid_resolver: &impl resource_id_resolver::ResourceIdResolver,
+    rid: &types::ResourceId,
+    new_rid: &types::ResourceId,
+) -> Option<types::Link> {
+    let link_comps = split_link(src, link, kind).ok()?;
+    let tgt = resolve_link(src, link, &link_comps, kind, rid_resolver).ok()?;
+
+    let new_link = match kind {
+        types::LinkKind::WikiLink | types::LinkKind::WikiEmbed => {
+            if tgt != rid {
+                return None;
+            }
+            link.retarget_wiki_link(new_rid).ok()?
+        }
+        types::LinkKind::MdLink | types::LinkKind::MdEmbed => {
+            // links into the same note stay untouched
+            if link.split_md_link().ok()?.target.is_empty() {
+                return None;
+            }
+
+            // relative links of the renamed note have to follow it
+            let new_tgt = if tgt == rid {
+                new_rid
+            } else if src != new_src {
+                tgt
+            } else {
+                return None;
+            };
+            link.retarget_md_link(new_src, new_tgt).ok()?
+        }
+    };
+
+    (new_link != *link).then_some(new_link)
+}
+
+/// Rewrites the links of the given notes for renaming a note.
+///
+/// Links pointing to the renamed note are changed to point to its new resource id.
+/// They keep their label, section, anchor and path style. The relative markdown
+/// links of the renamed note itself are adapted to its new directory. Only notes
+/// whose content changes are returned.
+///
+/// # Arguments
+///
+/// * `it_src` - An iterator over the resource ids and contents of the notes.
+/// * `md_analyzer` - Analyzer used to find the links.
+/// * `rid_resolver` - Resolver used to find the current targets of the links.
+/// * `rid` - Resource id of the renamed note.
+/// * `new_rid` - New resource id of the renamed note.
+pub fn adapter_to_renamed_links<'a, I: markdown::MarkdownAnalyzer<'a> + 'a + Copy>(
+    it_src: impl IntoIterator<Item = (types::ResourceId, &'a types::Content)> + 'a,
+    md_analyzer: I,
+    rid_resolver: &'a impl resource_id_resolver::ResourceIdResolver,
+    rid: &'a types::ResourceId,
+    new_rid: &'a types::ResourceId,
+) -> impl Iterator<Item = (types::ResourceId, String)> + 'a {
+    it_src.into_iter().filter_map(move |(src, content)| {
+        let buf = content.0.as_str();
+        let new_src = if src == *rid { new_rid } else { &src };
+
+        let mut out = String::with_capacity(buf.len());
+        let mut last = 0;
+        let mut changed = false;
+        for md_block in md_analyzer.analyze(buf) {
+            let raw = match md_block {
+                types::MdBlock::WikiLink(raw)
+                | types::MdBlock::Link(raw)
+                | types::MdBlock::Embed(raw) => raw,
+                _ => continue,
+            };
+            let Some((_, link, kind)) =
+                adapter_to_rid_and_links(once((src.clone(), md_block))).next()
+            else {
+                continue;
+            };
+            let Some(new_link) =
+                retarget_link(&src, new_src, &link, kind, rid_resolver, rid, new_rid)
+            else {
+                continue;
+            };
+            trace!("Rewrite {:?} to {:?} in {:?}", &link, &new_link, &src);
+
+            // the link is a sub slice of the buffer. Embeds are preceded by a '!'.
+            let start = raw.as_ptr() as usize - buf.as_ptr() as usize + raw.len() - link.0.len();
+            out.push_str(&buf[last..start]);
+            out.push_str(&new_link.0);
+            last = start + link.0.len();
+            changed = true;
+        }
+
+        if !changed {
+            return None;
+        }
+        out.push_str(&buf[last..]);
+        Some((src, out))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::adapter_to_renamed_links;
+    use crate::markdown::MarkdownAnalyzerImpl;
+    use crate::model::resource_id_resolver::{DefaultResourceIdResolverModel, ResourceLoc};
+    use crate::types::{Content, ResourceId};
+
+    fn create_resolver() -> DefaultResourceIdResolverModel {
+        let locs = ["old.md", "other.md", "sub/src.md"].map(|rid| {
+            let (dir, name) = rid.rsplit_once('/').unwrap_or(("", rid));
+            ResourceLoc {
+                rid: rid.into(),
+                norm_filename: name.into(),
+                dir_path: dir.into(),
+            }
+        });
+        DefaultResourceIdResolverModel::new(locs, vec![])
+    }
+
+    fn rename(rid: &str, content: &str, new_rid: &str) -> Vec<(ResourceId, String)> {
+        let resolver = create_resolver();
+        let content: Content = content.into();
+        let old_rid: ResourceId = "old.md".into();
+        let new_rid: ResourceId = new_rid.into();
+        adapter_to_renamed_links(
+            vec![(rid.into(), &content)],
+            MarkdownAnalyzerImpl::new(),
+            &resolver,
+            &old_rid,
+            &new_rid,
+        )
+        .collect()
+    }
+
+    #[test]
+    fn test_links_to_renamed_note_are_rewritten() {
+        let out = rename(
+            "sub/src.md",
+            "[[old|Label]] [[other]] ![[old#Section]]\n[md](../old.md) [[old.md^id]]",
+            "dir/new.md",
+        );
+        assert_eq!(
+            out,
+            vec![(
+                "sub/src.md".into(),
+                "[[new|Label]] [[other]] ![[new#Section]]\n[md](../dir/new.md) [[new.md^id]]"
+                    .to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_notes_without_links_to_renamed_note_are_skipped() {
+        let out = rename("sub/src.md", "[[other]] [md](../other.md)", "new.md");
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn test_md_links_of_renamed_note_follow_it() {
+        let out = rename(
+            "old.md",
+            "[other](other.md) [self](old.md) [section](#Section) [[other]]",
+            "dir/new.md",
+        );
+        assert_eq!(
+            out,
+            vec![(
+                "old.md".into(),
+                "[other](../other.md) [self](new.md) [section](#Section) [[other]]".to_string()
+            )]
+        );
+    }
+}
@@ -12,6 +12,7 @@ pub fn filter_rid_and_meta_data<'a>(
 }
 
 #[cfg(test)]
+#[allow(clippy::useless_vec)]
 mod tests {
     use super::types;
     use crate::adapters::filter_rid_and_meta_data;
@@ -0,0 +1,46 @@
+use crate::{markdown, types};
+
+/// Extracts all block identifiers of the given markdown contents.
+///
+/// The identifiers are returned without their leading `^`.
+pub fn adapter_to_rid_and_block_ids<'a, I: markdown::MarkdownAnalyzer<'a> + 'a + Copy>(
+    it_src: impl IntoIterator<Item = (types::ResourceId, &'a types::Content)> + 'a,
+    md_analyzer: I,
+) -> impl Iterator<Item = (types::ResourceId, Vec<String>)> + 'a {
+    it_src.into_iter().map(move |(rid, content)| {
+        let block_ids = md_analyzer
+            .analyze(&content.0)
+            .filter_map(|md_block| match md_block {
+                types::MdBlock::BlockId(block_id) => {
+                    Some(block_id.trim_start_matches('^').to_owned())
+                }
+                _ => None,
+            })
+            .collect();
+        (rid, block_ids)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::adapter_to_rid_and_block_ids;
+    use crate::markdown::MarkdownAnalyzerImpl;
+    use crate::types::{Content, ResourceId};
+
+    #[test]
+    fn test_block_ids_are_extracted() {
+        let rid: ResourceId = "note.md".into();
+        let content: Content = "Paragraph ^first\n\n- item ^second-id\n".into();
+
+        let out: Vec<_> = adapter_to_rid_and_block_ids(
+            vec![(rid.clone(), &content)],
+            MarkdownAnalyzerImpl::new(),
+        )
+        .collect();
+
+        assert_eq!(
+            out,
+            [(rid, vec!["first".to_owned(), "second-id".to_owned()])]
+        );
+    }
+}
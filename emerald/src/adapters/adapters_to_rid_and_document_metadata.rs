@@ -1,22 +1,80 @@
+use crate::markdown;
 use crate::types::{self, DocumentMetadata};
 #[allow(unused_imports)]
 use log::{debug, error, info, trace, warn};
 
-pub fn adapter_to_rid_and_document_metadata<'a>(
-    it_src: impl IntoIterator<Item = (types::ResourceId, Option<&'a str>)> + 'a,
-) -> impl Iterator<Item = (types::ResourceId, types::DocumentMetadata)> + 'a {
-    it_src.into_iter().map(|f| {
-        if let Some(yaml) = f.1 {
-            let res = serde_yaml::from_str::<types::DocumentMetadata>(yaml);
-            match res {
-                Ok(yaml_meta_data) => (f.0, yaml_meta_data),
-                Err(err) => {
-                    warn!("Invalid yaml found in {:?}\nError: {}\n{}", f.0, err, yaml);
-                    (f.0, DocumentMetadata::default())
-                }
+/// Parses the frontmatter of every note. A note with invalid frontmatter is
+/// treated as having none and a diagnostic spanning the frontmatter is returned.
+pub fn adapter_to_rid_and_document_metadata<'a, I>(
+    it_src: impl IntoIterator<Item = (types::ResourceId, &'a str)> + 'a,
+    splitter: I,
+) -> impl Iterator<
+    Item = (
+        types::ResourceId,
+        types::DocumentMetadata,
+        Option<types::Diagnostic>,
+    ),
+> + 'a
+where
+    I: markdown::MarkdownFrontmatterSplitter + Copy + 'a,
+{
+    it_src.into_iter().map(move |(rid, content)| {
+        let (Some(yaml), _) = splitter.split(content) else {
+            return (rid, DocumentMetadata::default(), None);
+        };
+        match serde_yaml::from_str::<types::DocumentMetadata>(yaml) {
+            Ok(yaml_meta_data) => (rid, yaml_meta_data, None),
+            Err(err) => {
+                warn!("Invalid yaml found in {:?}\nError: {}\n{}", rid, err, yaml);
+                let start = yaml.as_ptr() as usize - content.as_ptr() as usize;
+                let diagnostic = types::Diagnostic {
+                    path: rid.clone(),
+                    kind: types::DiagnosticKind::InvalidFrontmatter,
+                    message: err.to_string(),
+                    span: Some((start, start + yaml.len())),
+                };
+                (rid, DocumentMetadata::default(), Some(diagnostic))
             }
-        } else {
-            (f.0, DocumentMetadata::default())
         }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::adapter_to_rid_and_document_metadata;
+    use crate::markdown::DefaultMarkdownFrontmatterSplitter;
+    use crate::types::DiagnosticKind;
+
+    #[test]
+    fn test_invalid_frontmatter_is_reported() {
+        let content = "---\naliases: [a\n---\n# Heading\n";
+        let splitter = DefaultMarkdownFrontmatterSplitter::new();
+        let res: Vec<_> =
+            adapter_to_rid_and_document_metadata(vec![("a.md".into(), content)], splitter)
+                .collect();
+
+        assert_eq!(res.len(), 1);
+        assert!(res[0].1.aliases.is_none());
+        let diagnostic = res[0].2.as_ref().unwrap();
+        assert_eq!(diagnostic.kind, DiagnosticKind::InvalidFrontmatter);
+        assert_eq!(diagnostic.path.0.as_ref(), "a.md");
+        let (start, end) = diagnostic.span.unwrap();
+        assert_eq!(&content[start..end], "aliases: [a");
+    }
+
+    #[test]
+    fn test_valid_and_missing_frontmatter() {
+        let splitter = DefaultMarkdownFrontmatterSplitter::new();
+        let res: Vec<_> = adapter_to_rid_and_document_metadata(
+            vec![
+                ("a.md".into(), "---\naliases: [b]\n---\n"),
+                ("c.md".into(), "# No frontmatter\n"),
+            ],
+            splitter,
+        )
+        .collect();
+
+        assert_eq!(res[0].1.aliases, Some(vec!["b".to_string()]));
+        assert!(res.iter().all(|f| f.2.is_none()));
+    }
+}
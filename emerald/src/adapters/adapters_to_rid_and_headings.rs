@@ -0,0 +1,54 @@
+use crate::{markdown, types};
+
+/// Extracts all headings of the given markdown contents.
+///
+/// Yields the headings of every resource in document order. The span of each
+/// heading relates to the start of the content.
+pub fn adapter_to_rid_and_headings<'a, I: markdown::MarkdownAnalyzer<'a> + 'a + Copy>(
+    it_src: impl IntoIterator<Item = (types::ResourceId, &'a types::Content)> + 'a,
+    md_analyzer: I,
+) -> impl Iterator<Item = (types::ResourceId, Vec<types::Heading>)> + 'a {
+    it_src.into_iter().map(move |(rid, content)| {
+        let buf = content.0.as_str();
+        let headings = md_analyzer
+            .analyze(buf)
+            .filter_map(|md_block| match md_block {
+                types::MdBlock::Heading(heading) => {
+                    // the heading is a sub slice of the buffer
+                    let start = heading.as_ptr() as usize - buf.as_ptr() as usize;
+                    Some(types::Heading::from_md(heading, start))
+                }
+                _ => None,
+            })
+            .collect();
+        (rid, headings)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::adapter_to_rid_and_headings;
+    use crate::markdown::MarkdownAnalyzerImpl;
+    use crate::types::{Content, Heading, ResourceId};
+
+    #[test]
+    fn test_headings_with_spans_are_extracted() {
+        let rid: ResourceId = "note.md".into();
+        let content: Content = "# Title\ntext\n\nSection\n---\n".into();
+
+        let out: Vec<_> =
+            adapter_to_rid_and_headings(vec![(rid.clone(), &content)], MarkdownAnalyzerImpl::new())
+                .collect();
+
+        assert_eq!(
+            out,
+            [(
+                rid,
+                vec![
+                    Heading::new(1, "Title".into(), (0, 7)),
+                    Heading::new(2, "Section".into(), (14, 25))
+                ]
+            )]
+        );
+    }
+}
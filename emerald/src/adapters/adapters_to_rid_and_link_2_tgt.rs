@@ -1,16 +1,246 @@
-use crate::{model::resource_id_resolver, types};
+use crate::error::Result;
+use crate::model::resource_id_resolver::{self, Hint};
+use crate::model::{block_id, heading};
+use crate::types;
 #[allow(unused_imports)]
 use log::{debug, error, info, trace, warn};
+use std::collections::HashSet;
+
+pub(super) fn split_link(
+    src: &types::ResourceId,
+    link: &types::Link,
+    kind: types::LinkKind,
+) -> Result<types::LinkComps> {
+    match kind {
+        types::LinkKind::WikiLink | types::LinkKind::WikiEmbed => link.split(),
+        types::LinkKind::MdLink | types::LinkKind::MdEmbed => {
+            // markdown links are relative to their source. Convert them to an
+            // equivalent wiki link relative to the vault root.
+            link.split_md_link()?.to_link_comps(src)
+        }
+    }
+}
+
+pub(super) fn resolve_link<'a>(
+    src: &types::ResourceId,
+    link: &types::Link,
+    link_comps: &types::LinkComps,
+    kind: types::LinkKind,
+    rid_resolver: &'a impl resource_id_resolver::ResourceIdResolver,
+) -> Result<&'a types::ResourceId> {
+    match kind {
+        types::LinkKind::WikiLink | types::LinkKind::WikiEmbed => {
+            rid_resolver.resolve_with_hint(link, Hint::from_source(src))
+        }
+        types::LinkKind::MdLink | types::LinkKind::MdEmbed => {
+            // the path of a converted markdown link starts at the vault root
+            let link = match link_comps.path.as_deref() {
+                Some(path) if !path.is_empty() => format!("[[/{}/{}]]", path, link_comps.name),
+                _ => format!("[[/{}]]", link_comps.name),
+            };
+            rid_resolver.resolve(&types::Link(link))
+        }
+    }
+}
+
+/// Returns true if the target doesn't contain the section or the block the link
+/// points to. Sections and blocks are only checked for markdown targets.
+fn is_anchor_missing(
+    link_comps: &types::LinkComps,
+    tgt: &types::ResourceId,
+    heading_retriever: &impl heading::HeadingRetriever,
+    block_id_retriever: &impl block_id::BlockIdRetriever,
+) -> bool {
+    let section = link_comps.section.as_deref().filter(|f| !f.is_empty());
+    let anchor = link_comps.anchor.as_deref().filter(|f| !f.is_empty());
+
+    // markdown links refer to blocks through their section
+    let (section, anchor) = match section.and_then(|f| f.strip_prefix('^')) {
+        Some(block_id) => (None, Some(block_id)),
+        None => (section, anchor),
+    };
+
+    if let Some(anchor) = anchor {
+        if let Some(block_ids) = block_id_retriever.retrieve(tgt) {
+            if !block_ids.iter().any(|f| f == anchor) {
+                return true;
+            }
+        }
+    }
+
+    if let Some(section) = section {
+        if let Some(headings) = heading_retriever.retrieve(tgt) {
+            if !headings.iter().any(|f| f.matches_section(section)) {
+                return true;
+            }
+        }
+    }
+
+    false
+}
 
 pub fn adapter_to_rid_and_link_2_tgt<'a>(
-    it_src: impl IntoIterator<Item = (types::ResourceId, types::Link)> + 'a,
+    it_src: impl IntoIterator<Item = (types::ResourceId, types::Link, types::LinkKind)> + 'a,
     rid_resolver: &'a impl resource_id_resolver::ResourceIdResolver,
+    heading_retriever: &'a impl heading::HeadingRetriever,
+    block_id_retriever: &'a impl block_id::BlockIdRetriever,
 ) -> impl Iterator<Item = (types::ResourceId, types::Link2Tgt)> + 'a {
-    it_src.into_iter().map(|(rid, f)| {
-        if let Ok(tgt_rid) = rid_resolver.resolve(&f) {
-            (rid, types::Link2Tgt::new(f, Some(tgt_rid.clone())))
+    it_src.into_iter().map(|(rid, f, kind)| {
+        let Ok(link_comps) = split_link(&rid, &f, kind) else {
+            return (rid, types::Link2Tgt::new(f, None, kind));
+        };
+        let Ok(tgt_rid) = resolve_link(&rid, &f, &link_comps, kind, rid_resolver) else {
+            return (rid, types::Link2Tgt::new(f, None, kind));
+        };
+
+        let link_2_tgt = types::Link2Tgt::new(f, Some(tgt_rid.clone()), kind);
+        if is_anchor_missing(&link_comps, tgt_rid, heading_retriever, block_id_retriever) {
+            trace!(
+                "Anchor of link {:?} in {:?} is missing",
+                &link_2_tgt.link,
+                &rid
+            );
+            (rid, link_2_tgt.with_missing_anchor())
         } else {
-            (rid, types::Link2Tgt::new(f, None))
+            (rid, link_2_tgt)
         }
     })
 }
+
+/// Keeps the links which might resolve to a resource with one of the given
+/// names. The names have to be normalized like the names the resolver uses.
+pub fn filter_links_by_name<'a>(
+    it_src: impl IntoIterator<Item = (types::ResourceId, types::Link, types::LinkKind)> + 'a,
+    names: &'a HashSet<String>,
+) -> impl Iterator<Item = (types::ResourceId, types::Link, types::LinkKind)> + 'a {
+    it_src.into_iter().filter(|(rid, f, kind)| {
+        let Ok(link_comps) = split_link(rid, f, *kind) else {
+            return false;
+        };
+        let name = resource_id_resolver::norm_name(&link_comps.name);
+        names.contains(&name) || names.contains(&(name + ".md"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{filter_links_by_name, is_anchor_missing};
+    use crate::model::block_id::DefaultBlockIdModel;
+    use crate::model::heading::DefaultHeadingModel;
+    use crate::types::{Heading, Link, LinkComps, LinkKind, ResourceId};
+    use std::collections::HashSet;
+
+    fn create_models() -> (DefaultHeadingModel, DefaultBlockIdModel) {
+        let hmod = DefaultHeadingModel::new(vec![(
+            "note.md".into(),
+            vec![Heading::new(1, "My Heading".into(), (0, 12))],
+        )]);
+        let bmod = DefaultBlockIdModel::new(vec![("note.md".into(), vec!["block-1".into()])]);
+        (hmod, bmod)
+    }
+
+    fn split(link: &str) -> LinkComps {
+        Link(link.into()).split().unwrap()
+    }
+
+    #[test]
+    fn test_is_anchor_missing_without_section() {
+        let (hmod, bmod) = create_models();
+        let tgt: ResourceId = "note.md".into();
+        assert!(!is_anchor_missing(&split("[[note]]"), &tgt, &hmod, &bmod));
+    }
+
+    #[test]
+    fn test_is_anchor_missing_with_existing_section() {
+        let (hmod, bmod) = create_models();
+        let tgt: ResourceId = "note.md".into();
+        assert!(!is_anchor_missing(
+            &split("[[note#My Heading]]"),
+            &tgt,
+            &hmod,
+            &bmod
+        ));
+    }
+
+    #[test]
+    fn test_is_anchor_missing_with_missing_section() {
+        let (hmod, bmod) = create_models();
+        let tgt: ResourceId = "note.md".into();
+        assert!(is_anchor_missing(
+            &split("[[note#Other]]"),
+            &tgt,
+            &hmod,
+            &bmod
+        ));
+    }
+
+    #[test]
+    fn test_is_anchor_missing_with_existing_block() {
+        let (hmod, bmod) = create_models();
+        let tgt: ResourceId = "note.md".into();
+        assert!(!is_anchor_missing(
+            &split("[[note#^block-1]]"),
+            &tgt,
+            &hmod,
+            &bmod
+        ));
+    }
+
+    #[test]
+    fn test_is_anchor_missing_with_missing_block() {
+        let (hmod, bmod) = create_models();
+        let tgt: ResourceId = "note.md".into();
+        assert!(is_anchor_missing(
+            &split("[[note#^block-2]]"),
+            &tgt,
+            &hmod,
+            &bmod
+        ));
+    }
+
+    #[test]
+    fn test_is_anchor_missing_with_block_in_section() {
+        let (hmod, bmod) = create_models();
+        let tgt: ResourceId = "note.md".into();
+        let link_comps = LinkComps::new("note".into(), None, None, Some("^block-1".into()), None);
+        assert!(!is_anchor_missing(&link_comps, &tgt, &hmod, &bmod));
+    }
+
+    #[test]
+    fn test_is_anchor_missing_ignores_non_markdown_targets() {
+        let (hmod, bmod) = create_models();
+        let tgt: ResourceId = "doc.pdf".into();
+        assert!(!is_anchor_missing(
+            &split("[[doc.pdf#page=2]]"),
+            &tgt,
+            &hmod,
+            &bmod
+        ));
+    }
+
+    #[test]
+    fn test_filter_links_by_name() {
+        let names = HashSet::from(["note.md".to_string(), "alias".to_string()]);
+        let links = [
+            ("a.md", "[[Note#Heading]]", LinkKind::WikiLink),
+            ("a.md", "[[dir/note.md]]", LinkKind::WikiEmbed),
+            ("a.md", "[[Alias|label]]", LinkKind::WikiLink),
+            ("dir/a.md", "[text](../note.md)", LinkKind::MdLink),
+            ("a.md", "[[other]]", LinkKind::WikiLink),
+        ]
+        .map(|(src, link, kind)| (ResourceId::from(src), Link(link.into()), kind));
+
+        let kept: Vec<_> = filter_links_by_name(links, &names)
+            .map(|f| f.1 .0.to_string())
+            .collect();
+        assert_eq!(
+            kept,
+            [
+                "[[Note#Heading]]",
+                "[[dir/note.md]]",
+                "[[Alias|label]]",
+                "[text](../note.md)"
+            ]
+        );
+    }
+}
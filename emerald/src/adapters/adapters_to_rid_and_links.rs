@@ -2,14 +2,104 @@ use crate::types;
 #[allow(unused_imports)]
 use log::{debug, error, info, trace, warn};
 
+/// Returns true if the link is a markdown link pointing to an external url.
+fn is_external_md_link(link: &types::Link) -> bool {
+    link.split_md_link().is_ok_and(|comps| comps.is_external())
+}
+
+/// Extracts all links and embeds from the given markdown blocks.
+///
+/// Wiki links, standard markdown links and embeds are returned together with their
+/// `LinkKind`. The leading `!` of an embed is not part of the returned link.
+/// Markdown links pointing to external urls are skipped, since they can never be
+/// resolved to a resource of the vault.
 pub fn adapter_to_rid_and_links<'a>(
     it_src: impl IntoIterator<Item = (types::ResourceId, types::MdBlock<'a>)> + 'a,
-) -> impl Iterator<Item = (types::ResourceId, types::Link)> + 'a {
+) -> impl Iterator<Item = (types::ResourceId, types::Link, types::LinkKind)> + 'a {
     it_src
         .into_iter()
-        .filter(|(_, content_type)| matches!(content_type, types::MdBlock::WikiLink(_)))
-        .map(|(rid, content_type)| match content_type {
-            types::MdBlock::WikiLink(link) => (rid, types::Link(link.to_owned())),
-            _ => panic!(),
+        .filter_map(|(rid, content_type)| match content_type {
+            types::MdBlock::WikiLink(link) => {
+                Some((rid, types::Link(link.to_owned()), types::LinkKind::WikiLink))
+            }
+            types::MdBlock::Link(link) => {
+                let link = types::Link(link.to_owned());
+                if is_external_md_link(&link) {
+                    trace!("Skip external link {:?} in {:?}", &link, &rid);
+                    return None;
+                }
+                Some((rid, link, types::LinkKind::MdLink))
+            }
+            types::MdBlock::Embed(embed) => {
+                let link = types::Link(embed.trim_start_matches('!').to_owned());
+                if link.0.starts_with("[[") {
+                    return Some((rid, link, types::LinkKind::WikiEmbed));
+                }
+                if is_external_md_link(&link) {
+                    trace!("Skip external embed {:?} in {:?}", &link, &rid);
+                    return None;
+                }
+                Some((rid, link, types::LinkKind::MdEmbed))
+            }
+            _ => None,
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::adapter_to_rid_and_links;
+    use crate::types::{Link, LinkKind, MdBlock, ResourceId};
+
+    #[test]
+    fn test_wiki_link_and_md_link_are_extracted() {
+        let rid: ResourceId = "note.md".into();
+        let blocks = vec![
+            (rid.clone(), MdBlock::WikiLink("[[wiki]]")),
+            (rid.clone(), MdBlock::CodeBlock("`code`")),
+            (rid.clone(), MdBlock::Link("[md](md.md)")),
+        ];
+
+        let res: Vec<_> = adapter_to_rid_and_links(blocks).collect();
+
+        assert_eq!(
+            res,
+            vec![
+                (rid.clone(), Link::from("[[wiki]]"), LinkKind::WikiLink),
+                (rid.clone(), Link::from("[md](md.md)"), LinkKind::MdLink)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_embeds_are_extracted_without_exclamation_mark() {
+        let rid: ResourceId = "note.md".into();
+        let blocks = vec![
+            (rid.clone(), MdBlock::Embed("![[wiki]]")),
+            (rid.clone(), MdBlock::Embed("![alt](image.png)")),
+        ];
+
+        let res: Vec<_> = adapter_to_rid_and_links(blocks).collect();
+
+        assert_eq!(
+            res,
+            vec![
+                (rid.clone(), Link::from("[[wiki]]"), LinkKind::WikiEmbed),
+                (
+                    rid.clone(),
+                    Link::from("[alt](image.png)"),
+                    LinkKind::MdEmbed
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn test_external_md_link_is_skipped() {
+        let rid: ResourceId = "note.md".into();
+        let blocks = vec![(rid, MdBlock::Link("[web](https://example.com)"))];
+
+        let res: Vec<_> = adapter_to_rid_and_links(blocks).collect();
+
+        assert!(res.is_empty());
+    }
+}
@@ -0,0 +1,25 @@
+use crate::model::note;
+use crate::{markdown, types};
+
+/// Collects the tags of the given markdown contents.
+///
+/// The tags of the frontmatter are followed by the inline tags of the content. The
+/// leading `#` of inline tags is removed.
+pub fn adapter_to_rid_and_tags<'a, I: markdown::MarkdownAnalyzer<'a> + 'a + Copy>(
+    it_src: impl IntoIterator<Item = (types::ResourceId, &'a types::Content)> + 'a,
+    md_analyzer: I,
+    note_md_retriever: &'a impl note::NoteMetadataRetriever,
+) -> impl Iterator<Item = (types::ResourceId, Vec<String>)> + 'a {
+    it_src.into_iter().map(move |(rid, content)| {
+        let mut tags = note_md_retriever.retrieve(&rid).document.tags.clone();
+        tags.extend(
+            md_analyzer
+                .analyze(&content.0)
+                .filter_map(|md_block| match md_block {
+                    types::MdBlock::Tag(tag) => Some(tag.trim_start_matches('#').to_owned()),
+                    _ => None,
+                }),
+        );
+        (rid, tags)
+    })
+}
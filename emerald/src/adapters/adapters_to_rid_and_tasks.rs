@@ -0,0 +1,76 @@
+use crate::{markdown, types};
+
+/// Extracts all task list items of the given markdown contents.
+pub fn adapter_to_rid_and_tasks<'a, I: markdown::MarkdownAnalyzer<'a> + 'a + Copy>(
+    it_src: impl IntoIterator<Item = (types::ResourceId, &'a types::Content)> + 'a,
+    md_analyzer: I,
+) -> impl Iterator<Item = (types::ResourceId, Vec<types::Task>)> + 'a {
+    it_src.into_iter().map(move |(rid, content)| {
+        let tasks = md_analyzer
+            .analyze(&content.0)
+            .filter_map(|md_block| match md_block {
+                types::MdBlock::Task {
+                    checked,
+                    text,
+                    line,
+                    due,
+                } => {
+                    // the tags of a task are contained in its text
+                    let tags = md_analyzer
+                        .analyze(text)
+                        .filter_map(|md_block| match md_block {
+                            types::MdBlock::Tag(tag) => {
+                                Some(tag.trim_start_matches('#').to_owned())
+                            }
+                            _ => None,
+                        })
+                        .collect();
+
+                    Some(types::Task::new(
+                        text.to_owned(),
+                        checked,
+                        line,
+                        due.map(|f| f.to_owned()),
+                        tags,
+                    ))
+                }
+                _ => None,
+            })
+            .collect();
+        (rid, tasks)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::adapter_to_rid_and_tasks;
+    use crate::markdown::MarkdownAnalyzerImpl;
+    use crate::types::{Content, ResourceId, Task};
+
+    #[test]
+    fn test_tasks_with_tags_are_extracted() {
+        let rid: ResourceId = "note.md".into();
+        let content: Content = "# Todo\n- [ ] call #work 📅 2026-10-20\n- [x] done\n".into();
+
+        let out: Vec<_> =
+            adapter_to_rid_and_tasks(vec![(rid.clone(), &content)], MarkdownAnalyzerImpl::new())
+                .collect();
+
+        assert_eq!(
+            out,
+            [(
+                rid,
+                vec![
+                    Task::new(
+                        "call #work 📅 2026-10-20".into(),
+                        false,
+                        2,
+                        Some("2026-10-20".into()),
+                        vec!["work".into()]
+                    ),
+                    Task::new("done".into(), true, 3, None, vec![]),
+                ]
+            )]
+        );
+    }
+}
@@ -1,22 +1,35 @@
+mod adapters_to_broken_links;
 mod adapters_to_link_src_2_tgt;
+mod adapters_to_mentions;
+mod adapters_to_renamed_links;
 mod adapters_to_rid;
+mod adapters_to_rid_and_block_ids;
 mod adapters_to_rid_and_content;
 mod adapters_to_rid_and_content_type;
 mod adapters_to_rid_and_document_metadata;
 mod adapters_to_rid_and_filesystem_metadata;
+mod adapters_to_rid_and_headings;
 mod adapters_to_rid_and_link_2_tgt;
 mod adapters_to_rid_and_links;
-mod adapters_to_rid_and_yaml;
+mod adapters_to_rid_and_tags;
+mod adapters_to_rid_and_tasks;
 pub mod to_note_updater;
 pub mod to_outside;
 pub mod to_resource_id_resolver;
 pub mod to_unique_id;
 pub mod to_vault;
 
+pub use adapters_to_broken_links::adapter_to_broken_links;
 pub use adapters_to_link_src_2_tgt::adapter_to_link_src_2_tgt;
+pub use adapters_to_mentions::{adapter_to_linked_mentions, adapter_to_mentions};
+pub use adapters_to_renamed_links::adapter_to_renamed_links;
 pub use adapters_to_rid::filter_rid_and_meta_data;
+pub use adapters_to_rid_and_block_ids::adapter_to_rid_and_block_ids;
 pub use adapters_to_rid_and_content::adapter_to_rids_and_content;
 pub use adapters_to_rid_and_content_type::adapter_to_rid_and_content_type;
 pub use adapters_to_rid_and_document_metadata::adapter_to_rid_and_document_metadata;
 pub use adapters_to_rid_and_filesystem_metadata::adapter_to_rid_and_filesystem_metadata;
-pub use adapters_to_rid_and_yaml::adapter_to_rid_and_yaml;
+pub use adapters_to_rid_and_headings::adapter_to_rid_and_headings;
+pub use adapters_to_rid_and_link_2_tgt::{adapter_to_rid_and_link_2_tgt, filter_links_by_name};
+pub use adapters_to_rid_and_tags::adapter_to_rid_and_tags;
+pub use adapters_to_rid_and_tasks::adapter_to_rid_and_tasks;
@@ -1,7 +1,7 @@
 use crate::model::content;
 use crate::model::note_updater;
 use crate::types;
-use std::rc::Rc;
+use std::sync::Arc;
 
 /// Adapts a generic markdown content retriever to the `note_updater::MdContentRetriever` interface.
 ///
@@ -10,7 +10,7 @@ use std::rc::Rc;
 /// is needed.
 #[derive(Clone)]
 pub struct MdContentRetrieverAdapter {
-    content_retriever: Rc<dyn content::MdContentRetriever>,
+    content_retriever: Arc<dyn content::MdContentRetriever>,
 }
 
 impl MdContentRetrieverAdapter {
@@ -18,12 +18,12 @@ impl MdContentRetrieverAdapter {
     ///
     /// # Arguments
     ///
-    /// * `content_retriever`: An `Rc` pointer to an object implementing `content::MdContentRetriever`.
+    /// * `content_retriever`: An `Arc` pointer to an object implementing `content::MdContentRetriever`.
     ///
     /// # Returns
     ///
     /// A new instance of `ContentRetrieverAdapter`.
-    pub fn new(content_retriever: Rc<dyn content::MdContentRetriever>) -> Self {
+    pub fn new(content_retriever: Arc<dyn content::MdContentRetriever>) -> Self {
         Self { content_retriever }
     }
 }
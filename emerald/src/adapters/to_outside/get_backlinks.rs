@@ -2,7 +2,7 @@ use super::link_query_result::LinkQueryResult;
 use crate::types;
 
 /// Trait for querying links pointing to a target resource.
-pub trait GetBacklinks {
+pub trait GetBacklinks: Send + Sync {
     /// Returns an iterator over links pointing to the specified resource.
     ///
     /// # Arguments
@@ -5,19 +5,19 @@ use super::LinkQueryResult;
 use crate::model::{link, resource};
 use crate::types;
 use std::marker::PhantomData;
-use std::rc::Rc;
+use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct GetBacklinksAdapter<I = LinkQueryResultBuilderImpl> {
-    src_link_retriever: Rc<dyn link::SrcIterRetriever>,
-    res_meta_data_ret: Rc<dyn resource::ResourceMetadataRetriever>,
-    pd: PhantomData<I>,
+    src_link_retriever: Arc<dyn link::SrcIterRetriever>,
+    res_meta_data_ret: Arc<dyn resource::ResourceMetadataRetriever>,
+    pd: PhantomData<fn() -> I>,
 }
 
 impl GetBacklinksAdapter {
     pub fn new(
-        src_link_retriever: Rc<dyn link::SrcIterRetriever>,
-        res_meta_data_ret: Rc<dyn resource::ResourceMetadataRetriever>,
+        src_link_retriever: Arc<dyn link::SrcIterRetriever>,
+        res_meta_data_ret: Arc<dyn resource::ResourceMetadataRetriever>,
     ) -> Self {
         Self {
             src_link_retriever,
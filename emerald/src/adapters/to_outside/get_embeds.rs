@@ -0,0 +1,15 @@
+use super::link_query_result::LinkQueryResult;
+use crate::types;
+
+/// Trait for querying embeds (transclusions) contained in a target resource.
+pub trait GetEmbeds: Send + Sync {
+    /// Returns an iterator over the resources embedded in the specified resource.
+    ///
+    /// # Arguments
+    ///
+    /// * `rid`: Resource identifier.
+    fn get_embeds_of(
+        &self,
+        rid: &types::ResourceId,
+    ) -> Box<dyn Iterator<Item = LinkQueryResult> + 'static>;
+}
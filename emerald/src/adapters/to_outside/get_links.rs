@@ -2,7 +2,7 @@ use super::link_query_result::LinkQueryResult;
 use crate::types;
 
 /// Trait for querying links contained in a target resource.
-pub trait GetLinks {
+pub trait GetLinks: Send + Sync {
     /// Returns an iterator over links contained in the specified resource.
     ///
     /// # Arguments
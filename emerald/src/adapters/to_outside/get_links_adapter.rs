@@ -1,21 +1,21 @@
 use super::link_query_result_builder::{LinkQueryResultBuilder, LinkQueryResultBuilderImpl};
-use super::{GetLinks, LinkQueryResult};
+use super::{GetEmbeds, GetLinks, LinkQueryResult};
 use crate::model::{link, resource};
 use crate::types;
 use std::marker::PhantomData;
-use std::rc::Rc;
+use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct GetLinksAdapter<I = LinkQueryResultBuilderImpl> {
-    tgt_link_retriever: Rc<dyn link::TgtIterRetriever>,
-    res_meta_data_ret: Rc<dyn resource::ResourceMetadataRetriever>,
-    pd: PhantomData<I>,
+    tgt_link_retriever: Arc<dyn link::TgtIterRetriever>,
+    res_meta_data_ret: Arc<dyn resource::ResourceMetadataRetriever>,
+    pd: PhantomData<fn() -> I>,
 }
 
 impl GetLinksAdapter {
     pub fn new(
-        tgt_link_retriever: Rc<dyn link::TgtIterRetriever>,
-        res_meta_data_ret: Rc<dyn resource::ResourceMetadataRetriever>,
+        tgt_link_retriever: Arc<dyn link::TgtIterRetriever>,
+        res_meta_data_ret: Arc<dyn resource::ResourceMetadataRetriever>,
     ) -> Self {
         Self {
             tgt_link_retriever,
@@ -39,8 +39,31 @@ where
         let res_meta_data_ret = self.res_meta_data_ret.clone();
         Box::new(out_itr.filter_map(move |i| {
             // only consider valid targets
-            i.tgt.map(|valid_tgt| {
-                I::convert_to_link_query_result(res_meta_data_ret.as_ref(), valid_tgt)
+            i.tgt.map(|valid_tgt| match i.anchor_missing {
+                true => LinkQueryResult::LinkToMissingAnchor(valid_tgt),
+                false => I::convert_to_link_query_result(res_meta_data_ret.as_ref(), valid_tgt),
+            })
+        }))
+    }
+}
+
+impl<I> GetEmbeds for GetLinksAdapter<I>
+where
+    I: LinkQueryResultBuilder,
+{
+    fn get_embeds_of(
+        &self,
+        rid: &types::ResourceId,
+    ) -> Box<dyn Iterator<Item = LinkQueryResult> + 'static> {
+        let Some(out_itr) = self.tgt_link_retriever.retrieve(rid) else {
+            return Box::new(std::iter::empty());
+        };
+        let res_meta_data_ret = self.res_meta_data_ret.clone();
+        Box::new(out_itr.filter(|i| i.kind.is_embed()).filter_map(move |i| {
+            // only consider valid targets
+            i.tgt.map(|valid_tgt| match i.anchor_missing {
+                true => LinkQueryResult::LinkToMissingAnchor(valid_tgt),
+                false => I::convert_to_link_query_result(res_meta_data_ret.as_ref(), valid_tgt),
             })
         }))
     }
@@ -1,6 +1,10 @@
 use crate::types;
 
+#[allow(clippy::enum_variant_names)]
 pub enum LinkQueryResult {
     LinkToNote(types::ResourceId),
     LinkToResource(types::ResourceId),
+
+    /// The link points to a note which doesn't contain the linked section or block.
+    LinkToMissingAnchor(types::ResourceId),
 }
@@ -1,5 +1,6 @@
 mod get_backlinks;
 mod get_backlinks_adapter;
+mod get_embeds;
 mod get_links;
 mod get_links_adapter;
 mod link_query_result;
@@ -7,6 +8,7 @@ mod link_query_result_builder;
 
 pub use get_backlinks::GetBacklinks;
 pub use get_backlinks_adapter::GetBacklinksAdapter;
+pub use get_embeds::GetEmbeds;
 pub use get_links::GetLinks;
 pub use get_links_adapter::GetLinksAdapter;
 pub use link_query_result::LinkQueryResult;
@@ -0,0 +1,52 @@
+use crate::types;
+
+/// Converts the document metadata of notes into pairs of resource ids and aliases.
+///
+/// # Arguments
+///
+/// * `it_src` - An iterator over tuples, each containing a `types::ResourceId` and the
+///   `types::DocumentMetadata` of the note.
+///
+/// # Returns
+///
+/// An iterator over every alias of every note together with the resource id of the note.
+pub fn convert_to_aliases<'a>(
+    it_src: impl IntoIterator<Item = &'a (types::ResourceId, types::DocumentMetadata)> + 'a,
+) -> impl Iterator<Item = (types::ResourceId, String)> + 'a {
+    it_src.into_iter().flat_map(|(rid, doc_metadata)| {
+        doc_metadata
+            .aliases
+            .iter()
+            .flatten()
+            .map(move |alias| (rid.clone(), alias.clone()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::convert_to_aliases;
+    use crate::types::DocumentMetadata;
+
+    #[test]
+    fn test_convert_to_aliases() {
+        let metadata = vec![
+            (
+                "a.md".into(),
+                DocumentMetadata {
+                    aliases: Some(vec!["First".into(), "Second".into()]),
+                    ..Default::default()
+                },
+            ),
+            ("b.md".into(), DocumentMetadata::default()),
+        ];
+
+        let aliases: Vec<_> = convert_to_aliases(&metadata).collect();
+        assert_eq!(
+            aliases,
+            vec![
+                ("a.md".into(), "First".to_string()),
+                ("a.md".into(), "Second".to_string())
+            ]
+        );
+    }
+}
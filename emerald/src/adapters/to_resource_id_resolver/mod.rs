@@ -1,3 +1,5 @@
+mod alias_adapters;
 mod resource_loc_adapters;
 
+pub use alias_adapters::convert_to_aliases;
 pub use resource_loc_adapters::convert_to_resource_locations;
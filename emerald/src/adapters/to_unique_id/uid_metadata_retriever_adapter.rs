@@ -1,16 +1,24 @@
+use crate::model::content;
 use crate::model::note;
 use crate::model::unique_id;
 use crate::types;
-use std::rc::Rc;
+use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct UidMetadataRetrieverAdapter {
-    metadata_retriever: Rc<dyn note::NoteMetadataRetriever>,
+    metadata_retriever: Arc<dyn note::NoteMetadataRetriever>,
+    content_retriever: Arc<dyn content::MdContentRetriever>,
 }
 
 impl UidMetadataRetrieverAdapter {
-    pub fn new(metadata_retriever: Rc<dyn note::NoteMetadataRetriever>) -> Self {
-        Self { metadata_retriever }
+    pub fn new(
+        metadata_retriever: Arc<dyn note::NoteMetadataRetriever>,
+        content_retriever: Arc<dyn content::MdContentRetriever>,
+    ) -> Self {
+        Self {
+            metadata_retriever,
+            content_retriever,
+        }
     }
 }
 
@@ -19,4 +27,13 @@ impl unique_id::UidMetadataRetriever<types::ResourceId> for UidMetadataRetriever
         let note_metadata = self.metadata_retriever.retrieve(rid);
         note_metadata.document.uid.to_owned()
     }
+
+    fn retrieve_created(&self, rid: &types::ResourceId) -> i64 {
+        let note_metadata = self.metadata_retriever.retrieve(rid);
+        note_metadata.filesystem.created
+    }
+
+    fn retrieve_content(&self, rid: &types::ResourceId) -> &str {
+        &self.content_retriever.retrieve(rid).0
+    }
 }
@@ -2,7 +2,7 @@ use crate::model::content;
 use crate::model::unique_id;
 use crate::model::vault;
 use crate::types;
-use std::rc::Rc;
+use std::sync::Arc;
 
 /// Adapts a generic markdown content retriever to the `vault::MdContentRetriever` interface.
 ///
@@ -11,8 +11,8 @@ use std::rc::Rc;
 /// is needed.
 #[derive(Clone)]
 pub struct MdContentRetrieverAdapter {
-    content_retriever: Rc<dyn content::MdContentRetriever>,
-    uid_retriever: Rc<dyn unique_id::UidRetriever<types::ResourceId>>,
+    content_retriever: Arc<dyn content::MdContentRetriever>,
+    uid_retriever: Arc<dyn unique_id::UidRetriever<types::ResourceId>>,
 }
 
 impl MdContentRetrieverAdapter {
@@ -20,15 +20,15 @@ impl MdContentRetrieverAdapter {
     ///
     /// # Arguments
     ///
-    /// * `content_retriever`: An `Rc` pointer to an object implementing `content::MdContentRetriever`.
-    /// * `uid_retriever`: An `Rc` pointer to an object implementing `unique_id::UidRetriever`.
+    /// * `content_retriever`: An `Arc` pointer to an object implementing `content::MdContentRetriever`.
+    /// * `uid_retriever`: An `Arc` pointer to an object implementing `unique_id::UidRetriever`.
     ///
     /// # Returns
     ///
     /// A new instance of `MdContentRetrieverAdapter`.
     pub fn new(
-        content_retriever: Rc<dyn content::MdContentRetriever>,
-        uid_retriever: Rc<dyn unique_id::UidRetriever<types::ResourceId>>,
+        content_retriever: Arc<dyn content::MdContentRetriever>,
+        uid_retriever: Arc<dyn unique_id::UidRetriever<types::ResourceId>>,
     ) -> Self {
         Self {
             content_retriever,
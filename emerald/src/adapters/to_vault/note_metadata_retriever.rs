@@ -2,12 +2,12 @@ use crate::model::note;
 use crate::model::unique_id;
 use crate::model::vault;
 use crate::types;
-use std::rc::Rc;
+use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct NoteMetadataRetriever {
-    metadata_retriever: Rc<dyn note::NoteMetadataRetriever>,
-    uid_retriever: Rc<dyn unique_id::UidRetriever<types::ResourceId>>,
+    metadata_retriever: Arc<dyn note::NoteMetadataRetriever>,
+    uid_retriever: Arc<dyn unique_id::UidRetriever<types::ResourceId>>,
 }
 
 impl NoteMetadataRetriever {
@@ -15,15 +15,15 @@ impl NoteMetadataRetriever {
     ///
     /// # Arguments
     ///
-    /// * `metadata_retriever`: An `Rc` pointer to an object implementing `note::NoteMetadataRetriever`.
-    /// * `uid_retriever`: An `Rc` pointer to an object implementing `unique_id::UidRetriever`.
+    /// * `metadata_retriever`: An `Arc` pointer to an object implementing `note::NoteMetadataRetriever`.
+    /// * `uid_retriever`: An `Arc` pointer to an object implementing `unique_id::UidRetriever`.
     ///
     /// # Returns
     ///
     /// A new instance of `NoteMetadataRetriever`.
     pub fn new(
-        metadata_retriever: Rc<dyn note::NoteMetadataRetriever>,
-        uid_retriever: Rc<dyn unique_id::UidRetriever<types::ResourceId>>,
+        metadata_retriever: Arc<dyn note::NoteMetadataRetriever>,
+        uid_retriever: Arc<dyn unique_id::UidRetriever<types::ResourceId>>,
     ) -> Self {
         Self {
             metadata_retriever,
@@ -0,0 +1,144 @@
+use super::note_graph::NoteGraph;
+
+/// Orders components by descending size. Components of equal size keep the
+/// order of their first note.
+fn sort_components(components: &mut [Vec<usize>]) {
+    for component in components.iter_mut() {
+        component.sort();
+    }
+    components.sort_by(|a, b| b.len().cmp(&a.len()).then(a[0].cmp(&b[0])));
+}
+
+/// Returns the sets of notes which are connected if the direction of the links
+/// is ignored.
+pub fn weak_components(graph: &NoteGraph) -> Vec<Vec<usize>> {
+    fn find(parents: &mut [usize], mut node: usize) -> usize {
+        while parents[node] != node {
+            parents[node] = parents[parents[node]];
+            node = parents[node];
+        }
+        node
+    }
+
+    let mut parents: Vec<usize> = (0..graph.len()).collect();
+    for (src, tgts) in graph.out_edges.iter().enumerate() {
+        for tgt in tgts {
+            let (a, b) = (find(&mut parents, src), find(&mut parents, *tgt));
+            parents[a.max(b)] = a.min(b);
+        }
+    }
+
+    let mut components: Vec<Vec<usize>> = vec![vec![]; graph.len()];
+    for node in 0..graph.len() {
+        let root = find(&mut parents, node);
+        components[root].push(node);
+    }
+    let mut components: Vec<_> = components.into_iter().filter(|f| !f.is_empty()).collect();
+    sort_components(&mut components);
+    components
+}
+
+/// Returns the sets of notes in which every note can be reached from every
+/// other note by following links (Tarjan's algorithm). A note which isn't part
+/// of a cycle forms a component of its own.
+pub fn strong_components(graph: &NoteGraph) -> Vec<Vec<usize>> {
+    const UNVISITED: usize = usize::MAX;
+
+    let mut index = vec![UNVISITED; graph.len()];
+    let mut low_link = vec![0; graph.len()];
+    let mut on_stack = vec![false; graph.len()];
+    let mut stack = vec![];
+    let mut next_index = 0;
+    let mut components = vec![];
+
+    for root in 0..graph.len() {
+        if index[root] != UNVISITED {
+            continue;
+        }
+
+        // emulates the recursion with a stack of (node, position of the next edge)
+        let mut call_stack = vec![(root, 0)];
+        index[root] = next_index;
+        low_link[root] = next_index;
+        next_index += 1;
+        stack.push(root);
+        on_stack[root] = true;
+
+        while let Some(&(node, pos)) = call_stack.last() {
+            if let Some(&next) = graph.out_edges[node].get(pos) {
+                if let Some(top) = call_stack.last_mut() {
+                    top.1 += 1;
+                }
+                if index[next] == UNVISITED {
+                    index[next] = next_index;
+                    low_link[next] = next_index;
+                    next_index += 1;
+                    stack.push(next);
+                    on_stack[next] = true;
+                    call_stack.push((next, 0));
+                } else if on_stack[next] {
+                    low_link[node] = low_link[node].min(index[next]);
+                }
+                continue;
+            }
+
+            call_stack.pop();
+            if let Some(&(parent, _)) = call_stack.last() {
+                low_link[parent] = low_link[parent].min(low_link[node]);
+            }
+            if low_link[node] == index[node] {
+                let mut component = vec![];
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+
+    sort_components(&mut components);
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{strong_components, weak_components};
+    use crate::analytics::note_graph::NoteGraph;
+
+    #[test]
+    fn test_weak_components() {
+        let graph = NoteGraph::from_edges(6, &[(0, 1), (2, 1), (4, 3)]);
+        assert_eq!(
+            weak_components(&graph),
+            vec![vec![0, 1, 2], vec![3, 4], vec![5]]
+        );
+    }
+
+    #[test]
+    fn test_strong_components() {
+        // 0 -> 1 -> 2 -> 0 is a cycle, 3 <-> 4 as well and 5 is only linked
+        let graph =
+            NoteGraph::from_edges(6, &[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 3), (4, 5)]);
+        assert_eq!(
+            strong_components(&graph),
+            vec![vec![0, 1, 2], vec![3, 4], vec![5]]
+        );
+    }
+
+    #[test]
+    fn test_strong_components_without_cycles() {
+        let graph = NoteGraph::from_edges(3, &[(0, 1), (1, 2)]);
+        assert_eq!(strong_components(&graph), vec![vec![0], vec![1], vec![2]]);
+    }
+
+    #[test]
+    fn test_components_of_empty_graph() {
+        let graph = NoteGraph::from_edges(0, &[]);
+        assert!(weak_components(&graph).is_empty());
+        assert!(strong_components(&graph).is_empty());
+    }
+}
@@ -0,0 +1,71 @@
+use super::components::{strong_components, weak_components};
+use super::note_analytics::NoteAnalytics;
+use super::note_graph::NoteGraph;
+use super::page_rank::page_rank;
+use super::vault_analytics::VaultAnalytics;
+use crate::model::{link, note};
+use std::iter::zip;
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+/// Analyzes the link graph of the notes.
+///
+/// # Arguments
+///
+/// * `notes`: All notes of the vault.
+/// * `links`: Outgoing links of the notes.
+pub fn extract_analytics(
+    notes: &impl note::NotesIterSrc,
+    links: &impl link::TgtIterRetriever,
+) -> VaultAnalytics {
+    let graph = NoteGraph::new(notes.create_iter(), links);
+
+    let weak = weak_components(&graph);
+    let strong = strong_components(&graph);
+    let mut component = vec![0; graph.len()];
+    for (idx, members) in weak.iter().enumerate() {
+        members.iter().for_each(|f| component[*f] = idx);
+    }
+    let mut strong_component = vec![0; graph.len()];
+    for (idx, members) in strong.iter().enumerate() {
+        members.iter().for_each(|f| strong_component[*f] = idx);
+    }
+
+    let in_degrees = graph.in_degrees();
+    let ranks = page_rank(&graph);
+    let notes: Vec<_> = graph
+        .rids
+        .iter()
+        .enumerate()
+        .map(|(idx, rid)| {
+            let analytics = NoteAnalytics {
+                in_degree: in_degrees[idx],
+                out_degree: graph.out_edges[idx].len(),
+                page_rank: ranks[idx],
+                component: component[idx],
+                strong_component: strong_component[idx],
+            };
+            (rid.clone(), analytics)
+        })
+        .collect();
+    debug!(
+        "{} notes in {} weakly and {} strongly connected components",
+        notes.len(),
+        weak.len(),
+        strong.len()
+    );
+
+    let to_rids = |components: Vec<Vec<usize>>| {
+        components
+            .into_iter()
+            .map(|f| f.into_iter().map(|idx| graph.rids[idx].clone()).collect())
+            .collect()
+    };
+    VaultAnalytics {
+        index: zip(graph.rids.iter().cloned(), 0..).collect(),
+        notes,
+        weak_components: to_rids(weak),
+        strong_components: to_rids(strong),
+    }
+}
@@ -0,0 +1,10 @@
+mod components;
+mod extract_analytics;
+mod note_analytics;
+mod note_graph;
+mod page_rank;
+mod vault_analytics;
+
+pub use extract_analytics::extract_analytics;
+pub use note_analytics::NoteAnalytics;
+pub use vault_analytics::VaultAnalytics;
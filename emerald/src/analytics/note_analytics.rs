@@ -0,0 +1,32 @@
+/// Position of a note in the link graph of the vault. Only links between
+/// distinct notes are taken into account.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NoteAnalytics {
+    /// Number of notes linking to the note.
+    pub in_degree: usize,
+
+    /// Number of notes the note links to.
+    pub out_degree: usize,
+
+    /// PageRank of the note. The ranks of all notes sum up to 1.
+    pub page_rank: f64,
+
+    /// Index of the weakly connected component containing the note. Components
+    /// are ordered by descending size.
+    pub component: usize,
+
+    /// Index of the strongly connected component containing the note.
+    pub strong_component: usize,
+}
+
+impl NoteAnalytics {
+    /// Returns true if the note neither links to other notes nor is linked.
+    pub fn is_orphan(&self) -> bool {
+        self.in_degree == 0 && self.out_degree == 0
+    }
+
+    /// Returns true if the note is linked but doesn't link to other notes.
+    pub fn is_dead_end(&self) -> bool {
+        self.in_degree > 0 && self.out_degree == 0
+    }
+}
@@ -0,0 +1,107 @@
+use crate::model::link;
+use crate::types;
+use std::collections::HashMap;
+
+/// Directed graph of the links between notes. Nodes are referred to by their
+/// index in `rids`.
+pub struct NoteGraph {
+    pub rids: Vec<types::ResourceId>,
+
+    /// Distinct targets of the links of each note. Links of a note to itself
+    /// and links to attachments or missing notes are not part of the graph.
+    pub out_edges: Vec<Vec<usize>>,
+}
+
+impl NoteGraph {
+    pub fn new(
+        it_notes: impl IntoIterator<Item = types::ResourceId>,
+        links: &impl link::TgtIterRetriever,
+    ) -> Self {
+        let rids: Vec<_> = it_notes.into_iter().collect();
+        let index: HashMap<_, _> = rids.iter().enumerate().map(|(i, f)| (f, i)).collect();
+
+        let out_edges = rids
+            .iter()
+            .enumerate()
+            .map(|(src, rid)| {
+                let mut tgts: Vec<usize> = links
+                    .retrieve(rid)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|f| f.tgt.and_then(|tgt| index.get(&tgt).copied()))
+                    .filter(|tgt| *tgt != src)
+                    .collect();
+                tgts.sort();
+                tgts.dedup();
+                tgts
+            })
+            .collect();
+
+        Self { rids, out_edges }
+    }
+
+    pub fn len(&self) -> usize {
+        self.rids.len()
+    }
+
+    /// Returns the number of links pointing to each note.
+    pub fn in_degrees(&self) -> Vec<usize> {
+        let mut in_degrees = vec![0; self.len()];
+        for tgt in self.out_edges.iter().flatten() {
+            in_degrees[*tgt] += 1;
+        }
+        in_degrees
+    }
+
+    #[cfg(test)]
+    pub fn from_edges(count: usize, edges: &[(usize, usize)]) -> Self {
+        let mut out_edges = vec![vec![]; count];
+        for (src, tgt) in edges {
+            out_edges[*src].push(*tgt);
+        }
+        Self {
+            rids: (0..count).map(|f| format!("{}.md", f).into()).collect(),
+            out_edges,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NoteGraph;
+    use crate::model::link::TgtIterRetriever;
+    use crate::types::{Link2Tgt, LinkKind, ResourceId};
+
+    struct Links;
+
+    impl TgtIterRetriever for Links {
+        fn retrieve(&self, src: &ResourceId) -> Option<Box<dyn Iterator<Item = Link2Tgt>>> {
+            let link = |tgt: Option<&str>| {
+                Link2Tgt::new("[[x]]".into(), tgt.map(|f| f.into()), LinkKind::WikiLink)
+            };
+            match src.0.as_ref() {
+                "a.md" => Some(Box::new(
+                    vec![
+                        link(Some("b.md")),
+                        link(Some("b.md")),
+                        link(Some("a.md")),
+                        link(Some("image.png")),
+                        link(None),
+                    ]
+                    .into_iter(),
+                )),
+                "b.md" => Some(Box::new(vec![link(Some("c.md"))].into_iter())),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_only_distinct_links_between_notes_are_edges() {
+        let notes = ["a.md", "b.md", "c.md"].map(ResourceId::from);
+        let dut = NoteGraph::new(notes, &Links);
+
+        assert_eq!(dut.out_edges, vec![vec![1], vec![2], vec![]]);
+        assert_eq!(dut.in_degrees(), vec![0, 1, 1]);
+    }
+}
@@ -0,0 +1,82 @@
+use super::note_graph::NoteGraph;
+use std::iter::zip;
+
+/// Probability that a random surfer follows a link instead of jumping to a
+/// random note.
+const DAMPING: f64 = 0.85;
+const MAX_ITERATIONS: usize = 100;
+const TOLERANCE: f64 = 1e-10;
+
+/// Computes the PageRank of every note. The ranks sum up to 1. Notes without
+/// links distribute their rank evenly among all notes.
+pub fn page_rank(graph: &NoteGraph) -> Vec<f64> {
+    let count = graph.len();
+    if count == 0 {
+        return vec![];
+    }
+
+    let mut ranks = vec![1.0 / count as f64; count];
+    for _ in 0..MAX_ITERATIONS {
+        let dangling: f64 = zip(&graph.out_edges, &ranks)
+            .filter(|(tgts, _)| tgts.is_empty())
+            .map(|(_, rank)| rank)
+            .sum();
+
+        let mut next = vec![(1.0 - DAMPING + DAMPING * dangling) / count as f64; count];
+        for (tgts, rank) in zip(&graph.out_edges, &ranks) {
+            for tgt in tgts {
+                next[*tgt] += DAMPING * rank / tgts.len() as f64;
+            }
+        }
+
+        let delta: f64 = zip(&ranks, &next).map(|(a, b)| (a - b).abs()).sum();
+        ranks = next;
+        if delta < TOLERANCE {
+            break;
+        }
+    }
+    ranks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::page_rank;
+    use crate::analytics::note_graph::NoteGraph;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-6, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn test_page_rank_of_cycle_is_uniform() {
+        let graph = NoteGraph::from_edges(3, &[(0, 1), (1, 2), (2, 0)]);
+        for rank in page_rank(&graph) {
+            assert_close(rank, 1.0 / 3.0);
+        }
+    }
+
+    #[test]
+    fn test_page_rank_prefers_linked_notes() {
+        let graph = NoteGraph::from_edges(4, &[(0, 3), (1, 3), (2, 3), (3, 0)]);
+        let ranks = page_rank(&graph);
+
+        assert_close(ranks.iter().sum(), 1.0);
+        assert!(ranks[3] > ranks[0]);
+        assert!(ranks[0] > ranks[1]);
+        assert_close(ranks[1], ranks[2]);
+    }
+
+    #[test]
+    fn test_page_rank_with_dangling_notes() {
+        let graph = NoteGraph::from_edges(2, &[(0, 1)]);
+        let ranks = page_rank(&graph);
+
+        assert_close(ranks.iter().sum(), 1.0);
+        assert!(ranks[1] > ranks[0]);
+    }
+
+    #[test]
+    fn test_page_rank_of_empty_graph() {
+        assert!(page_rank(&NoteGraph::from_edges(0, &[])).is_empty());
+    }
+}
@@ -0,0 +1,67 @@
+use super::note_analytics::NoteAnalytics;
+use crate::types;
+use std::collections::HashMap;
+
+/// Link graph analytics of all notes of a vault.
+#[derive(Debug, Clone, Default)]
+pub struct VaultAnalytics {
+    pub(super) notes: Vec<(types::ResourceId, NoteAnalytics)>,
+    pub(super) index: HashMap<types::ResourceId, usize>,
+    pub(super) weak_components: Vec<Vec<types::ResourceId>>,
+    pub(super) strong_components: Vec<Vec<types::ResourceId>>,
+}
+
+impl VaultAnalytics {
+    /// Returns the analytics of a note or None if there is no such note.
+    pub fn get(&self, rid: &types::ResourceId) -> Option<&NoteAnalytics> {
+        self.index.get(rid).map(|f| &self.notes[*f].1)
+    }
+
+    /// Returns the notes which neither link to other notes nor are linked.
+    pub fn orphans(&self) -> Vec<types::ResourceId> {
+        self.filter(|f| f.is_orphan())
+    }
+
+    /// Returns the notes which are linked but don't link to other notes.
+    pub fn dead_ends(&self) -> Vec<types::ResourceId> {
+        self.filter(|f| f.is_dead_end())
+    }
+
+    /// Returns the sets of notes which are connected if the direction of the
+    /// links is ignored, ordered by descending size.
+    pub fn weak_components(&self) -> &[Vec<types::ResourceId>] {
+        &self.weak_components
+    }
+
+    /// Returns the sets of notes in which every note can be reached from every
+    /// other note, ordered by descending size. Notes which aren't part of a
+    /// cycle form a component of their own.
+    pub fn strong_components(&self) -> &[Vec<types::ResourceId>] {
+        &self.strong_components
+    }
+
+    /// Returns all notes ordered by descending in degree. Notes with the same
+    /// in degree are ordered by descending out degree.
+    pub fn degree_ranking(&self) -> Vec<(types::ResourceId, NoteAnalytics)> {
+        let mut ranking = self.notes.clone();
+        ranking.sort_by(|(_, a), (_, b)| {
+            (b.in_degree, b.out_degree).cmp(&(a.in_degree, a.out_degree))
+        });
+        ranking
+    }
+
+    /// Returns all notes ordered by descending PageRank.
+    pub fn page_rank_ranking(&self) -> Vec<(types::ResourceId, NoteAnalytics)> {
+        let mut ranking = self.notes.clone();
+        ranking.sort_by(|(_, a), (_, b)| b.page_rank.total_cmp(&a.page_rank));
+        ranking
+    }
+
+    fn filter(&self, predicate: impl Fn(&NoteAnalytics) -> bool) -> Vec<types::ResourceId> {
+        self.notes
+            .iter()
+            .filter(|(_, f)| predicate(f))
+            .map(|(rid, _)| rid.clone())
+            .collect()
+    }
+}
@@ -1,93 +1,319 @@
 use super::adapters;
-use super::error::Result;
+use super::analytics;
+use super::emerald_options::EmeraldOptions;
+use super::error::{EmeraldError, Result};
+use super::graph;
 use super::markdown;
+use super::model::block_id;
 use super::model::content;
+use super::model::heading;
 use super::model::link;
 use super::model::note;
 use super::model::note_updater;
 use super::model::resource;
 use super::model::resource_id_resolver;
+use super::model::tag;
+use super::model::task;
 use super::model::unique_id;
 use super::model::vault;
 use super::resources;
 use super::stats;
+use super::traversal;
 use super::types;
+use crate::adapters::to_outside::{GetBacklinks, GetEmbeds, GetLinks};
+use crate::model::content::MdContentRetriever;
+use crate::model::note::NoteMetadataRetriever;
 use crate::model::note::NotesIterSrc;
+use crate::model::resource::{ResourceIterSrc, ResourceMetadataRetriever};
+use crate::model::resource_id_resolver::AliasConflictRetriever;
+use crate::model::resource_id_resolver::ResourceIdResolver;
+use crate::model::tag::TagRetriever;
+use crate::model::task::TaskRetriever;
 use crate::model::unique_id::UidRetriever;
 use crate::model::vault::Vault;
-use crate::resources::FsMetadataAccessImpl;
+use crate::resources::FilesystemMetadataLoader;
 use crate::Note;
 use crate::NoteTypes;
 use crate::Uid;
 
 #[allow(unused_imports)]
 use log::{debug, error, info, trace, warn};
-use std::iter::zip;
-use std::rc::Rc;
-use std::{path::Path, time::Instant};
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::iter::{once, zip};
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// Number of notes handed to a worker thread at once while loading a vault.
+const LOAD_CHUNK_SIZE: usize = 64;
 
 #[allow(dead_code)]
 pub struct DefaultEmerald {
-    pub vault: vault::VaultImpl<unique_id::Uid>,
     pub stats: stats::VaultStats,
-    pub nmod: Rc<note::DefaultNoteModel>,
-    pub lmod: Rc<link::DefaultLinkModel>,
-    pub n_updater: note_updater::NoteUpdater<types::ResourceId>,
-    pub uid_mod: Rc<unique_id::UniqueId<types::ResourceId>>,
-    pub get_links: Rc<dyn adapters::to_outside::GetLinks>,
-    pub get_backlinks: Rc<dyn adapters::to_outside::GetBacklinks>,
+    pub nmod: Arc<note::DefaultNoteModel>,
+    pub cmod: Arc<content::DefaultContentModel>,
+    pub lmod: Arc<link::DefaultLinkModel>,
+    pub rmod: Arc<resource::DefaultResourceModel>,
+    pub hmod: Arc<heading::DefaultHeadingModel>,
+    pub bmod: Arc<block_id::DefaultBlockIdModel>,
+    pub tmod: Arc<tag::DefaultTagModel>,
+    pub task_mod: Arc<task::DefaultTaskModel>,
+    pub lrmod: Arc<resource_id_resolver::DefaultResourceIdResolverModel>,
+    pub uid_mod: Arc<unique_id::UniqueId<types::ResourceId>>,
+    pub content_writer: Arc<dyn resources::ContentWriter>,
+    pub resource_mover: Arc<dyn resources::ResourceMover>,
+    pub storage: Arc<dyn resources::VaultStorage>,
+    options: EmeraldOptions,
+    diagnostics: Vec<types::Diagnostic>,
+    analytics: OnceLock<analytics::VaultAnalytics>,
+    /// Start of the last scan in seconds since the unix epoch.
+    loaded_at: i64,
 }
 
-impl DefaultEmerald {
-    pub fn new(vault_path: &Path) -> Result<DefaultEmerald> {
-        // Build dependency root
-        let start = Instant::now();
-        let mut path_list = resources::get_path_list(vault_path)?;
-        path_list.sort_by(|a, b| a.file_stem().cmp(&b.file_stem()));
-        let all_ros_vec: Vec<_> = resources::adapter_to_ro(path_list).collect();
-        let elapsed = start.elapsed();
-        debug!("Creation of ResourceObject vec: {:?}", elapsed);
+type FsMetadataList = Vec<(types::ResourceId, types::FilesystemMetadata)>;
+type ContentList = Vec<(types::ResourceId, types::Content)>;
 
-        let start = Instant::now();
-        let ros_rids: Vec<_> = resources::adapter_to_ro_and_rid(&all_ros_vec, vault_path).collect();
-        let elapsed = start.elapsed();
-        debug!(
-            "Creation of (ResourceObject, ResourceId) vec: {:?}",
-            elapsed
-        );
+/// Models holding the loaded state of a vault.
+struct VaultModels {
+    nmod: Arc<note::DefaultNoteModel>,
+    cmod: Arc<content::DefaultContentModel>,
+    lmod: Arc<link::DefaultLinkModel>,
+    rmod: Arc<resource::DefaultResourceModel>,
+    hmod: Arc<heading::DefaultHeadingModel>,
+    bmod: Arc<block_id::DefaultBlockIdModel>,
+    tmod: Arc<tag::DefaultTagModel>,
+    task_mod: Arc<task::DefaultTaskModel>,
+    lrmod: Arc<resource_id_resolver::DefaultResourceIdResolverModel>,
+}
 
-        let start = Instant::now();
-        let ro_retriever = resources::ResourceObjectMap::new(&ros_rids);
-        let elapsed = start.elapsed();
-        debug!("Creation of ResourceObjectMap: {:?}", elapsed);
+fn file_stem(rid: &types::ResourceId) -> Option<&std::ffi::OsStr> {
+    Path::new(rid.0.as_ref()).file_stem()
+}
 
-        let start = Instant::now();
-        let all_vec: Vec<_> = ros_rids.into_iter().map(|(_, rid)| rid).collect();
-        let elapsed = start.elapsed();
-        debug!("Creation of ResourceId vec: {:?}", elapsed);
+/// Returns the current time in seconds since the unix epoch.
+fn unix_time_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |f| f.as_secs() as i64)
+}
 
-        let start = Instant::now();
-        let content_loader = resources::FileContentLoader::new(ro_retriever.clone());
-        let elapsed = start.elapsed();
-        debug!("Creation of FileContentLoader: {:?}", elapsed);
+/// Lists all resources of the vault and loads their filesystem meta data.
+fn scan_vault(
+    storage: &Arc<dyn resources::VaultStorage>,
+    options: &EmeraldOptions,
+) -> Result<(FsMetadataList, Vec<types::Diagnostic>)> {
+    let start = Instant::now();
+    let mut all_vec = storage.list()?;
+    all_vec.sort_by(|a, b| file_stem(a).cmp(&file_stem(b)));
+    let elapsed = start.elapsed();
+    debug!("Listing of resources: {:?}", elapsed);
 
-        let start = Instant::now();
-        let fs_meta_data_loader = resources::FilesystemMetadataLoaderImpl::new(
-            ro_retriever.clone(),
-            FsMetadataAccessImpl(),
-        );
-        let elapsed = start.elapsed();
-        debug!("Creation of FilesystemMetadataLoader: {:?}", elapsed);
+    let start = Instant::now();
+    let fs_meta_data_loader = resources::FilesystemMetadataLoaderImpl::new(storage.clone());
+    let (all_fs_meta_data, diagnostics) =
+        load_fs_metadata(all_vec, &fs_meta_data_loader, options.lenient)?;
+    let elapsed = start.elapsed();
+    debug!("Loading of filesystem meta data: {:?}", elapsed);
 
-        let start = Instant::now();
+    Ok((all_fs_meta_data, diagnostics))
+}
 
-        // load all meta data and ensure that there were no errors
-        let all_fs_meta_data: Vec<_> = adapters::adapter_to_rid_and_filesystem_metadata(
-            all_vec.clone(),
-            &fs_meta_data_loader,
-        )?
+/// Returns the path the filesystem metadata paths of the storage are relative to.
+fn common_path(storage: &dyn resources::VaultStorage) -> &Path {
+    storage.root().unwrap_or(Path::new(""))
+}
+
+/// Loads the filesystem meta data of the resources. Resources whose meta data
+/// can't be read fail the load, unless it is lenient. Then they are skipped.
+fn load_fs_metadata(
+    rids: Vec<types::ResourceId>,
+    meta_data_loader: &impl resources::FilesystemMetadataLoader,
+    lenient: bool,
+) -> Result<(FsMetadataList, Vec<types::Diagnostic>)> {
+    if !lenient {
+        let all_fs_meta_data =
+            adapters::adapter_to_rid_and_filesystem_metadata(rids, meta_data_loader)?.collect();
+        return Ok((all_fs_meta_data, vec![]));
+    }
+
+    let mut all_fs_meta_data = Vec::with_capacity(rids.len());
+    let mut diagnostics = Vec::new();
+    for rid in rids {
+        match meta_data_loader.load(&rid) {
+            Ok(meta_data) => all_fs_meta_data.push((rid, meta_data)),
+            Err(err) => diagnostics.push(types::Diagnostic {
+                path: rid,
+                kind: types::DiagnosticKind::UnreadableMetadata,
+                message: err.to_string(),
+                span: None,
+            }),
+        }
+    }
+    Ok((all_fs_meta_data, diagnostics))
+}
+
+/// Loads the contents of the notes in parallel. Notes which can't be read fail
+/// the load, unless it is lenient. Then invalid UTF-8 is replaced and
+/// unreadable notes are treated as empty.
+fn load_contents(
+    rids: &[types::ResourceId],
+    content_loader: &(impl resources::ContentLoader + Sync),
+    lenient: bool,
+) -> Result<(ContentList, Vec<types::Diagnostic>)> {
+    if !lenient {
+        let contents = rids
+            .par_chunks(LOAD_CHUNK_SIZE)
+            .map(|rids| {
+                resources::adapter_to_rid_and_content(rids, content_loader)
+                    .map(Iterator::collect::<Vec<_>>)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        return Ok((contents.into_iter().flatten().collect(), vec![]));
+    }
+
+    let loaded: Vec<_> = rids
+        .par_chunks(LOAD_CHUNK_SIZE)
+        .flat_map_iter(|rids| {
+            rids.iter()
+                .map(|rid| (rid.clone(), content_loader.load_lossy(rid)))
+        })
         .collect();
 
+    let mut contents = Vec::with_capacity(loaded.len());
+    let mut diagnostics = Vec::new();
+    for (rid, res) in loaded {
+        let (content, diagnostic) = match res {
+            Ok((content, None)) => (content, None),
+            Ok((content, Some(span))) => (
+                content,
+                Some(types::Diagnostic {
+                    path: rid.clone(),
+                    kind: types::DiagnosticKind::InvalidUtf8,
+                    message: "Invalid UTF-8 sequences were replaced".into(),
+                    span: Some(span),
+                }),
+            ),
+            Err(err) => (
+                types::Content::from(""),
+                Some(types::Diagnostic {
+                    path: rid.clone(),
+                    kind: types::DiagnosticKind::UnreadableContent,
+                    message: err.to_string(),
+                    span: None,
+                }),
+            ),
+        };
+        contents.push((rid, content));
+        diagnostics.extend(diagnostic);
+    }
+    Ok((contents, diagnostics))
+}
+
+/// Parses the frontmatter of the notes in parallel. Invalid frontmatter is
+/// treated as missing and reported.
+fn extract_document_metadata(
+    contents: &[(types::ResourceId, types::Content)],
+) -> (
+    Vec<(types::ResourceId, types::DocumentMetadata)>,
+    Vec<types::Diagnostic>,
+) {
+    let md_analyzer = markdown::DefaultMarkdownFrontmatterSplitter::new();
+    let extracted: Vec<_> = contents
+        .par_chunks(LOAD_CHUNK_SIZE)
+        .flat_map_iter(|contents| {
+            let c_it = contents.iter().map(|f| (f.0.clone(), f.1 .0.as_str()));
+            adapters::adapter_to_rid_and_document_metadata(c_it, md_analyzer).collect::<Vec<_>>()
+        })
+        .collect();
+
+    let mut diagnostics = Vec::new();
+    let doc_meta_data = extracted
+        .into_iter()
+        .map(|(rid, doc_meta_data, diagnostic)| {
+            diagnostics.extend(diagnostic);
+            (rid, doc_meta_data)
+        })
+        .collect();
+    (doc_meta_data, diagnostics)
+}
+
+fn extract_headings(
+    rids: &[types::ResourceId],
+    cmod: &content::DefaultContentModel,
+) -> Vec<(types::ResourceId, Vec<types::Heading>)> {
+    let md_analyzer = markdown::MarkdownAnalyzerImpl::new();
+    let c_it = adapters::adapter_to_rids_and_content(rids, cmod);
+    adapters::adapter_to_rid_and_headings(c_it, md_analyzer).collect()
+}
+
+fn extract_block_ids(
+    rids: &[types::ResourceId],
+    cmod: &content::DefaultContentModel,
+) -> Vec<(types::ResourceId, Vec<String>)> {
+    let md_analyzer = markdown::MarkdownAnalyzerImpl::new();
+    let c_it = adapters::adapter_to_rids_and_content(rids, cmod);
+    adapters::adapter_to_rid_and_block_ids(c_it, md_analyzer).collect()
+}
+
+fn extract_tags(
+    rids: &[types::ResourceId],
+    cmod: &content::DefaultContentModel,
+    nmod: &note::DefaultNoteModel,
+) -> Vec<(types::ResourceId, Vec<String>)> {
+    let md_analyzer = markdown::MarkdownAnalyzerImpl::new();
+    let c_it = adapters::adapter_to_rids_and_content(rids, cmod);
+    adapters::adapter_to_rid_and_tags(c_it, md_analyzer, nmod).collect()
+}
+
+fn extract_tasks(
+    rids: &[types::ResourceId],
+    cmod: &content::DefaultContentModel,
+) -> Vec<(types::ResourceId, Vec<types::Task>)> {
+    let md_analyzer = markdown::MarkdownAnalyzerImpl::new();
+    let c_it = adapters::adapter_to_rids_and_content(rids, cmod);
+    adapters::adapter_to_rid_and_tasks(c_it, md_analyzer).collect()
+}
+
+/// Parses and resolves the links of the notes in parallel.
+fn extract_links(
+    rids: &[types::ResourceId],
+    cmod: &content::DefaultContentModel,
+    lrmod: &resource_id_resolver::DefaultResourceIdResolverModel,
+    hmod: &heading::DefaultHeadingModel,
+    bmod: &block_id::DefaultBlockIdModel,
+) -> Vec<types::LinkSrc2Tgt> {
+    let md_analyzer = markdown::MarkdownAnalyzerImpl::new();
+    rids.par_chunks(LOAD_CHUNK_SIZE)
+        .flat_map_iter(|rids| {
+            let c_it = adapters::adapter_to_rids_and_content(rids, cmod);
+            let ct_it = adapters::adapter_to_rid_and_content_type(c_it, md_analyzer);
+            adapters::adapter_to_link_src_2_tgt(ct_it, lrmod, hmod, bmod).collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+impl DefaultEmerald {
+    pub fn new(vault_path: &Path) -> Result<DefaultEmerald> {
+        Self::with_options(vault_path, &EmeraldOptions::default())
+    }
+
+    pub fn with_options(vault_path: &Path, options: &EmeraldOptions) -> Result<DefaultEmerald> {
+        let storage = resources::FileVaultStorage::new(vault_path, options.scan.clone());
+        Self::with_storage(Arc::new(storage), options)
+    }
+
+    /// Loads the vault from any storage. The scan options are ignored since the
+    /// storage determines which resources belong to the vault.
+    pub fn with_storage(
+        storage: Arc<dyn resources::VaultStorage>,
+        options: &EmeraldOptions,
+    ) -> Result<DefaultEmerald> {
+        // Build dependency root
+        let loaded_at = unix_time_now();
+        let (all_fs_meta_data, mut diagnostics) = scan_vault(&storage, options)?;
+
+        let start = Instant::now();
         let md_fs_meta_data: Vec<_> =
             adapters::filter_rid_and_meta_data(&all_fs_meta_data).collect();
 
@@ -96,105 +322,165 @@ impl DefaultEmerald {
         debug!("Creation of ResourceId md vec: {:?}", elapsed);
 
         let start = Instant::now();
-        let md_content_vec =
-            resources::adapter_to_rid_and_content(md_rids.iter(), &content_loader)?;
-        let cmod = Rc::new(content::DefaultContentModel::new(md_content_vec));
-        let elapsed = start.elapsed();
-        debug!("Creation of DefaultContentModel: {:?}", elapsed);
+        let content_loader = resources::StorageContentLoader::new(storage.clone());
+        let (contents, content_diagnostics) =
+            load_contents(&md_rids, &content_loader, options.lenient)?;
+        diagnostics.extend(content_diagnostics);
 
-        let start = Instant::now();
-        let md_analyzer = markdown::DefaultMarkdownFrontmatterSplitter::new();
-        let c_it = adapters::adapter_to_rids_and_content(md_rids.iter(), cmod.as_ref())
-            .map(|f| (f.0, f.1 .0.as_str()));
-        let ct_it = adapters::adapter_to_rid_and_yaml(c_it, md_analyzer);
-        let md_doc_meta_data: Vec<_> =
-            adapters::adapter_to_rid_and_document_metadata(ct_it).collect();
-        let elapsed = start.elapsed();
+        let start_yaml = Instant::now();
+        let (md_doc_meta_data, yaml_diagnostics) = extract_document_metadata(&contents);
+        diagnostics.extend(yaml_diagnostics);
+        let elapsed = start_yaml.elapsed();
         debug!("YAML extraction: {:?}", elapsed);
 
+        let cmod = Arc::new(content::DefaultContentModel::new(contents));
+        let elapsed = start.elapsed();
+        debug!("Creation of DefaultContentModel: {:?}", elapsed);
+
         let start = Instant::now();
         let resource_loc_iter = adapters::to_resource_id_resolver::convert_to_resource_locations(
             &all_fs_meta_data,
-            vault_path,
+            common_path(storage.as_ref()),
         );
-        let lrmod = Rc::new(resource_id_resolver::DefaultResourceIdResolverModel::new(
+        let alias_iter = adapters::to_resource_id_resolver::convert_to_aliases(&md_doc_meta_data);
+        let lrmod = Arc::new(resource_id_resolver::DefaultResourceIdResolverModel::new(
             resource_loc_iter,
+            alias_iter,
         ));
         let elapsed = start.elapsed();
         debug!("Creation of DefaultResourceIdResolverModel: {:?}", elapsed);
 
         let start = Instant::now();
-        let rmod = Rc::new(resource::DefaultResourceModel::new(&all_fs_meta_data));
+        let rmod = Arc::new(resource::DefaultResourceModel::new(&all_fs_meta_data));
         let elapsed = start.elapsed();
         debug!("Creation of DefaultResourceModel: {:?}", elapsed);
 
         let start = Instant::now();
-        let md_analyzer = markdown::MarkdownAnalyzerImpl::new();
-        let c_it = adapters::adapter_to_rids_and_content(md_rids.iter(), cmod.as_ref());
-        let ct_it = adapters::adapter_to_rid_and_content_type(c_it, md_analyzer);
-        let s2t_idx: Vec<_> = adapters::adapter_to_link_src_2_tgt(ct_it, lrmod.as_ref()).collect();
+        let hmod = Arc::new(heading::DefaultHeadingModel::new(extract_headings(
+            &md_rids,
+            cmod.as_ref(),
+        )));
+        let elapsed = start.elapsed();
+        debug!("Creation of DefaultHeadingModel: {:?}", elapsed);
+
+        let start = Instant::now();
+        let bmod = Arc::new(block_id::DefaultBlockIdModel::new(extract_block_ids(
+            &md_rids,
+            cmod.as_ref(),
+        )));
+        let elapsed = start.elapsed();
+        debug!("Creation of DefaultBlockIdModel: {:?}", elapsed);
+
+        let start = Instant::now();
+        let s2t_idx = extract_links(
+            &md_rids,
+            cmod.as_ref(),
+            lrmod.as_ref(),
+            hmod.as_ref(),
+            bmod.as_ref(),
+        );
         let elapsed = start.elapsed();
         debug!("Link and Backlink extraction: {:?}", elapsed);
 
         let start = Instant::now();
-        let lmod = Rc::new(link::DefaultLinkModel::new(s2t_idx));
+        let lmod = Arc::new(link::DefaultLinkModel::new(s2t_idx));
         let elapsed = start.elapsed();
         debug!("Creation of DefaultLinkModel: {:?}", elapsed);
 
         let start = Instant::now();
-        let md_meta_data = zip(md_fs_meta_data.clone(), md_doc_meta_data).map(|f| {
+        let md_meta_data = zip(md_fs_meta_data, md_doc_meta_data).map(|f| {
             assert!(f.0 .0 == f.1 .0); // ensure that rids are the same.
             (f.0 .0, f.0 .1, f.1 .1)
         });
-        let nmod = Rc::new(note::DefaultNoteModel::new(md_meta_data));
+        let nmod = Arc::new(note::DefaultNoteModel::new(md_meta_data));
         let elapsed = start.elapsed();
         debug!("Creation of DefaultNoteModel: {:?}", elapsed);
 
         let start = Instant::now();
-        let uid_metadata_retriever_adapter = Rc::new(
-            adapters::to_unique_id::UidMetadataRetrieverAdapter::new(nmod.clone()),
-        );
-        let uid_mod = Rc::new(unique_id::UniqueId::new(
-            nmod.create_iter(),
-            uid_metadata_retriever_adapter,
-        ));
-
+        let tmod = Arc::new(tag::DefaultTagModel::new(extract_tags(
+            &md_rids,
+            cmod.as_ref(),
+            nmod.as_ref(),
+        )));
         let elapsed = start.elapsed();
-        debug!("Creation of UiniqueId: {:?}", elapsed);
+        debug!("Creation of DefaultTagModel: {:?}", elapsed);
 
         let start = Instant::now();
-        let md_retriever_adapter = Rc::new(adapters::to_vault::NoteMetadataRetriever::new(
-            nmod.clone(),
-            uid_mod.clone(),
-        ));
-        let content_retriever_adapter = Rc::new(
-            adapters::to_vault::MdContentRetrieverAdapter::new(cmod.clone(), uid_mod.clone()),
-        );
+        let task_mod = Arc::new(task::DefaultTaskModel::new(extract_tasks(
+            &md_rids,
+            cmod.as_ref(),
+        )));
+        let elapsed = start.elapsed();
+        debug!("Creation of DefaultTaskModel: {:?}", elapsed);
 
-        let get_backlinks_adapter = Rc::new(adapters::to_outside::GetBacklinksAdapter::new(
-            lmod.clone(),
-            rmod.clone(),
-        ));
+        Self::assemble(
+            storage,
+            options,
+            VaultModels {
+                nmod,
+                cmod,
+                lmod,
+                rmod,
+                hmod,
+                bmod,
+                tmod,
+                task_mod,
+                lrmod,
+            },
+            diagnostics,
+            loaded_at,
+        )
+    }
 
-        let get_links_adapter = Rc::new(adapters::to_outside::GetLinksAdapter::new(
-            lmod.clone(),
-            rmod.clone(),
-        ));
+    /// Returns the directory the vault was loaded from. Vaults which aren't
+    /// stored in the filesystem have none.
+    pub fn vault_path(&self) -> Option<&Path> {
+        self.storage.root()
+    }
+
+    /// Creates the uids and the statistics on top of the models.
+    fn assemble(
+        storage: Arc<dyn resources::VaultStorage>,
+        options: &EmeraldOptions,
+        models: VaultModels,
+        mut diagnostics: Vec<types::Diagnostic>,
+        loaded_at: i64,
+    ) -> Result<DefaultEmerald> {
+        let VaultModels {
+            nmod,
+            cmod,
+            lmod,
+            rmod,
+            hmod,
+            bmod,
+            tmod,
+            task_mod,
+            lrmod,
+        } = models;
 
-        let vault = vault::VaultImpl::new(md_retriever_adapter, content_retriever_adapter);
+        let start = Instant::now();
+        let content_writer = Arc::new(resources::StorageContentWriter::new(storage.clone()));
+        let elapsed = start.elapsed();
+        debug!("Creation of StorageContentWriter: {:?}", elapsed);
 
+        let start = Instant::now();
+        let resource_mover = Arc::new(resources::StorageResourceMover::new(storage.clone()));
         let elapsed = start.elapsed();
-        debug!("Creation of Vault: {:?}", elapsed);
+        debug!("Creation of StorageResourceMover: {:?}", elapsed);
 
         let start = Instant::now();
-        let content_retriever_adapter = Rc::new(
-            adapters::to_note_updater::MdContentRetrieverAdapter::new(cmod.clone()),
-        );
+        let uid_metadata_retriever_adapter =
+            adapters::to_unique_id::UidMetadataRetrieverAdapter::new(nmod.clone(), cmod.clone());
+        let uid_mod = Arc::new(unique_id::UniqueId::new(
+            nmod.create_iter(),
+            &uid_metadata_retriever_adapter,
+            options.uid_strategy,
+        )?);
 
-        let note_updater =
-            note_updater::NoteUpdater::<types::ResourceId>::new(content_retriever_adapter);
         let elapsed = start.elapsed();
-        debug!("Creation of NoteUpdater: {:?}", elapsed);
+        debug!("Creation of UiniqueId: {:?}", elapsed);
+        diagnostics.sort_by(|a, b| a.path.0.cmp(&b.path.0));
+
         // -----
         // Aquire stats
         let link_stats = stats::extract_link_stats(lmod.as_ref());
@@ -205,19 +491,84 @@ impl DefaultEmerald {
         };
         // -------
         Ok(DefaultEmerald {
-            vault,
             stats: vault_stats,
             nmod,
-            n_updater: note_updater,
+            cmod,
             uid_mod,
             lmod,
-            get_links: get_links_adapter,
-            get_backlinks: get_backlinks_adapter,
+            rmod,
+            hmod,
+            bmod,
+            tmod,
+            task_mod,
+            lrmod,
+            content_writer,
+            resource_mover,
+            storage,
+            options: options.clone(),
+            diagnostics,
+            analytics: OnceLock::new(),
+            loaded_at,
         })
     }
+
+    /// Creates the vault on top of the current models.
+    fn vault(&self) -> vault::VaultImpl<unique_id::Uid> {
+        let md_retriever_adapter = Arc::new(adapters::to_vault::NoteMetadataRetriever::new(
+            self.nmod.clone(),
+            self.uid_mod.clone(),
+        ));
+        let content_retriever_adapter =
+            Arc::new(adapters::to_vault::MdContentRetrieverAdapter::new(
+                self.cmod.clone(),
+                self.uid_mod.clone(),
+            ));
+        vault::VaultImpl::new(md_retriever_adapter, content_retriever_adapter)
+    }
+
+    fn note_updater(&self) -> note_updater::NoteUpdater<types::ResourceId> {
+        let content_retriever_adapter = Arc::new(
+            adapters::to_note_updater::MdContentRetrieverAdapter::new(self.cmod.clone()),
+        );
+        note_updater::NoteUpdater::<types::ResourceId>::new(content_retriever_adapter)
+    }
+
+    fn uid_metadata_retriever(&self) -> adapters::to_unique_id::UidMetadataRetrieverAdapter {
+        adapters::to_unique_id::UidMetadataRetrieverAdapter::new(
+            self.nmod.clone(),
+            self.cmod.clone(),
+        )
+    }
+
+    fn get_links_adapter(&self) -> adapters::to_outside::GetLinksAdapter {
+        adapters::to_outside::GetLinksAdapter::new(self.lmod.clone(), self.rmod.clone())
+    }
+
+    /// Maps the results of a link query to the notes and resources they point to.
+    fn to_note_types(
+        &self,
+        link_iter: Box<dyn Iterator<Item = adapters::to_outside::LinkQueryResult> + 'static>,
+    ) -> Box<dyn Iterator<Item = vault::NoteTypes<types::ResourceId, unique_id::Uid>> + 'static>
+    {
+        let uid_mod_clone = self.uid_mod.clone();
+        let vault = self.vault();
+        Box::new(link_iter.map(move |f| match f {
+            adapters::to_outside::LinkQueryResult::LinkToNote(rid) => {
+                let link_uid = uid_mod_clone.get_uid_from_rid(&rid).expect("Should exist");
+                NoteTypes::Note(vault.get_note(link_uid))
+            }
+            adapters::to_outside::LinkQueryResult::LinkToResource(rid) => {
+                NoteTypes::ResourceRef(rid)
+            }
+            adapters::to_outside::LinkQueryResult::LinkToMissingAnchor(rid) => {
+                let link_uid = uid_mod_clone.get_uid_from_rid(&rid).expect("Should exist");
+                NoteTypes::AnchorMissing(vault.get_note(link_uid))
+            }
+        }))
+    }
 }
 
-pub trait Emerald {
+pub trait Emerald: Send + Sync {
     fn flat_iter(&self) -> std::vec::IntoIter<vault::Note<unique_id::Uid>>;
 
     /// Returns an iterator over links contained in the specified Note.
@@ -240,13 +591,531 @@ pub trait Emerald {
         note: &vault::Note<unique_id::Uid>,
     ) -> Box<dyn Iterator<Item = vault::NoteTypes<types::ResourceId, unique_id::Uid>> + 'static>;
 
-    fn update_note(&self, rid: &types::ResourceId, value: &str) -> String;
+    /// Returns an iterator over notes and resources embedded in the specified Note.
+    ///
+    /// # Arguments
+    ///
+    /// * `note`: Note.
+    fn get_embeds_of(
+        &self,
+        note: &vault::Note<unique_id::Uid>,
+    ) -> Box<dyn Iterator<Item = vault::NoteTypes<types::ResourceId, unique_id::Uid>> + 'static>;
+
+    /// Returns the outline of the specified Note.
+    ///
+    /// The outline is a tree of the headings of the note. The spans of the headings
+    /// relate to the complete content of the note including its frontmatter.
+    ///
+    /// # Arguments
+    ///
+    /// * `note`: Note.
+    fn get_outline_of(&self, note: &vault::Note<unique_id::Uid>) -> Vec<types::OutlineNode>;
+
+    /// Returns the tags of the specified Note.
+    ///
+    /// The tags of the frontmatter are followed by the inline tags of the note.
+    /// Tags are returned without their leading `#`.
+    ///
+    /// # Arguments
+    ///
+    /// * `note`: Note.
+    fn get_tags_of(&self, note: &vault::Note<unique_id::Uid>) -> Vec<String>;
+
+    /// Returns all notes tagged with `tag` or one of its child tags.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag`: Tag with or without leading `#` like `project` or `#project/alpha`.
+    fn get_notes_with_tag(&self, tag: &str) -> std::vec::IntoIter<vault::Note<unique_id::Uid>>;
+
+    /// Returns all tags of the vault including their parent tags together with the
+    /// number of notes tagged with the tag or one of its child tags. Parent tags are
+    /// ordered before their children.
+    fn get_tag_counts(&self) -> Vec<(String, usize)>;
+
+    /// Returns the tasks of the specified Note in document order.
+    ///
+    /// # Arguments
+    ///
+    /// * `note`: Note.
+    fn get_tasks_of(&self, note: &vault::Note<unique_id::Uid>) -> Vec<types::Task>;
+
+    /// Returns all tasks of the vault matching the filter together with the note
+    /// containing them.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter`: Criteria the tasks must match.
+    fn get_tasks(
+        &self,
+        filter: &types::TaskFilter,
+    ) -> Vec<(vault::Note<unique_id::Uid>, types::Task)>;
+
+    /// Writes the uid into the frontmatter of the note and returns the updated content.
+    ///
+    /// # Arguments
+    ///
+    /// * `rid`: Resource id of the note.
+    /// * `value`: Uid of the note.
+    fn update_note(&self, rid: &types::ResourceId, value: &str) -> Result<String>;
+
+    /// Applies the command to the frontmatter of the note and returns the updated
+    /// content. The note itself isn't changed; use `write_note` to store the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `rid`: Resource id of the note.
+    /// * `cmd`: Change of the frontmatter.
+    fn update_note_with(
+        &self,
+        rid: &types::ResourceId,
+        cmd: note_updater::NoteUpdateCommand,
+    ) -> Result<String>;
+
+    /// Returns the content of the note as it was loaded.
+    ///
+    /// # Arguments
+    ///
+    /// * `rid`: Resource id of the note.
+    fn get_content(&self, rid: &types::ResourceId) -> &str;
+
+    /// Replaces the content of the note on disk. The file is replaced atomically.
+    ///
+    /// # Arguments
+    ///
+    /// * `rid`: Resource id of the note.
+    /// * `content`: New content of the note.
+    fn write_note(&self, rid: &types::ResourceId, content: &str) -> Result<()>;
+
+    /// Returns the notes whose content changes when a note is renamed together
+    /// with their new content. Nothing is written.
+    ///
+    /// # Arguments
+    ///
+    /// * `rid`: Resource id of the note.
+    /// * `new_rid`: New resource id of the note.
+    fn plan_rename(
+        &self,
+        rid: &types::ResourceId,
+        new_rid: &types::ResourceId,
+    ) -> Result<Vec<(types::ResourceId, String)>>;
+
+    /// Moves a note and rewrites every link pointing to it. The rename is refused
+    /// if the new name is already used by another note or alias, since links to
+    /// it would become ambiguous. The loaded vault is not updated.
+    ///
+    /// # Arguments
+    ///
+    /// * `rid`: Resource id of the note.
+    /// * `new_rid`: New resource id of the note.
+    fn rename_note(&self, rid: &types::ResourceId, new_rid: &types::ResourceId) -> Result<()>;
 
     fn get_resource_id(&self, note: &vault::Note<unique_id::Uid>) -> Option<types::ResourceId>;
     fn file_count(&self) -> usize;
     fn md_file_count(&self) -> usize;
     fn valid_backlink_count(&self) -> usize;
     fn invalid_backlink_count(&self) -> usize;
+
+    /// Returns the number of links whose target exists but doesn't contain the
+    /// section or block the link points to.
+    fn anchor_missing_backlink_count(&self) -> usize;
+
+    /// Returns the aliases which can't be used in links because they match the
+    /// filename of another note.
+    fn get_alias_conflicts(&self) -> Vec<types::AliasConflict>;
+
+    /// Returns all links whose target doesn't exist together with their position
+    /// and the names of similar notes. Links with a missing section or block are
+    /// not part of the result.
+    fn get_broken_links(&self) -> Vec<types::BrokenLink>;
+
+    /// Returns the problems which were found while loading the vault, ordered by
+    /// path. Without the lenient option only invalid frontmatter is reported,
+    /// since other problems fail the load.
+    fn get_diagnostics(&self) -> &[types::Diagnostic];
+
+    /// Returns the link graph of the vault. Use `Graph::write` to export it.
+    ///
+    /// # Arguments
+    ///
+    /// * `options`: Determines whether attachments and broken link targets are
+    ///   part of the graph.
+    fn get_graph(&self, options: &graph::GraphOptions) -> graph::Graph;
+
+    /// Returns the analytics of the link graph: orphans, dead ends, connected
+    /// components, degrees and PageRank of the notes.
+    fn get_analytics(&self) -> &analytics::VaultAnalytics;
+
+    /// Returns the occurrences of the title and the aliases of a note in all other
+    /// notes which are not part of a link, a code block or the frontmatter. The
+    /// search ignores case and only matches whole words.
+    ///
+    /// # Arguments
+    ///
+    /// * `rid`: Resource id of the mentioned note.
+    fn get_unlinked_mentions(&self, rid: &types::ResourceId) -> Result<Vec<types::Mention>>;
+
+    /// Returns the notes whose content changes when the unlinked mentions of a
+    /// note are turned into wiki links together with their new content. Nothing
+    /// is written.
+    ///
+    /// # Arguments
+    ///
+    /// * `rid`: Resource id of the mentioned note.
+    fn plan_link_mentions(
+        &self,
+        rid: &types::ResourceId,
+    ) -> Result<Vec<(types::ResourceId, String)>>;
+
+    /// Returns the resources which can be reached from a note by following at
+    /// most `depth` links. Each resource is returned once with its distance to
+    /// the note, closest resources first.
+    ///
+    /// # Arguments
+    ///
+    /// * `rid`: Resource id of the note.
+    /// * `depth`: Maximal number of links to follow.
+    /// * `direction`: Follow links, backlinks or both.
+    fn get_neighbours(
+        &self,
+        rid: &types::ResourceId,
+        depth: usize,
+        direction: traversal::Direction,
+    ) -> Vec<traversal::Neighbour>;
+
+    /// Returns the shortest chain of links between two notes including both
+    /// notes, or None if there is no such chain.
+    ///
+    /// # Arguments
+    ///
+    /// * `from`: Resource id of the first note.
+    /// * `to`: Resource id of the last note.
+    /// * `direction`: Follow links, backlinks or both.
+    fn get_shortest_path(
+        &self,
+        from: &types::ResourceId,
+        to: &types::ResourceId,
+        direction: traversal::Direction,
+    ) -> Option<Vec<types::ResourceId>>;
+
+    /// Rescans the vault and reloads only the resources which were added, modified
+    /// or removed since the last load. Changes are detected by the size and the
+    /// modification time (in seconds) of the files. Notes modified in the second
+    /// of the last scan or later are compared by content, since a same-size edit
+    /// within that second leaves both unchanged. Links of unchanged notes are
+    /// resolved again if they might point to another target now.
+    fn refresh(&mut self) -> Result<types::VaultChanges>;
+
+    /// Reloads the given resources without comparing their size and modification
+    /// time, so every edit is seen. A path of a directory reloads the resources
+    /// below it. Resources which don't exist anymore are removed, new resources
+    /// at the paths are added. Paths which the scan skips, e.g. hidden or ignored
+    /// ones, are ignored without listing the vault.
+    ///
+    /// # Arguments
+    ///
+    /// * `rids`: Vault relative paths of changed resources or directories.
+    fn reload_paths(&mut self, rids: &[types::ResourceId]) -> Result<types::VaultChanges>;
+}
+
+impl DefaultEmerald {
+    /// Compares the scanned resources with the loaded ones.
+    fn detect_changes(
+        &self,
+        all_fs_meta_data: &[(types::ResourceId, types::FilesystemMetadata)],
+    ) -> types::VaultChanges {
+        let old_rids: HashSet<_> = self.rmod.create_iter().collect();
+        let new_rids: HashSet<_> = all_fs_meta_data.iter().map(|f| &f.0).collect();
+        let content_loader = resources::StorageContentLoader::new(self.storage.clone());
+
+        let mut changes = types::VaultChanges::default();
+        for (rid, fs_meta_data) in all_fs_meta_data {
+            if !old_rids.contains(rid) {
+                changes.added.push(rid.clone());
+                continue;
+            }
+            let meta_data = self.rmod.retrieve(rid);
+            if meta_data.size != fs_meta_data.size
+                || meta_data.modified != fs_meta_data.modified
+                || (fs_meta_data.modified >= self.loaded_at
+                    && self.content_differs(rid, fs_meta_data, &content_loader))
+            {
+                changes.modified.push(rid.clone());
+            }
+        }
+        changes.removed = self
+            .rmod
+            .create_iter()
+            .filter(|rid| !new_rids.contains(rid))
+            .collect();
+        changes
+    }
+
+    /// Returns true if the stored content of a note differs from the loaded one.
+    /// Other resources are never compared.
+    fn content_differs(
+        &self,
+        rid: &types::ResourceId,
+        fs_meta_data: &types::FilesystemMetadata,
+        content_loader: &impl resources::ContentLoader,
+    ) -> bool {
+        if !matches!(fs_meta_data.resource_type, types::ResourceType::Markdown()) {
+            return false;
+        }
+        content_loader
+            .load_lossy(rid)
+            .map_or(true, |(content, _)| content.0 != self.cmod.retrieve(rid).0)
+    }
+
+    /// Returns the normalized names which might resolve to another resource after
+    /// the update, or `None` if the resolver doesn't change. These are the
+    /// filenames of the added and removed resources and the old and new aliases
+    /// of notes whose aliases changed.
+    fn changed_names(
+        &self,
+        changes: &types::VaultChanges,
+        md_doc_meta_data: &[(types::ResourceId, types::DocumentMetadata)],
+    ) -> Option<HashSet<String>> {
+        let mut names: HashSet<_> = changes
+            .added
+            .iter()
+            .chain(changes.removed.iter())
+            .map(resource_id_resolver::norm_filename)
+            .collect();
+        for (rid, doc_meta_data) in md_doc_meta_data {
+            let aliases = doc_meta_data.aliases.as_deref().unwrap_or_default();
+            let old_aliases = match self.uid_mod.get_uid_from_rid(rid) {
+                Some(_) => self.nmod.retrieve(rid).document.aliases.as_slice(),
+                None => &[],
+            };
+            if old_aliases != aliases {
+                names.extend(
+                    old_aliases
+                        .iter()
+                        .chain(aliases)
+                        .map(|f| resource_id_resolver::norm_name(f)),
+                );
+            }
+        }
+        (!names.is_empty()).then_some(names)
+    }
+
+    /// Returns the unchanged notes whose links have to be resolved again, together
+    /// with their links resolved against the updated models.
+    ///
+    /// These are the notes linking to a touched resource, since the headings and
+    /// blocks of the target might have changed. If the resolver changed, notes
+    /// linking to a resource with one of the changed names and notes with broken
+    /// links to such a name are affected as well.
+    fn reresolve_links(
+        &self,
+        touched: &HashSet<&types::ResourceId>,
+        names: Option<&HashSet<String>>,
+    ) -> (Vec<types::ResourceId>, Vec<types::LinkSrc2Tgt>) {
+        let mut tgts: HashSet<_> = touched.iter().copied().collect();
+        let mut srcs = HashSet::new();
+        if let Some(names) = names {
+            tgts.extend(names.iter().flat_map(|f| self.lrmod.rids_with_name(f)));
+
+            let broken_links = self
+                .lmod
+                .iter()
+                .filter(|f| f.tgt.is_none() && !touched.contains(&f.src))
+                .map(|f| (f.src.clone(), f.link.clone(), f.kind));
+            srcs.extend(adapters::filter_links_by_name(broken_links, names).map(|f| f.0));
+        }
+        srcs.extend(
+            tgts.into_iter()
+                .flat_map(|f| link::SrcIterRetriever::retrieve(self.lmod.as_ref(), f))
+                .flatten()
+                .map(|f| f.src)
+                .filter(|f| !touched.contains(f)),
+        );
+
+        let it_links = srcs.iter().flat_map(|src| {
+            link::TgtIterRetriever::retrieve(self.lmod.as_ref(), src)
+                .into_iter()
+                .flatten()
+                .map(|f| (src.clone(), f.link, f.kind))
+        });
+        let links = adapters::adapter_to_rid_and_link_2_tgt(
+            it_links,
+            self.lrmod.as_ref(),
+            self.hmod.as_ref(),
+            self.bmod.as_ref(),
+        )
+        .map(|(src, link_2_tgt)| types::LinkSrc2Tgt::from_link_to_target(src, link_2_tgt))
+        .collect();
+        (srcs.into_iter().collect(), links)
+    }
+
+    /// Loads the added and modified resources and updates the models in place.
+    /// Only the changed notes and the notes linking to them are processed.
+    ///
+    /// Everything which can fail is done before the first model is touched, so
+    /// the vault stays unchanged if the update fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `changes`: The added, modified and removed resources.
+    /// * `fs_meta_data`: The filesystem metadata of the added and modified resources.
+    /// * `diagnostics`: The diagnostics of the vault without those of the touched
+    ///   resources.
+    fn apply_changes(
+        &mut self,
+        mut changes: types::VaultChanges,
+        fs_meta_data: FsMetadataList,
+        mut diagnostics: Vec<types::Diagnostic>,
+    ) -> Result<types::VaultChanges> {
+        if changes.is_empty() {
+            diagnostics.sort_by(|a, b| a.path.0.cmp(&b.path.0));
+            self.diagnostics = diagnostics;
+            return Ok(changes);
+        }
+        debug!("Update of {:?}", &changes);
+
+        let md_fs_meta_data: Vec<_> = adapters::filter_rid_and_meta_data(&fs_meta_data).collect();
+        let md_rids: Vec<_> = md_fs_meta_data.iter().map(|f| f.0.clone()).collect();
+        let removed = &changes.removed;
+
+        let content_loader = resources::StorageContentLoader::new(self.storage.clone());
+        let (contents, content_diagnostics) =
+            load_contents(&md_rids, &content_loader, self.options.lenient)?;
+        diagnostics.extend(content_diagnostics);
+        let (md_doc_meta_data, yaml_diagnostics) = extract_document_metadata(&contents);
+        diagnostics.extend(yaml_diagnostics);
+
+        let uid_claims: Vec<_> = md_doc_meta_data
+            .iter()
+            .map(|(rid, doc_meta_data)| {
+                let uid = doc_meta_data.uid.as_ref();
+                (
+                    rid.clone(),
+                    uid.and_then(types::FrontmatterUid::to_uid_string),
+                )
+            })
+            .collect();
+        self.uid_mod
+            .check_update(&uid_claims, removed, &self.uid_metadata_retriever())?;
+
+        // Nothing fails from here on
+        let names = self.changed_names(&changes, &md_doc_meta_data);
+        let aliases: Vec<_> =
+            adapters::to_resource_id_resolver::convert_to_aliases(&md_doc_meta_data).collect();
+
+        Arc::make_mut(&mut self.cmod).update(contents, removed);
+        let md_meta_data = zip(md_fs_meta_data, md_doc_meta_data).map(|f| (f.0 .0, f.0 .1, f.1 .1));
+        Arc::make_mut(&mut self.nmod).update(md_meta_data, removed);
+        Arc::make_mut(&mut self.rmod).update(&fs_meta_data, removed);
+
+        let touched: Vec<_> = changes
+            .added
+            .iter()
+            .chain(changes.modified.iter())
+            .chain(removed.iter())
+            .cloned()
+            .collect();
+        if names.is_some() {
+            let resource_loc_iter =
+                adapters::to_resource_id_resolver::convert_to_resource_locations(
+                    &fs_meta_data,
+                    common_path(self.storage.as_ref()),
+                );
+            Arc::make_mut(&mut self.lrmod).update(resource_loc_iter, aliases, &touched);
+        }
+
+        Arc::make_mut(&mut self.hmod).update(extract_headings(&md_rids, &self.cmod), removed);
+        Arc::make_mut(&mut self.bmod).update(extract_block_ids(&md_rids, &self.cmod), removed);
+        Arc::make_mut(&mut self.tmod)
+            .update(extract_tags(&md_rids, &self.cmod, &self.nmod), removed);
+        Arc::make_mut(&mut self.task_mod).update(extract_tasks(&md_rids, &self.cmod), removed);
+
+        let mut links = extract_links(&md_rids, &self.cmod, &self.lrmod, &self.hmod, &self.bmod);
+        let (mut srcs, reresolved) =
+            self.reresolve_links(&touched.iter().collect(), names.as_ref());
+        links.extend(reresolved);
+        srcs.extend(md_rids.iter().cloned());
+        srcs.extend(removed.iter().cloned());
+
+        let new_link_stats = stats::count_links(&links);
+        let old_links = Arc::make_mut(&mut self.lmod).update(links, &srcs);
+        self.stats.link_stats -= stats::count_links(&old_links);
+        self.stats.link_stats += new_link_stats;
+        self.stats.file_stats = stats::extract_file_stats(self.rmod.as_ref(), self.nmod.as_ref());
+        self.analytics = OnceLock::new();
+
+        let uid_metadata_retriever = self.uid_metadata_retriever();
+        Arc::make_mut(&mut self.uid_mod).update(&md_rids, removed, &uid_metadata_retriever);
+
+        // report the notes whose outgoing links differ
+        let mut old_links_of_src = HashMap::<&types::ResourceId, Vec<types::Link2Tgt>>::new();
+        for s2t in old_links.iter() {
+            old_links_of_src
+                .entry(&s2t.src)
+                .or_default()
+                .push(s2t.get_link_to_target());
+        }
+        let mut links_changed: Vec<_> = srcs
+            .into_iter()
+            .filter(|rid| {
+                let new_links: Vec<_> = link::TgtIterRetriever::retrieve(self.lmod.as_ref(), rid)
+                    .into_iter()
+                    .flatten()
+                    .collect();
+                self.uid_mod.get_uid_from_rid(rid).is_some()
+                    && old_links_of_src.get(rid).map_or(&[][..], Vec::as_slice) != new_links
+            })
+            .collect();
+        links_changed.sort_by(|a, b| a.0.cmp(&b.0));
+        links_changed.dedup();
+        changes.links_changed = links_changed;
+
+        diagnostics.sort_by(|a, b| a.path.0.cmp(&b.path.0));
+        self.diagnostics = diagnostics;
+        Ok(changes)
+    }
+
+    /// Returns the title and the aliases of a note.
+    fn mention_names(&self, rid: &types::ResourceId) -> Result<Vec<String>> {
+        if self.uid_mod.get_uid_from_rid(rid).is_none() {
+            return Err(EmeraldError::ResourceObjectNotFound);
+        }
+
+        let metadata = self.nmod.retrieve(rid);
+        Ok(once(metadata.title.clone())
+            .chain(metadata.document.aliases.iter().cloned())
+            .collect())
+    }
+
+    /// Ensures that a note can be renamed without creating an ambiguity.
+    fn check_rename(&self, rid: &types::ResourceId, new_rid: &types::ResourceId) -> Result<()> {
+        if self.uid_mod.get_uid_from_rid(rid).is_none() {
+            return Err(EmeraldError::ResourceObjectNotFound);
+        }
+
+        let new_name = new_rid.0.rsplit('/').next().unwrap_or_default();
+        let new_stem = new_name.strip_suffix(".md").unwrap_or(new_name);
+        if new_stem.is_empty() || !new_name.ends_with(".md") {
+            return Err(EmeraldError::NotAMarkdownFile);
+        }
+
+        // bare links to the new name must still resolve to the renamed note
+        for name in [new_stem, new_name] {
+            let link = types::Link(format!("[[{}]]", name));
+            if let Ok(other) = self.lrmod.resolve(&link) {
+                if other == new_rid {
+                    return Err(EmeraldError::ResourceAlreadyExists(new_rid.0.to_string()));
+                }
+                if other != rid {
+                    return Err(EmeraldError::AmbiguousName(
+                        name.to_owned(),
+                        other.0.to_string(),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Emerald for DefaultEmerald {
@@ -272,12 +1141,155 @@ impl Emerald for DefaultEmerald {
         self.stats.link_stats.invalid_backlinks
     }
 
+    fn anchor_missing_backlink_count(&self) -> usize {
+        self.stats.link_stats.anchor_missing_backlinks
+    }
+
+    fn get_alias_conflicts(&self) -> Vec<types::AliasConflict> {
+        self.lrmod.retrieve_alias_conflicts().to_vec()
+    }
+
+    fn plan_rename(
+        &self,
+        rid: &types::ResourceId,
+        new_rid: &types::ResourceId,
+    ) -> Result<Vec<(types::ResourceId, String)>> {
+        self.check_rename(rid, new_rid)?;
+
+        let mut srcs: Vec<_> = link::SrcIterRetriever::retrieve(self.lmod.as_ref(), rid)
+            .into_iter()
+            .flatten()
+            .map(|f| f.src)
+            .chain(once(rid.clone()))
+            .collect();
+        srcs.sort_by(|a, b| a.0.cmp(&b.0));
+        srcs.dedup();
+
+        let md_analyzer = markdown::MarkdownAnalyzerImpl::new();
+        let c_it = adapters::adapter_to_rids_and_content(srcs.iter(), self.cmod.as_ref());
+        Ok(
+            adapters::adapter_to_renamed_links(
+                c_it,
+                md_analyzer,
+                self.lrmod.as_ref(),
+                rid,
+                new_rid,
+            )
+            .collect(),
+        )
+    }
+
+    fn rename_note(&self, rid: &types::ResourceId, new_rid: &types::ResourceId) -> Result<()> {
+        let updates = self.plan_rename(rid, new_rid)?;
+
+        // the renamed note is updated before it is moved, so that a failed move
+        // can be undone.
+        let (own_update, other_updates): (Vec<_>, Vec<_>) =
+            updates.into_iter().partition(|(src, _)| src == rid);
+        for (src, content) in own_update.iter() {
+            self.content_writer.write(src, content)?;
+        }
+        if let Err(err) = self.resource_mover.move_resource(rid, new_rid) {
+            if !own_update.is_empty() {
+                self.content_writer.write(rid, self.get_content(rid))?;
+            }
+            return Err(err);
+        }
+
+        for (src, content) in other_updates.iter() {
+            self.content_writer.write(src, content)?;
+        }
+        Ok(())
+    }
+
+    fn get_broken_links(&self) -> Vec<types::BrokenLink> {
+        let rids: Vec<_> = self.nmod.create_iter().collect();
+        let note_names: Vec<_> = self.flat_iter().map(|note| note.title).collect();
+
+        let md_analyzer = markdown::MarkdownAnalyzerImpl::new();
+        let c_it = adapters::adapter_to_rids_and_content(rids.iter(), self.cmod.as_ref());
+        adapters::adapter_to_broken_links(c_it, md_analyzer, self.lrmod.as_ref(), &note_names)
+            .collect()
+    }
+
+    fn get_diagnostics(&self) -> &[types::Diagnostic] {
+        &self.diagnostics
+    }
+
+    fn get_graph(&self, options: &graph::GraphOptions) -> graph::Graph {
+        graph::extract_graph(
+            self.lmod.as_ref(),
+            self.rmod.as_ref(),
+            self.nmod.as_ref(),
+            self.tmod.as_ref(),
+            options,
+        )
+    }
+
+    fn get_analytics(&self) -> &analytics::VaultAnalytics {
+        self.analytics.get_or_init(|| {
+            let start = Instant::now();
+            let vault_analytics =
+                analytics::extract_analytics(self.nmod.as_ref(), self.lmod.as_ref());
+            let elapsed = start.elapsed();
+            debug!("Link graph analytics: {:?}", elapsed);
+            vault_analytics
+        })
+    }
+
+    fn get_neighbours(
+        &self,
+        rid: &types::ResourceId,
+        depth: usize,
+        direction: traversal::Direction,
+    ) -> Vec<traversal::Neighbour> {
+        traversal::find_neighbours(self.lmod.as_ref(), rid, depth, direction)
+    }
+
+    fn get_shortest_path(
+        &self,
+        from: &types::ResourceId,
+        to: &types::ResourceId,
+        direction: traversal::Direction,
+    ) -> Option<Vec<types::ResourceId>> {
+        traversal::find_shortest_path(self.lmod.as_ref(), from, to, direction)
+    }
+
+    fn get_unlinked_mentions(&self, rid: &types::ResourceId) -> Result<Vec<types::Mention>> {
+        let names = self.mention_names(rid)?;
+        let srcs: Vec<_> = self.nmod.create_iter().filter(|f| f != rid).collect();
+
+        let md_analyzer = markdown::MarkdownAnalyzerImpl::new();
+        let c_it = adapters::adapter_to_rids_and_content(srcs.iter(), self.cmod.as_ref());
+        Ok(adapters::adapter_to_mentions(c_it, md_analyzer, &names).collect())
+    }
+
+    fn plan_link_mentions(
+        &self,
+        rid: &types::ResourceId,
+    ) -> Result<Vec<(types::ResourceId, String)>> {
+        let names = self.mention_names(rid)?;
+        let srcs: Vec<_> = self.nmod.create_iter().filter(|f| f != rid).collect();
+
+        let md_analyzer = markdown::MarkdownAnalyzerImpl::new();
+        let c_it = adapters::adapter_to_rids_and_content(srcs.iter(), self.cmod.as_ref());
+        Ok(adapters::adapter_to_linked_mentions(
+            c_it,
+            md_analyzer,
+            self.lrmod.as_ref(),
+            rid,
+            &names,
+        )
+        .collect())
+    }
+
     fn flat_iter(&self) -> std::vec::IntoIter<Note<Uid>> {
+        let vault = self.vault();
         let vcev: Vec<vault::Note<unique_id::Uid>> = self
             .nmod
             .create_iter()
             .map(|rid| self.uid_mod.get_uid_from_rid(&rid).expect("Unknown Uid"))
-            .map(|uid| self.vault.get_note(uid))
+            .map(|uid| vault.get_note(uid))
             .collect();
 
         vcev.into_iter()
@@ -293,18 +1305,8 @@ impl Emerald for DefaultEmerald {
             .get_rid_from_uid(&note.uid)
             .expect("Should exist");
 
-        let link_iter = self.get_links.get_links_of(tgt);
-        let uid_mod_clone = self.uid_mod.clone();
-        let vault_clone = self.vault.clone();
-        Box::new(link_iter.map(move |f| match f {
-            adapters::to_outside::LinkQueryResult::LinkToNote(rid) => {
-                let link_uid = uid_mod_clone.get_uid_from_rid(&rid).expect("Should exist");
-                NoteTypes::Note(vault_clone.get_note(link_uid))
-            }
-            adapters::to_outside::LinkQueryResult::LinkToResource(rid) => {
-                NoteTypes::ResourceRef(rid)
-            }
-        }))
+        let link_iter = self.get_links_adapter().get_links_of(tgt);
+        self.to_note_types(link_iter)
     }
 
     fn get_backlinks_of(
@@ -317,24 +1319,100 @@ impl Emerald for DefaultEmerald {
             .get_rid_from_uid(&note.uid)
             .expect("Should exist");
 
-        let backlinks_iter = self.get_backlinks.get_backlinks_of(src);
+        let backlinks_iter =
+            adapters::to_outside::GetBacklinksAdapter::new(self.lmod.clone(), self.rmod.clone())
+                .get_backlinks_of(src);
 
-        let uid_mod_clone = self.uid_mod.clone();
-        let vault_clone = self.vault.clone();
+        self.to_note_types(backlinks_iter)
+    }
 
-        Box::new(backlinks_iter.map(move |f| match f {
-            adapters::to_outside::LinkQueryResult::LinkToNote(rid) => {
-                let link_uid = uid_mod_clone.get_uid_from_rid(&rid).expect("Should exist");
-                NoteTypes::Note(vault_clone.get_note(link_uid))
-            }
-            adapters::to_outside::LinkQueryResult::LinkToResource(rid) => {
-                NoteTypes::ResourceRef(rid)
-            }
-        }))
+    fn get_embeds_of(
+        &self,
+        note: &vault::Note<unique_id::Uid>,
+    ) -> Box<dyn Iterator<Item = vault::NoteTypes<types::ResourceId, unique_id::Uid>> + 'static>
+    {
+        let tgt = self
+            .uid_mod
+            .get_rid_from_uid(&note.uid)
+            .expect("Should exist");
+
+        let embed_iter = self.get_links_adapter().get_embeds_of(tgt);
+        self.to_note_types(embed_iter)
     }
 
-    fn update_note(&self, rid: &types::ResourceId, value: &str) -> String {
-        self.n_updater.update_note(
+    fn get_outline_of(&self, note: &vault::Note<unique_id::Uid>) -> Vec<types::OutlineNode> {
+        let rid = self
+            .uid_mod
+            .get_rid_from_uid(&note.uid)
+            .expect("Should exist");
+
+        self.hmod.get_outline(rid)
+    }
+
+    fn get_tags_of(&self, note: &vault::Note<unique_id::Uid>) -> Vec<String> {
+        let rid = self
+            .uid_mod
+            .get_rid_from_uid(&note.uid)
+            .expect("Should exist");
+
+        self.tmod.retrieve(rid).to_vec()
+    }
+
+    fn get_notes_with_tag(&self, tag: &str) -> std::vec::IntoIter<vault::Note<unique_id::Uid>> {
+        let vault = self.vault();
+        let notes: Vec<vault::Note<unique_id::Uid>> = self
+            .tmod
+            .get_rids_with_tag(tag)
+            .into_iter()
+            .map(|rid| self.uid_mod.get_uid_from_rid(&rid).expect("Unknown Uid"))
+            .map(|uid| vault.get_note(uid))
+            .collect();
+
+        notes.into_iter()
+    }
+
+    fn get_tag_counts(&self) -> Vec<(String, usize)> {
+        self.tmod.get_tag_counts()
+    }
+
+    fn get_tasks_of(&self, note: &vault::Note<unique_id::Uid>) -> Vec<types::Task> {
+        let rid = self
+            .uid_mod
+            .get_rid_from_uid(&note.uid)
+            .expect("Should exist");
+
+        self.task_mod.retrieve(rid).to_vec()
+    }
+
+    fn get_tasks(
+        &self,
+        filter: &types::TaskFilter,
+    ) -> Vec<(vault::Note<unique_id::Uid>, types::Task)> {
+        let vault = self.vault();
+        self.nmod
+            .create_iter()
+            .flat_map(|rid| {
+                let note_tags = &self.nmod.retrieve(&rid).document.tags;
+                let tasks: Vec<_> = self
+                    .task_mod
+                    .retrieve(&rid)
+                    .iter()
+                    .filter(|task| filter.matches(task, note_tags))
+                    .cloned()
+                    .collect();
+                if tasks.is_empty() {
+                    return vec![];
+                }
+
+                let uid = self.uid_mod.get_uid_from_rid(&rid).expect("Unknown Uid");
+                let note = vault.get_note(uid);
+                tasks.into_iter().map(|task| (note.clone(), task)).collect()
+            })
+            .collect()
+    }
+
+    fn update_note(&self, rid: &types::ResourceId, value: &str) -> Result<String> {
+        self.note_updater().update_note(
             rid,
             note_updater::NoteUpdateCommand::UpdateOrInsert {
                 key: "uid".into(),
@@ -342,4 +1420,118 @@ impl Emerald for DefaultEmerald {
             },
         )
     }
+
+    fn update_note_with(
+        &self,
+        rid: &types::ResourceId,
+        cmd: note_updater::NoteUpdateCommand,
+    ) -> Result<String> {
+        self.note_updater().update_note(rid, cmd)
+    }
+
+    fn get_content(&self, rid: &types::ResourceId) -> &str {
+        &self.cmod.retrieve(rid).0
+    }
+
+    fn write_note(&self, rid: &types::ResourceId, content: &str) -> Result<()> {
+        self.content_writer.write(rid, content)
+    }
+
+    fn refresh(&mut self) -> Result<types::VaultChanges> {
+        let loaded_at = unix_time_now();
+        let (all_fs_meta_data, mut diagnostics) = scan_vault(&self.storage, &self.options)?;
+        let changes = self.detect_changes(&all_fs_meta_data);
+
+        // metadata is scanned again each time, other problems are kept as long
+        // as their notes don't change
+        let touched: HashSet<_> = changes
+            .added
+            .iter()
+            .chain(changes.modified.iter())
+            .chain(changes.removed.iter())
+            .collect();
+        diagnostics.extend(
+            self.diagnostics
+                .iter()
+                .filter(|f| {
+                    f.kind != types::DiagnosticKind::UnreadableMetadata
+                        && !touched.contains(&f.path)
+                })
+                .cloned(),
+        );
+        let fs_meta_data: FsMetadataList = all_fs_meta_data
+            .into_iter()
+            .filter(|f| touched.contains(&f.0))
+            .collect();
+
+        let changes = self.apply_changes(changes, fs_meta_data, diagnostics)?;
+        self.loaded_at = loaded_at;
+        Ok(changes)
+    }
+
+    fn reload_paths(&mut self, rids: &[types::ResourceId]) -> Result<types::VaultChanges> {
+        let is_below = |rid: &types::ResourceId, path: &types::ResourceId| {
+            Path::new(rid.0.as_ref()).starts_with(path.0.as_ref())
+        };
+        let (mut known, unknown): (Vec<_>, Vec<_>) =
+            rids.iter().cloned().partition(|f| self.rmod.contains(f));
+        if !unknown.is_empty() {
+            known.extend(
+                self.rmod
+                    .create_iter()
+                    .filter(|rid| unknown.iter().any(|f| is_below(rid, f))),
+            );
+        }
+        known.sort_by(|a, b| a.0.cmp(&b.0));
+        known.dedup();
+
+        let mut changes = types::VaultChanges::default();
+        let mut fs_meta_data = FsMetadataList::new();
+        let fs_meta_data_loader =
+            resources::FilesystemMetadataLoaderImpl::new(self.storage.clone());
+        for rid in known {
+            match fs_meta_data_loader.load(&rid) {
+                Ok(meta_data) => {
+                    changes.modified.push(rid.clone());
+                    fs_meta_data.push((rid, meta_data));
+                }
+                Err(_) => changes.removed.push(rid),
+            }
+        }
+
+        // only the storage knows which new files belong to the vault, paths it
+        // skips like hidden or ignored ones aren't listed at all
+        let mut diagnostics = vec![];
+        if !unknown.is_empty() {
+            let mut added: Vec<_> = self
+                .storage
+                .list_below(&unknown)?
+                .into_iter()
+                .filter(|rid| !self.rmod.contains(rid))
+                .collect();
+            added.sort_by(|a, b| file_stem(a).cmp(&file_stem(b)));
+            let (added_fs_meta_data, metadata_diagnostics) =
+                load_fs_metadata(added, &fs_meta_data_loader, self.options.lenient)?;
+            changes
+                .added
+                .extend(added_fs_meta_data.iter().map(|f| f.0.clone()));
+            fs_meta_data.extend(added_fs_meta_data);
+            diagnostics.extend(metadata_diagnostics);
+        }
+
+        let touched: HashSet<_> = changes
+            .added
+            .iter()
+            .chain(changes.modified.iter())
+            .chain(changes.removed.iter())
+            .chain(rids.iter())
+            .collect();
+        diagnostics.extend(
+            self.diagnostics
+                .iter()
+                .filter(|f| !touched.contains(&f.path))
+                .cloned(),
+        );
+        self.apply_changes(changes, fs_meta_data, diagnostics)
+    }
 }
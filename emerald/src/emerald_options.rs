@@ -0,0 +1,17 @@
+use crate::model::unique_id::UidStrategy;
+use crate::resources::ScanOptions;
+
+/// Options which control how a vault is loaded.
+#[derive(Debug, Clone, Default)]
+pub struct EmeraldOptions {
+    /// Strategy used to mint uids for notes without a uid in their frontmatter.
+    pub uid_strategy: UidStrategy,
+
+    /// Determines which files of the vault directory are loaded. Vaults loaded
+    /// from another storage ignore it.
+    pub scan: ScanOptions,
+
+    /// Skips or degrades resources which can't be loaded instead of failing. The
+    /// problems are reported as diagnostics.
+    pub lenient: bool,
+}
@@ -25,12 +25,27 @@ pub enum EmeraldError {
     #[error("Wrong value was assigned.")]
     ValueError,
 
+    #[error("The uid {0} is used by {1} and {2}.")]
+    DuplicateUid(String, String, String),
+
+    #[error("The frontmatter is invalid: {0}")]
+    InvalidFrontmatter(String),
+
+    #[error("The resource {0} already exists.")]
+    ResourceAlreadyExists(String),
+
+    #[error("The name {0} is already used by {1}.")]
+    AmbiguousName(String, String),
+
     #[error("Keys are not unique")]
     NotUnique,
 
     #[error("Failed interpreting a wiki link.")]
     NotAWikiLink,
 
+    #[error("Failed interpreting a markdown link.")]
+    NotAMarkdownLink,
+
     #[error("Failed interpreting a resource id.")]
     NotAResourceId,
 
@@ -43,6 +58,21 @@ pub enum EmeraldError {
     #[error("The ResourceObject {0} has no assigned ResourceId")]
     ResourceObjectHasNoResourceId(String),
 
+    #[error("Reading the archive failed: {0}")]
+    ArchiveError(#[from] zip::result::ZipError),
+
+    #[error("The format of the archive {0} is unknown.")]
+    UnknownArchiveFormat(PathBuf),
+
+    #[error("The storage of the vault is read only.")]
+    ReadOnlyStorage,
+
+    #[error("Scanning the vault failed: {0}")]
+    ScanError(#[from] ignore::Error),
+
+    #[error("Watching the vault failed: {0}")]
+    WatchError(#[from] notify::Error),
+
     #[error("unknown error")]
     Unknown,
 }
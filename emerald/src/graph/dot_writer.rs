@@ -0,0 +1,73 @@
+use super::graph_data::{Graph, GraphNodeKind};
+
+/// Quotes a text as DOT identifier.
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Writes the graph in the Graphviz DOT language.
+pub fn write_dot(graph: &Graph) -> String {
+    let mut out = String::from("digraph vault {\n");
+
+    for node in graph.nodes.iter() {
+        let mut attrs = vec![
+            format!("label={}", quote(&node.title)),
+            format!("kind={}", quote(node.kind.as_str())),
+        ];
+        match node.kind {
+            GraphNodeKind::Note => (),
+            GraphNodeKind::Attachment => attrs.push("shape=box".to_owned()),
+            GraphNodeKind::Broken => attrs.push("style=dashed".to_owned()),
+        }
+        if !node.path.is_empty() {
+            attrs.push(format!("path={}", quote(&node.path)));
+            attrs.push(format!("size={}", node.size));
+        }
+        if !node.tags.is_empty() {
+            attrs.push(format!("tags={}", quote(&node.tags.join(","))));
+        }
+        out.push_str(&format!(
+            "    {} [{}];\n",
+            quote(&node.id),
+            attrs.join(", ")
+        ));
+    }
+
+    for edge in graph.edges.iter() {
+        let mut attrs = vec![format!("kind={}", quote(edge.kind.as_str()))];
+        if edge.kind.is_embed() {
+            attrs.push("style=bold".to_owned());
+        }
+        if let Some(label) = &edge.label {
+            attrs.push(format!("label={}", quote(label)));
+        }
+        out.push_str(&format!(
+            "    {} -> {} [{}];\n",
+            quote(&graph.nodes[edge.source].id),
+            quote(&graph.nodes[edge.target].id),
+            attrs.join(", ")
+        ));
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::write_dot;
+    use crate::graph::graph_data::tests::create_graph;
+
+    #[test]
+    fn test_write_dot() {
+        let expected = r#"digraph vault {
+    "a.md" [label="Note \"a\"", kind="note", path="a.md", size=10, tags="tag,other"];
+    "image.png" [label="image.png", kind="attachment", shape=box, path="image.png", size=200];
+    "broken:missing" [label="missing", kind="broken", style=dashed];
+    "a.md" -> "image.png" [kind="wiki_embed", style=bold];
+    "a.md" -> "broken:missing" [kind="md_link", label="Missing"];
+}
+"#;
+        assert_eq!(write_dot(&create_graph()), expected);
+    }
+}
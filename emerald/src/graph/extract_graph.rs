@@ -0,0 +1,178 @@
+use super::graph_data::{Graph, GraphEdge, GraphNode, GraphNodeKind};
+use super::graph_options::GraphOptions;
+use crate::model::{link, note, resource, tag};
+use crate::types;
+use std::collections::HashMap;
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+/// Returns the label of a link if it has one.
+fn link_label(link: &types::Link, kind: types::LinkKind) -> Option<String> {
+    let label = match kind {
+        types::LinkKind::WikiLink | types::LinkKind::WikiEmbed => link.split().ok()?.label,
+        types::LinkKind::MdLink | types::LinkKind::MdEmbed => {
+            Some(link.split_md_link().ok()?.label)
+        }
+    };
+    label.filter(|f| !f.is_empty())
+}
+
+/// Returns the name a broken link points to, e.g. `folder/note` for `[[folder/note#section]]`.
+fn broken_target(link: &types::Link, kind: types::LinkKind) -> String {
+    match kind {
+        types::LinkKind::WikiLink | types::LinkKind::WikiEmbed => match link.split() {
+            Ok(comps) => match comps.path {
+                Some(path) => format!("{}/{}", path, comps.name),
+                None => comps.name,
+            },
+            Err(_) => link.0.clone(),
+        },
+        types::LinkKind::MdLink | types::LinkKind::MdEmbed => match link.split_md_link() {
+            Ok(comps) => comps.target,
+            Err(_) => link.0.clone(),
+        },
+    }
+}
+
+/// Returns the file name of a resource without the `.md` extension of notes.
+fn resource_title(rid: &types::ResourceId) -> String {
+    let name = rid.0.rsplit('/').next().unwrap_or_default();
+    name.strip_suffix(".md").unwrap_or(name).to_owned()
+}
+
+/// Builds the link graph of the vault.
+///
+/// Every note becomes a node. Attachments and placeholders for the targets of
+/// broken links are only added if requested by the options. Links whose source
+/// or target isn't part of the graph are left out.
+///
+/// # Arguments
+///
+/// * `it_links`: All links of the vault.
+/// * `resources`: All resources of the vault.
+/// * `notes`: Metadata of the notes.
+/// * `tags`: Tags of the notes.
+/// * `options`: Nodes to include.
+pub fn extract_graph(
+    it_links: &impl link::LinksIterSrc,
+    resources: &(impl resource::ResourceIterSrc + resource::ResourceMetadataRetriever),
+    notes: &impl note::NoteMetadataRetriever,
+    tags: &impl tag::TagRetriever,
+    options: &GraphOptions,
+) -> Graph {
+    let mut graph = Graph::default();
+    let mut node_index: HashMap<String, usize> = HashMap::new();
+
+    for rid in resources.create_iter() {
+        let metadata = resources.retrieve(&rid);
+        let node = match metadata.resource_type {
+            types::ResourceType::Markdown() => GraphNode {
+                id: rid.0.to_string(),
+                kind: GraphNodeKind::Note,
+                title: notes.retrieve(&rid).title.clone(),
+                path: rid.0.to_string(),
+                tags: tags.retrieve(&rid).to_vec(),
+                size: metadata.size,
+            },
+            _ if options.include_attachments => GraphNode {
+                id: rid.0.to_string(),
+                kind: GraphNodeKind::Attachment,
+                title: resource_title(&rid),
+                path: rid.0.to_string(),
+                tags: vec![],
+                size: metadata.size,
+            },
+            _ => continue,
+        };
+        node_index.insert(node.id.clone(), graph.nodes.len());
+        graph.nodes.push(node);
+    }
+
+    for s2t in it_links.create_iter() {
+        let Some(&source) = node_index.get(s2t.src.0.as_ref()) else {
+            continue;
+        };
+
+        let target = match &s2t.tgt {
+            Some(tgt) => node_index.get(tgt.0.as_ref()).copied(),
+            None if options.include_broken_links => {
+                let name = broken_target(&s2t.link, s2t.kind);
+                let id = format!("broken:{}", name);
+                let idx = *node_index.entry(id.clone()).or_insert_with(|| {
+                    graph.nodes.push(GraphNode {
+                        id,
+                        kind: GraphNodeKind::Broken,
+                        title: name,
+                        path: String::new(),
+                        tags: vec![],
+                        size: 0,
+                    });
+                    graph.nodes.len() - 1
+                });
+                Some(idx)
+            }
+            None => None,
+        };
+        let Some(target) = target else {
+            trace!(
+                "Link {:?} of {:?} is not part of the graph",
+                s2t.link,
+                s2t.src
+            );
+            continue;
+        };
+
+        graph.edges.push(GraphEdge {
+            source,
+            target,
+            kind: s2t.kind,
+            label: link_label(&s2t.link, s2t.kind),
+        });
+    }
+
+    graph
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{broken_target, link_label, resource_title};
+    use crate::types::{Link, LinkKind, ResourceId};
+
+    #[test]
+    fn test_link_label() {
+        let label = |link: &str, kind| link_label(&Link::from(link), kind);
+
+        assert_eq!(
+            label("[[note|Label]]", LinkKind::WikiLink),
+            Some("Label".into())
+        );
+        assert_eq!(label("[[note]]", LinkKind::WikiEmbed), None);
+        assert_eq!(
+            label("[Text](note.md)", LinkKind::MdLink),
+            Some("Text".into())
+        );
+        assert_eq!(label("[](image.png)", LinkKind::MdEmbed), None);
+    }
+
+    #[test]
+    fn test_broken_target() {
+        let target = |link: &str, kind| broken_target(&Link::from(link), kind);
+
+        assert_eq!(
+            target("[[a/missing#section]]", LinkKind::WikiLink),
+            "a/missing"
+        );
+        assert_eq!(target("[[missing|Label]]", LinkKind::WikiLink), "missing");
+        assert_eq!(
+            target("[x](missing%20note.md)", LinkKind::MdLink),
+            "missing note.md"
+        );
+    }
+
+    #[test]
+    fn test_resource_title() {
+        assert_eq!(resource_title(&ResourceId::from("a/b/note.md")), "note");
+        assert_eq!(resource_title(&ResourceId::from("image.png")), "image.png");
+    }
+}
@@ -0,0 +1,146 @@
+use super::dot_writer::write_dot;
+use super::graph_format::GraphFormat;
+use super::graphml_writer::write_graphml;
+use super::json_writer::write_json;
+use super::mermaid_writer::write_mermaid;
+use crate::types;
+
+/// Describes what a node of the graph stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphNodeKind {
+    /// A markdown note.
+    Note,
+
+    /// A file which is not a markdown note, e.g. an image.
+    Attachment,
+
+    /// Placeholder for the target of a broken link.
+    Broken,
+}
+
+impl GraphNodeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GraphNodeKind::Note => "note",
+            GraphNodeKind::Attachment => "attachment",
+            GraphNodeKind::Broken => "broken",
+        }
+    }
+}
+
+/// A note, attachment or broken link target of the graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphNode {
+    /// Unique id of the node. This is the vault relative path for notes and
+    /// attachments.
+    pub id: String,
+
+    pub kind: GraphNodeKind,
+
+    /// Title of a note, name of an attachment or target of a broken link.
+    pub title: String,
+
+    /// Vault relative path. Empty for broken link targets.
+    pub path: String,
+
+    /// Tags of a note without their leading `#`.
+    pub tags: Vec<String>,
+
+    /// Size of the file in bytes.
+    pub size: u64,
+}
+
+/// A link between two nodes of the graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphEdge {
+    /// Index of the node containing the link.
+    pub source: usize,
+
+    /// Index of the node the link points to.
+    pub target: usize,
+
+    pub kind: types::LinkKind,
+
+    /// Label of the link if it has one.
+    pub label: Option<String>,
+}
+
+/// The link graph of a vault.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Graph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+impl Graph {
+    /// Writes the graph in the given format.
+    pub fn write(&self, format: GraphFormat) -> String {
+        match format {
+            GraphFormat::Dot => write_dot(self),
+            GraphFormat::GraphMl => write_graphml(self),
+            GraphFormat::Mermaid => write_mermaid(self),
+            GraphFormat::Json => write_json(self),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::{Graph, GraphEdge, GraphNode, GraphNodeKind};
+    use crate::types::LinkKind;
+
+    /// Creates a graph with a note linking to an attachment and a missing note.
+    pub fn create_graph() -> Graph {
+        let node = |id: &str, kind, title: &str, path: &str, tags: &[&str], size| GraphNode {
+            id: id.into(),
+            kind,
+            title: title.into(),
+            path: path.into(),
+            tags: tags.iter().map(|f| f.to_string()).collect(),
+            size,
+        };
+
+        Graph {
+            nodes: vec![
+                node(
+                    "a.md",
+                    GraphNodeKind::Note,
+                    "Note \"a\"",
+                    "a.md",
+                    &["tag", "other"],
+                    10,
+                ),
+                node(
+                    "image.png",
+                    GraphNodeKind::Attachment,
+                    "image.png",
+                    "image.png",
+                    &[],
+                    200,
+                ),
+                node(
+                    "broken:missing",
+                    GraphNodeKind::Broken,
+                    "missing",
+                    "",
+                    &[],
+                    0,
+                ),
+            ],
+            edges: vec![
+                GraphEdge {
+                    source: 0,
+                    target: 1,
+                    kind: LinkKind::WikiEmbed,
+                    label: None,
+                },
+                GraphEdge {
+                    source: 0,
+                    target: 2,
+                    kind: LinkKind::MdLink,
+                    label: Some("Missing".into()),
+                },
+            ],
+        }
+    }
+}
@@ -0,0 +1,15 @@
+/// Output formats of the graph export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    /// Graphviz DOT language.
+    Dot,
+
+    /// GraphML xml format.
+    GraphMl,
+
+    /// Mermaid flowchart.
+    Mermaid,
+
+    /// JSON document with a node and an edge list.
+    Json,
+}
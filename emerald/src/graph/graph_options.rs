@@ -0,0 +1,9 @@
+/// Determines which nodes are part of the exported graph.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GraphOptions {
+    /// Adds attachments which are linked or embedded by notes.
+    pub include_attachments: bool,
+
+    /// Adds a placeholder node for every distinct target of a broken link.
+    pub include_broken_links: bool,
+}
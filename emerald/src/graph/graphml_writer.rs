@@ -0,0 +1,111 @@
+use super::graph_data::Graph;
+
+/// Escapes the characters with a special meaning in xml.
+fn escape_xml(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            ch => out.push(ch),
+        }
+    }
+    out
+}
+
+/// Declarations of the node and edge attributes as (id, domain, name, type).
+const KEYS: &[(&str, &str, &str, &str)] = &[
+    ("d0", "node", "kind", "string"),
+    ("d1", "node", "title", "string"),
+    ("d2", "node", "path", "string"),
+    ("d3", "node", "tags", "string"),
+    ("d4", "node", "size", "long"),
+    ("d5", "edge", "kind", "string"),
+    ("d6", "edge", "label", "string"),
+];
+
+fn data(key: &str, value: &str) -> String {
+    format!("      <data key=\"{}\">{}</data>\n", key, escape_xml(value))
+}
+
+/// Writes the graph as GraphML document.
+pub fn write_graphml(graph: &Graph) -> String {
+    let mut out = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n"
+    ));
+    for (id, domain, name, attr_type) in KEYS {
+        out.push_str(&format!(
+            "  <key id=\"{}\" for=\"{}\" attr.name=\"{}\" attr.type=\"{}\"/>\n",
+            id, domain, name, attr_type
+        ));
+    }
+    out.push_str("  <graph id=\"vault\" edgedefault=\"directed\">\n");
+
+    for node in graph.nodes.iter() {
+        out.push_str(&format!("    <node id=\"{}\">\n", escape_xml(&node.id)));
+        out.push_str(&data("d0", node.kind.as_str()));
+        out.push_str(&data("d1", &node.title));
+        out.push_str(&data("d2", &node.path));
+        out.push_str(&data("d3", &node.tags.join(",")));
+        out.push_str(&data("d4", &node.size.to_string()));
+        out.push_str("    </node>\n");
+    }
+
+    for (idx, edge) in graph.edges.iter().enumerate() {
+        out.push_str(&format!(
+            "    <edge id=\"e{}\" source=\"{}\" target=\"{}\">\n",
+            idx,
+            escape_xml(&graph.nodes[edge.source].id),
+            escape_xml(&graph.nodes[edge.target].id)
+        ));
+        out.push_str(&data("d5", edge.kind.as_str()));
+        if let Some(label) = &edge.label {
+            out.push_str(&data("d6", label));
+        }
+        out.push_str("    </edge>\n");
+    }
+
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{escape_xml, write_graphml};
+    use crate::graph::graph_data::tests::create_graph;
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(escape_xml("a<b> & \"c\""), "a&lt;b&gt; &amp; &quot;c&quot;");
+    }
+
+    #[test]
+    fn test_write_graphml() {
+        let res = write_graphml(&create_graph());
+
+        assert!(res.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<graphml"));
+        assert!(
+            res.contains("  <key id=\"d4\" for=\"node\" attr.name=\"size\" attr.type=\"long\"/>\n")
+        );
+        assert!(res.contains(concat!(
+            "    <node id=\"a.md\">\n",
+            "      <data key=\"d0\">note</data>\n",
+            "      <data key=\"d1\">Note &quot;a&quot;</data>\n",
+            "      <data key=\"d2\">a.md</data>\n",
+            "      <data key=\"d3\">tag,other</data>\n",
+            "      <data key=\"d4\">10</data>\n",
+            "    </node>\n"
+        )));
+        assert!(res.contains(concat!(
+            "    <edge id=\"e1\" source=\"a.md\" target=\"broken:missing\">\n",
+            "      <data key=\"d5\">md_link</data>\n",
+            "      <data key=\"d6\">Missing</data>\n",
+            "    </edge>\n"
+        )));
+        assert!(res.ends_with("  </graph>\n</graphml>\n"));
+    }
+}
@@ -0,0 +1,68 @@
+use super::graph_data::Graph;
+use serde_json::json;
+
+/// Writes the graph as JSON document with a node and an edge list. Edges refer
+/// to the ids of their nodes.
+pub fn write_json(graph: &Graph) -> String {
+    let nodes: Vec<_> = graph
+        .nodes
+        .iter()
+        .map(|f| {
+            json!({
+                "id": f.id,
+                "kind": f.kind.as_str(),
+                "title": f.title,
+                "path": f.path,
+                "tags": f.tags,
+                "size": f.size,
+            })
+        })
+        .collect();
+    let edges: Vec<_> = graph
+        .edges
+        .iter()
+        .map(|f| {
+            json!({
+                "source": graph.nodes[f.source].id,
+                "target": graph.nodes[f.target].id,
+                "kind": f.kind.as_str(),
+                "label": f.label,
+            })
+        })
+        .collect();
+
+    let document = json!({ "nodes": nodes, "edges": edges });
+    serde_json::to_string_pretty(&document).expect("JSON serialization failed") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::write_json;
+    use crate::graph::graph_data::tests::create_graph;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn test_write_json() {
+        let res: Value = serde_json::from_str(&write_json(&create_graph())).unwrap();
+
+        assert_eq!(res["nodes"].as_array().unwrap().len(), 3);
+        assert_eq!(
+            res["nodes"][0],
+            json!({
+                "id": "a.md",
+                "kind": "note",
+                "title": "Note \"a\"",
+                "path": "a.md",
+                "tags": ["tag", "other"],
+                "size": 10,
+            })
+        );
+        assert_eq!(
+            res["edges"],
+            json!([
+                {"source": "a.md", "target": "image.png", "kind": "wiki_embed", "label": null},
+                {"source": "a.md", "target": "broken:missing", "kind": "md_link", "label": "Missing"},
+            ])
+        );
+    }
+}
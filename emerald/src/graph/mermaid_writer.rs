@@ -0,0 +1,74 @@
+use super::graph_data::{Graph, GraphNodeKind};
+
+/// Escapes a text for use inside a quoted mermaid label.
+fn escape_mermaid(text: &str) -> String {
+    text.replace('#', "#35;")
+        .replace('"', "#quot;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+}
+
+/// Writes the graph as mermaid flowchart.
+///
+/// Mermaid ids can't contain arbitrary characters, so the nodes are numbered
+/// and labelled with their title. Embeds are drawn as thick arrows and broken
+/// link targets with a dashed border.
+pub fn write_mermaid(graph: &Graph) -> String {
+    let mut out = String::from("flowchart LR\n");
+
+    for (idx, node) in graph.nodes.iter().enumerate() {
+        let title = escape_mermaid(&node.title);
+        let line = match node.kind {
+            GraphNodeKind::Note => format!("    n{}[\"{}\"]\n", idx, title),
+            GraphNodeKind::Attachment => format!("    n{}[/\"{}\"/]\n", idx, title),
+            GraphNodeKind::Broken => format!("    n{}[\"{}\"]:::broken\n", idx, title),
+        };
+        out.push_str(&line);
+    }
+
+    for edge in graph.edges.iter() {
+        let arrow = if edge.kind.is_embed() { "==>" } else { "-->" };
+        let label = match &edge.label {
+            Some(label) => format!("|\"{}\"|", escape_mermaid(label)),
+            None => String::new(),
+        };
+        out.push_str(&format!(
+            "    n{} {}{} n{}\n",
+            edge.source, arrow, label, edge.target
+        ));
+    }
+
+    if graph.nodes.iter().any(|f| f.kind == GraphNodeKind::Broken) {
+        out.push_str("    classDef broken stroke-dasharray: 5 5\n");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::write_mermaid;
+    use crate::graph::graph_data::tests::create_graph;
+
+    #[test]
+    fn test_write_mermaid() {
+        let expected = r#"flowchart LR
+    n0["Note #quot;a#quot;"]
+    n1[/"image.png"/]
+    n2["missing"]:::broken
+    n0 ==> n1
+    n0 -->|"Missing"| n2
+    classDef broken stroke-dasharray: 5 5
+"#;
+        assert_eq!(write_mermaid(&create_graph()), expected);
+    }
+
+    #[test]
+    fn test_write_mermaid_without_broken_nodes() {
+        let mut graph = create_graph();
+        graph.nodes.truncate(2);
+        graph.edges.truncate(1);
+
+        let res = write_mermaid(&graph);
+        assert!(!res.contains("classDef"));
+    }
+}
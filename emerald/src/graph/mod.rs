@@ -0,0 +1,13 @@
+mod dot_writer;
+mod extract_graph;
+mod graph_data;
+mod graph_format;
+mod graph_options;
+mod graphml_writer;
+mod json_writer;
+mod mermaid_writer;
+
+pub use extract_graph::extract_graph;
+pub use graph_data::{Graph, GraphEdge, GraphNode, GraphNodeKind};
+pub use graph_format::GraphFormat;
+pub use graph_options::GraphOptions;
@@ -1,19 +1,58 @@
 mod adapters;
+mod analytics;
 mod emerald;
+mod emerald_options;
 mod error;
+mod graph;
 mod markdown;
 mod model;
 mod resources;
 mod stats;
+mod traversal;
 mod types;
 mod utils;
+mod watch;
 mod yaml;
 
+pub use crate::analytics::NoteAnalytics;
+pub use crate::analytics::VaultAnalytics;
 pub use crate::emerald::DefaultEmerald;
 pub use crate::emerald::Emerald;
+pub use crate::emerald_options::EmeraldOptions;
 pub use crate::error::EmeraldError;
 pub use crate::error::Result;
+pub use crate::graph::Graph;
+pub use crate::graph::GraphEdge;
+pub use crate::graph::GraphFormat;
+pub use crate::graph::GraphNode;
+pub use crate::graph::GraphNodeKind;
+pub use crate::graph::GraphOptions;
+pub use crate::model::note_updater::FrontmatterValue;
+pub use crate::model::note_updater::NoteUpdateCommand;
 pub use crate::model::unique_id::Uid;
+pub use crate::model::unique_id::UidStrategy;
 pub use crate::model::vault::Note;
 pub use crate::model::vault::NoteTypes;
+pub use crate::resources::ArchiveFormat;
+pub use crate::resources::ArchiveVaultStorage;
+pub use crate::resources::FileVaultStorage;
+pub use crate::resources::MemoryVaultStorage;
+pub use crate::resources::ScanOptions;
+pub use crate::resources::StorageMetadata;
+pub use crate::resources::VaultStorage;
+pub use crate::traversal::Direction;
+pub use crate::traversal::Neighbour;
+pub use crate::types::AliasConflict;
+pub use crate::types::BrokenLink;
+pub use crate::types::Diagnostic;
+pub use crate::types::DiagnosticKind;
+pub use crate::types::Heading;
+pub use crate::types::LinkKind;
+pub use crate::types::Mention;
+pub use crate::types::OutlineNode;
 pub use crate::types::ResourceId;
+pub use crate::types::Task;
+pub use crate::types::TaskFilter;
+pub use crate::types::VaultChanges;
+pub use crate::watch::VaultEvent;
+pub use crate::watch::VaultWatcher;
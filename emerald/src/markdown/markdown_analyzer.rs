@@ -12,7 +12,7 @@
 /// # Associated Types
 ///
 /// * `Iterator`: The type of iterator returned by the `analyze` method. It must implement
-/// the `Iterator` trait and yield items of type `types::MdBlock<'a>`.
+///   the `Iterator` trait and yield items of type `types::MdBlock<'a>`.
 ///
 use crate::types;
 
@@ -6,6 +6,24 @@ use crate::types;
 #[allow(unused_imports)]
 use log::{debug, error, info, trace, warn};
 
+/// Returns the due date (`YYYY-MM-DD`) written as `📅 2026-10-20` or `due:: 2026-10-20`.
+fn find_due_date(text: &str) -> Option<&str> {
+    let is_date = |date: &str| {
+        date.len() == 10
+            && date.bytes().enumerate().all(|(idx, ch)| match idx {
+                4 | 7 => ch == b'-',
+                _ => ch.is_ascii_digit(),
+            })
+    };
+
+    ["📅", "due::"].iter().find_map(|marker| {
+        text.match_indices(marker).find_map(|(idx, _)| {
+            let date = text[(idx + marker.len())..].trim_start().get(..10)?;
+            is_date(date).then_some(date)
+        })
+    })
+}
+
 #[derive(Debug)]
 pub struct MarkdownAnalyzerIter<'a> {
     buf: &'a str,
@@ -19,16 +37,51 @@ impl<'a> MarkdownAnalyzerIter<'a> {
             state_data: StateData {
                 state: State::DocumentStart,
                 it: Utf8Iterator::new(buf),
+                last_block: None,
             },
         }
     }
 
+    fn convert_task(&self, s: usize, e: usize) -> types::MdBlock<'a> {
+        let item = &self.buf[s..e];
+
+        // the task parser ensured the format "<marker> [<state>]<text>"
+        let checkbox_idx = item.find('[').expect("Checkbox expected");
+        let checked = matches!(item.as_bytes()[checkbox_idx + 1], b'x' | b'X');
+        let text = item[(checkbox_idx + 3)..].trim();
+
+        types::MdBlock::Task {
+            checked,
+            text,
+            line: self.buf[..s].matches('\n').count() + 1,
+            due: find_due_date(text),
+        }
+    }
+
+    /// Returns the range of a yielded block which can't be part of a paragraph.
+    fn block_range(&self, inp: &Yield) -> Option<(usize, usize)> {
+        match *inp {
+            Yield::YamlFrontmatter(s, e) | Yield::Heading(s, e) | Yield::Task(s, e) => Some((s, e)),
+            Yield::CodeBlock(s, e) => {
+                // inline code is part of the surrounding text
+                let code = &self.buf[s..e];
+                (code.contains('\n') || !code.starts_with('`')).then_some((s, e))
+            }
+            _ => None,
+        }
+    }
+
     fn convert_yield_res_to_md_block(&self, inp: Yield) -> types::MdBlock<'a> {
         match inp {
             Yield::YamlFrontmatter(s, e) => types::MdBlock::YamlFrontmatter(&self.buf[s..e]),
             Yield::CodeBlock(s, e) => types::MdBlock::CodeBlock(&self.buf[s..e]),
             Yield::WikiLink(s, e) => types::MdBlock::WikiLink(&self.buf[s..e]),
             Yield::Link(s, e) => types::MdBlock::Link(&self.buf[s..e]),
+            Yield::Embed(s, e) => types::MdBlock::Embed(&self.buf[s..e]),
+            Yield::Heading(s, e) => types::MdBlock::Heading(&self.buf[s..e]),
+            Yield::BlockId(s, e) => types::MdBlock::BlockId(&self.buf[s..e]),
+            Yield::Tag(s, e) => types::MdBlock::Tag(&self.buf[s..e]),
+            Yield::Task(s, e) => self.convert_task(s, e),
         }
     }
 }
@@ -60,6 +113,9 @@ impl<'a> Iterator for MarkdownAnalyzerIter<'a> {
                 }
                 ActionResult::YieldState(state, yield_state) => {
                     self.state_data.state = state;
+                    if let Some(range) = self.block_range(&yield_state) {
+                        self.state_data.last_block = Some(range);
+                    }
                     return Some(self.convert_yield_res_to_md_block(yield_state));
                 }
             }
@@ -18,7 +18,7 @@ pub trait MarkdownFrontmatterSplitter {
     /// # Arguments
     ///
     /// * `content` - A string slice (`&'a str`) representing the markdown content to be split.
-    ///               It should contain the markdown text along with the optional YAML frontmatter.
+    ///   It should contain the markdown text along with the optional YAML frontmatter.
     ///
     /// # Returns
     ///
@@ -46,10 +46,10 @@ impl DefaultMarkdownFrontmatterSplitter {
     fn trim_pre_and_postamble<'a>(&self, content: &'a str) -> &'a str {
         content
             .trim_start_matches("---")
-            .trim_start_matches('\n')
-            .trim_end_matches('\n')
+            .trim_start_matches(['\r', '\n'])
+            .trim_end_matches(['\r', '\n', ' '])
             .trim_end_matches("---")
-            .trim_end_matches('\n')
+            .trim_end_matches(['\r', '\n'])
     }
 }
 impl MarkdownFrontmatterSplitter for DefaultMarkdownFrontmatterSplitter {
@@ -70,3 +70,22 @@ impl MarkdownFrontmatterSplitter for DefaultMarkdownFrontmatterSplitter {
         (yaml, &content[start_of_markdown..])
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{DefaultMarkdownFrontmatterSplitter, MarkdownFrontmatterSplitter};
+
+    #[test]
+    fn test_split_with_frontmatter() {
+        let dut = DefaultMarkdownFrontmatterSplitter::new();
+        let out = dut.split("---\na: 1\n---\nText");
+        assert_eq!(out, (Some("a: 1"), "Text"));
+    }
+
+    #[test]
+    fn test_split_with_crlf_frontmatter() {
+        let dut = DefaultMarkdownFrontmatterSplitter::new();
+        let out = dut.split("---\r\na: 1\r\n---\r\nText");
+        assert_eq!(out, (Some("a: 1"), "Text"));
+    }
+}
@@ -10,6 +10,11 @@ mod markdown_analyzer_impl;
 mod markdown_analyzer_iter;
 mod markdown_splitters;
 mod states;
+#[allow(
+    clippy::module_inception,
+    clippy::needless_borrow,
+    clippy::useless_conversion
+)]
 mod tests;
 mod utf8_iterator;
 mod utils;
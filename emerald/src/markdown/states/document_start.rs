@@ -1,3 +1,4 @@
+use super::line_start::line_start;
 use super::parsers;
 use super::state::{ActionResult, State, StateData, Yield};
 use crate::markdown::utils::*;
@@ -9,6 +10,12 @@ pub(crate) fn document_start(state_data: &mut StateData) -> ActionResult {
     let Some((index, i)) = state_data.it.peek().cloned() else {
         return ActionResult::EndOfFile;
     };
+
+    // # Blocks at the start of a line
+    if let Some(action_result) = line_start(state_data) {
+        return action_result;
+    }
+
     match i {
         // # Start of parsing
         '-' => match parsers::yaml_frontmatter(&mut state_data.it, index) {
@@ -27,6 +34,19 @@ pub(crate) fn document_start(state_data: &mut StateData) -> ActionResult {
             consume!(state_data.it);
             ActionResult::NextState(State::EmptyLine)
         }
+        '#' => {
+            // save position of iterator ... needed for backtracking
+            let it_pos = state_data.it.get_pos();
+            match parsers::heading(&mut state_data.it, index) {
+                parsers::ParseResult::Failed => {
+                    state_data.it.set_pos(it_pos);
+                    ActionResult::NextState(State::Text)
+                }
+                parsers::ParseResult::Yield(s, e) => {
+                    ActionResult::YieldState(State::Text, Yield::Heading(s, e))
+                }
+            }
+        }
         _ => ActionResult::NextState(State::Text),
     }
 }
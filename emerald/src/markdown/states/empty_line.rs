@@ -1,3 +1,4 @@
+use super::line_start::line_start;
 use super::parsers;
 use super::state::{ActionResult, State, StateData, Yield};
 use crate::markdown::utils::*;
@@ -10,6 +11,11 @@ pub(crate) fn empty_line(state_data: &mut StateData) -> ActionResult {
         return ActionResult::EndOfFile;
     };
 
+    // # Blocks at the start of a line
+    if let Some(action_result) = line_start(state_data) {
+        return action_result;
+    }
+
     match i {
         // # Empty Line found
         '\n' => {
@@ -22,6 +28,19 @@ pub(crate) fn empty_line(state_data: &mut StateData) -> ActionResult {
                 ActionResult::YieldState(State::InlCodeBlock, Yield::CodeBlock(s, e))
             }
         },
+        '#' => {
+            // save position of iterator ... needed for backtracking
+            let it_pos = state_data.it.get_pos();
+            match parsers::heading(&mut state_data.it, index) {
+                parsers::ParseResult::Failed => {
+                    state_data.it.set_pos(it_pos);
+                    ActionResult::NextState(State::Text)
+                }
+                parsers::ParseResult::Yield(s, e) => {
+                    ActionResult::YieldState(State::Text, Yield::Heading(s, e))
+                }
+            }
+        }
         _ => ActionResult::NextState(State::Text),
     }
 }
@@ -0,0 +1,31 @@
+use super::parsers;
+use super::state::{ActionResult, State, StateData, Yield};
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+/// Checks for blocks which can only start at the beginning of a line but
+/// might be indented.
+///
+/// Returns `None` if no such block was found. The iterator is left untouched
+/// in this case.
+pub(crate) fn line_start(state_data: &mut StateData) -> Option<ActionResult> {
+    let (index, i) = state_data.it.peek().cloned()?;
+
+    match i {
+        ' ' | '\t' | '-' | '*' | '+' | '0'..='9' => {
+            // save position of iterator ... needed for backtracking
+            let it_pos = state_data.it.get_pos();
+            match parsers::task(&mut state_data.it, index) {
+                parsers::ParseResult::Failed => {
+                    state_data.it.set_pos(it_pos);
+                    None
+                }
+                parsers::ParseResult::Yield(s, e) => {
+                    Some(ActionResult::YieldState(State::Text, Yield::Task(s, e)))
+                }
+            }
+        }
+        _ => None,
+    }
+}
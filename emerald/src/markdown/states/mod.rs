@@ -1,6 +1,7 @@
 mod document_start;
 mod empty_line;
 mod inline_codeblock;
+mod line_start;
 mod new_line;
 mod parsers;
 pub(crate) mod state;
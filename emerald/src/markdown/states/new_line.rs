@@ -1,5 +1,6 @@
+use super::line_start::line_start;
 use super::parsers;
-use super::state::{ActionResult, State, StateData};
+use super::state::{ActionResult, State, StateData, Yield};
 use crate::markdown::utils::*;
 
 #[allow(unused_imports)]
@@ -10,6 +11,11 @@ pub(crate) fn new_line(state_data: &mut StateData) -> ActionResult {
         return ActionResult::EndOfFile;
     };
 
+    // # Blocks at the start of a line
+    if let Some(action_result) = line_start(state_data) {
+        return action_result;
+    }
+
     match i {
         // # New line found
         ' ' => match parsers::empty_line(&mut state_data.it, index) {
@@ -20,6 +26,32 @@ pub(crate) fn new_line(state_data: &mut StateData) -> ActionResult {
             consume!(state_data.it);
             ActionResult::NextState(State::EmptyLine)
         }
+        '#' => {
+            // save position of iterator ... needed for backtracking
+            let it_pos = state_data.it.get_pos();
+            match parsers::heading(&mut state_data.it, index) {
+                parsers::ParseResult::Failed => {
+                    state_data.it.set_pos(it_pos);
+                    ActionResult::NextState(State::Text)
+                }
+                parsers::ParseResult::Yield(s, e) => {
+                    ActionResult::YieldState(State::Text, Yield::Heading(s, e))
+                }
+            }
+        }
+        '=' | '-' => {
+            // save position of iterator ... needed for backtracking
+            let it_pos = state_data.it.get_pos();
+            match parsers::setext_heading(&mut state_data.it, index, state_data.last_block) {
+                parsers::ParseResult::Failed => {
+                    state_data.it.set_pos(it_pos);
+                    ActionResult::NextState(State::Text)
+                }
+                parsers::ParseResult::Yield(s, e) => {
+                    ActionResult::YieldState(State::Text, Yield::Heading(s, e))
+                }
+            }
+        }
         _ => ActionResult::NextState(State::Text),
     }
 }
@@ -0,0 +1,44 @@
+use super::ParseResult;
+use crate::markdown::utf8_iterator::Utf8Iterator;
+use crate::markdown::utils::*;
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+/// Detects a block identifier (`^block-id`) in the markdown input.
+///
+/// A block identifier marks the block it belongs to as target for links like
+/// `[[note#^block-id]]`. It must be preceded by a whitespace or start a line and
+/// consists of ascii letters, digits and dashes. Only whitespaces may follow up to
+/// the end of the line.
+///
+/// # Returns
+/// - `ParseResult::Yield(start_idx, end_idx)` if a block identifier was detected,
+///   where the range covers the `^` and the identifier.
+/// - `ParseResult::Failed` if the input is no block identifier.
+pub(crate) fn block_id(it: &mut Utf8Iterator, start_idx: usize) -> ParseResult {
+    let preceding = it.as_str()[..start_idx].chars().next_back();
+    if !matches!(preceding, None | Some(' ' | '\t' | '\n')) {
+        return ParseResult::Failed;
+    }
+
+    if consume_expected_chars!(it, '^').is_none() {
+        return ParseResult::Failed;
+    }
+
+    let id_len = gather!(it, Option::<i32>::None, 'a'..='z' | 'A'..='Z' | '0'..='9' | '-');
+    if id_len == 0 {
+        return ParseResult::Failed;
+    }
+    let end_pos = it.get_pos();
+
+    // only whitespaces are allowed up to the end of the line
+    gather!(it, Option::<i32>::None, ' ' | '\t');
+    consume_expected_chars!(it, '\r');
+    if consume_expected_chars!(it, '\n').is_none() {
+        return ParseResult::Failed;
+    }
+    it.set_pos(end_pos);
+
+    ParseResult::Yield(start_idx, end_pos)
+}
@@ -0,0 +1,130 @@
+use super::ParseResult;
+use crate::markdown::utf8_iterator::Utf8Iterator;
+use crate::markdown::utils::*;
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+/// Detects an ATX heading (`# Heading`) in the markdown input.
+///
+/// An ATX heading starts with 1 to 6 `#` characters, which must be followed by a
+/// whitespace or the end of the line. The yielded range covers the whole heading
+/// line without the line ending.
+///
+/// On success the iterator is placed directly after the opening `#` sequence. This
+/// allows the text state to continue parsing the heading text, which might contain
+/// links.
+///
+/// # Returns
+/// - `ParseResult::Yield(start_idx, end_idx)` if an ATX heading was detected.
+/// - `ParseResult::Failed` if the line is not a heading.
+pub(crate) fn heading(it: &mut Utf8Iterator, start_idx: usize) -> ParseResult {
+    let level = gather!(it, Some(7), '#');
+    if !(1..=6).contains(&level) {
+        return ParseResult::Failed;
+    }
+
+    let text_pos = it.get_pos();
+    if consume_expected_chars!(it, ' ' | '\t' | '\r' | '\n').is_none() {
+        return ParseResult::Failed;
+    }
+    it.set_pos(text_pos);
+
+    let end_idx = find_line_end(it);
+    it.set_pos(text_pos);
+
+    ParseResult::Yield(start_idx, end_idx)
+}
+
+/// Detects a setext heading underline (`===` or `---`) in the markdown input.
+///
+/// A setext heading consists of a line of text followed by an underline made of `=`
+/// (level 1) or `-` (level 2) characters. This parser must be called at the start of
+/// the line following a line of text. The yielded range covers the text line and the
+/// underline without the line ending.
+///
+/// The previous line is no heading text if it belongs to `last_block`, the last
+/// block which can't be part of a paragraph (e.g. the closing fence of a code block),
+/// or if it starts a list item or a blockquote.
+///
+/// On success the iterator is placed at the line ending of the underline.
+///
+/// # Returns
+/// - `ParseResult::Yield(start_idx, end_idx)` if a setext heading was detected,
+///   where `start_idx` is the start of the text line.
+/// - `ParseResult::Failed` if the line is not an underline or the previous line
+///   can not be a heading.
+pub(crate) fn setext_heading(
+    it: &mut Utf8Iterator,
+    start_idx: usize,
+    last_block: Option<(usize, usize)>,
+) -> ParseResult {
+    let cnt = match it.peek() {
+        Some((_, '=')) => gather!(it, Option::<i32>::None, '='),
+        Some((_, '-')) => gather!(it, Option::<i32>::None, '-'),
+        _ => 0,
+    };
+    if cnt == 0 {
+        return ParseResult::Failed;
+    }
+
+    let end_pos = it.get_pos();
+
+    // gather all trailing whitespaces doesnt matter how many
+    gather!(it, Option::<i32>::None, ' ' | '\t');
+
+    consume_expected_chars!(it, '\r');
+    if consume_expected_chars!(it, '\n').is_none() {
+        return ParseResult::Failed;
+    }
+    it.set_pos(end_pos);
+    gather!(it, Option::<i32>::None, ' ' | '\t');
+
+    // the previous line contains the text of the heading
+    let Some(prev_line_end) = start_idx.checked_sub(1) else {
+        return ParseResult::Failed;
+    };
+    let before = &it.as_str()[..prev_line_end];
+    let prev_line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+    let prev_line = before[prev_line_start..].trim();
+    if prev_line.is_empty() || prev_line.starts_with('#') || starts_with_container(prev_line) {
+        return ParseResult::Failed;
+    }
+    if last_block.is_some_and(|(_, end)| end > prev_line_start) {
+        return ParseResult::Failed;
+    }
+
+    ParseResult::Yield(prev_line_start, end_pos)
+}
+
+/// Returns true if the line starts with a list item marker (`-`, `*`, `+`, `1.`, `1)`)
+/// or a blockquote marker (`>`).
+fn starts_with_container(line: &str) -> bool {
+    if line.starts_with('>') {
+        return true;
+    }
+
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    let marker = match digits {
+        0 => line.strip_prefix(['-', '*', '+']),
+        1..=9 => line[digits..].strip_prefix(['.', ')']),
+        _ => None,
+    };
+    marker.is_some_and(|rest| rest.is_empty() || rest.starts_with([' ', '\t']))
+}
+
+/// Returns the index of the end of the current line, excluding the line ending.
+fn find_line_end(it: &mut Utf8Iterator) -> usize {
+    let mut end_idx = it.get_pos();
+    loop {
+        let IterResult::Some((idx, i)) = consume!(it) else {
+            break;
+        };
+        match i {
+            '\n' => break,
+            '\r' => (),
+            _ => end_idx = idx + i.len_utf8(),
+        }
+    }
+    end_idx
+}
@@ -1,15 +1,28 @@
+// The parsers consume the iterator char by char and use `let ... else { break; }` to
+// detect the end of the input.
+#![allow(clippy::while_let_loop)]
+
+mod block_id;
 mod code_block;
 mod empty_line;
+mod heading;
 mod inline_code_block;
 mod link;
 mod parse_result;
+mod tag;
+mod task;
 mod wikilink;
 mod yaml_frontmatter;
 
+pub(crate) use block_id::block_id;
 pub(crate) use code_block::code_block;
 pub(crate) use empty_line::empty_line;
+pub(crate) use heading::heading;
+pub(crate) use heading::setext_heading;
 pub(crate) use inline_code_block::inline_code_block;
 pub(crate) use link::link;
 pub(crate) use parse_result::ParseResult;
+pub(crate) use tag::tag;
+pub(crate) use task::task;
 pub(crate) use wikilink::wiki_link;
 pub(crate) use yaml_frontmatter::yaml_frontmatter;
@@ -0,0 +1,46 @@
+use super::ParseResult;
+use crate::markdown::utf8_iterator::Utf8Iterator;
+use crate::markdown::utils::*;
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+/// Detects an inline tag (`#tag` or `#project/alpha`) in the markdown input.
+///
+/// A tag must be preceded by a whitespace or start a line. It consists of letters,
+/// digits, `_`, `-` and `/` (to nest tags) and must contain at least one character
+/// which is not a digit.
+///
+/// # Returns
+/// - `ParseResult::Yield(start_idx, end_idx)` if a tag was detected, where the range
+///   covers the `#` and the tag.
+/// - `ParseResult::Failed` if the input is no tag.
+pub(crate) fn tag(it: &mut Utf8Iterator, start_idx: usize) -> ParseResult {
+    let preceding = it.as_str()[..start_idx].chars().next_back();
+    if !matches!(preceding, None | Some(' ' | '\t' | '\n')) {
+        return ParseResult::Failed;
+    }
+
+    if consume_expected_chars!(it, '#').is_none() {
+        return ParseResult::Failed;
+    }
+
+    let mut end_idx = it.get_pos();
+    let mut only_digits = true;
+    while let Some((idx, ch)) = it.peek().cloned() {
+        if !(ch.is_alphanumeric() || matches!(ch, '_' | '-' | '/')) {
+            break;
+        }
+        if !ch.is_ascii_digit() {
+            only_digits = false;
+        }
+        it.next();
+        end_idx = idx + ch.len_utf8();
+    }
+
+    if only_digits {
+        return ParseResult::Failed;
+    }
+
+    ParseResult::Yield(start_idx, end_idx)
+}
@@ -0,0 +1,71 @@
+use super::ParseResult;
+use crate::markdown::utf8_iterator::Utf8Iterator;
+use crate::markdown::utils::*;
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+/// Detects a task list item (`- [ ] task` or `1. [x] done`) in the markdown input.
+///
+/// The item may be indented and must start with a bullet (`-`, `*`, `+`) or an
+/// ordered list marker (`1.`, `1)`) followed by the checkbox `[ ]`, `[x]` or `[X]`.
+/// The yielded range covers the item from its marker to the end of the line,
+/// without the line ending.
+///
+/// On success the iterator is placed directly after the checkbox. This allows the
+/// text state to continue parsing the text of the task, which might contain links
+/// and tags.
+///
+/// # Returns
+/// - `ParseResult::Yield(start_idx, end_idx)` if a task list item was detected.
+/// - `ParseResult::Failed` if the line is no task list item.
+pub(crate) fn task(it: &mut Utf8Iterator, _start_idx: usize) -> ParseResult {
+    gather!(it, Option::<i32>::None, ' ' | '\t');
+    let marker_idx = it.get_pos();
+
+    match it.peek() {
+        Some((_, '-' | '*' | '+')) => {
+            it.next();
+        }
+        Some((_, '0'..='9')) => {
+            gather!(it, Some(9), '0'..='9');
+            if consume_expected_chars!(it, '.' | ')').is_none_or_eof() {
+                return ParseResult::Failed;
+            }
+        }
+        _ => return ParseResult::Failed,
+    }
+
+    if gather!(it, Option::<i32>::None, ' ' | '\t') == 0 {
+        return ParseResult::Failed;
+    }
+
+    if consume_expected_chars!(it, '[').is_none_or_eof()
+        || consume_expected_chars!(it, ' ' | 'x' | 'X').is_none_or_eof()
+        || consume_expected_chars!(it, ']').is_none_or_eof()
+    {
+        return ParseResult::Failed;
+    }
+
+    let text_pos = it.get_pos();
+    if consume_expected_chars!(it, ' ' | '\t' | '\r' | '\n').is_none() {
+        return ParseResult::Failed;
+    }
+    it.set_pos(text_pos);
+
+    // find the end of the line
+    let mut end_idx = text_pos;
+    loop {
+        let IterResult::Some((idx, i)) = consume!(it) else {
+            break;
+        };
+        match i {
+            '\n' => break,
+            '\r' => (),
+            _ => end_idx = idx + i.len_utf8(),
+        }
+    }
+    it.set_pos(text_pos);
+
+    ParseResult::Yield(marker_idx, end_idx)
+}
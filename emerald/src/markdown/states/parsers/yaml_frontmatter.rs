@@ -22,7 +22,7 @@ use log::{debug, error, info, trace, warn};
 ///   is detected, where `start_idx` is the starting index and `end_idx` is the index immediately after
 ///   the end of the block.
 /// - `ParseResult::Failed` if the detected block does not conform to the expected YAML
-///    front matter format.
+///   front matter format.
 pub(crate) fn yaml_frontmatter(it: &mut Utf8Iterator, start_idx: usize) -> ParseResult {
     // gather 3 dashes
     if gather!(it, Option::<i32>::None, '-') != 3 {
@@ -35,6 +35,17 @@ pub(crate) fn yaml_frontmatter(it: &mut Utf8Iterator, start_idx: usize) -> Parse
         return ParseResult::Failed;
     }
 
+    // an empty frontmatter is closed right away
+    let pos = it.get_pos();
+    if gather!(it, Option::<i32>::None, '-') == 3 {
+        gather!(it, Option::<i32>::None, ' ');
+        consume_expected_chars!(it, '\r');
+        if consume_expected_chars!(it, '\n').is_some() || it.peek().is_none() {
+            return ParseResult::Yield(start_idx, it.get_pos());
+        }
+    }
+    it.set_pos(pos);
+
     let mut last_index: usize = 0;
     loop {
         let IterResult::Some((index, i)) = consume!(it) else {
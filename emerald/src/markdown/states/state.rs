@@ -26,6 +26,11 @@ pub enum Yield {
     CodeBlock(usize, usize),
     WikiLink(usize, usize),
     Link(usize, usize),
+    Embed(usize, usize),
+    Heading(usize, usize),
+    BlockId(usize, usize),
+    Tag(usize, usize),
+    Task(usize, usize),
 }
 
 pub enum ActionResult {
@@ -39,4 +44,8 @@ pub enum ActionResult {
 pub struct StateData<'a> {
     pub state: State,
     pub it: Utf8Iterator<'a>,
+
+    /// Range of the last yielded block which can't be part of a paragraph,
+    /// e.g. a code block or a heading.
+    pub last_block: Option<(usize, usize)>,
 }
@@ -28,13 +28,64 @@ pub(crate) fn text(state_data: &mut StateData) -> ActionResult {
                 parsers::ParseResult::Failed => {
                     // backtrack if the link was not a wikilink
                     state_data.it.set_pos(it_pos);
-                    ActionResult::Error(State::NewLine)
+                    ActionResult::Error(State::Text)
                 }
                 parsers::ParseResult::Yield(s, e) => {
                     ActionResult::YieldState(State::Text, Yield::Link(s, e))
                 }
             }
         }
+        '!' => {
+            consume!(state_data.it);
+
+            // save position of iterator ... needed for backtracking
+            let it_pos = state_data.it.get_pos();
+            match parsers::wiki_link(&mut state_data.it, index) {
+                parsers::ParseResult::Failed => {
+                    // backtrack if the embed was not a wikilink
+                    state_data.it.set_pos(it_pos);
+                }
+                parsers::ParseResult::Yield(s, e) => {
+                    return ActionResult::YieldState(State::Text, Yield::Embed(s, e))
+                }
+            };
+            match parsers::link(&mut state_data.it, index) {
+                parsers::ParseResult::Failed => {
+                    // backtrack ... the '!' was ordinary text
+                    state_data.it.set_pos(it_pos);
+                    ActionResult::NextState(State::Text)
+                }
+                parsers::ParseResult::Yield(s, e) => {
+                    ActionResult::YieldState(State::Text, Yield::Embed(s, e))
+                }
+            }
+        }
+        '#' => {
+            // save position of iterator ... needed for backtracking
+            let it_pos = state_data.it.get_pos();
+            match parsers::tag(&mut state_data.it, index) {
+                parsers::ParseResult::Failed => {
+                    state_data.it.set_pos(it_pos);
+                    ActionResult::Error(State::Text)
+                }
+                parsers::ParseResult::Yield(s, e) => {
+                    ActionResult::YieldState(State::Text, Yield::Tag(s, e))
+                }
+            }
+        }
+        '^' => {
+            // save position of iterator ... needed for backtracking
+            let it_pos = state_data.it.get_pos();
+            match parsers::block_id(&mut state_data.it, index) {
+                parsers::ParseResult::Failed => {
+                    state_data.it.set_pos(it_pos);
+                    ActionResult::Error(State::Text)
+                }
+                parsers::ParseResult::Yield(s, e) => {
+                    ActionResult::YieldState(State::Text, Yield::BlockId(s, e))
+                }
+            }
+        }
         '`' => match parsers::code_block(&mut state_data.it, index) {
             parsers::ParseResult::Failed => ActionResult::Error(State::NewLine),
             parsers::ParseResult::Yield(s, e) => {
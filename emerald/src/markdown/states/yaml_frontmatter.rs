@@ -1,3 +1,4 @@
+use super::line_start::line_start;
 use super::parsers;
 use super::state::{ActionResult, State, StateData, Yield};
 
@@ -9,6 +10,11 @@ pub(crate) fn yaml_frontmatter(state_data: &mut StateData) -> ActionResult {
         return ActionResult::EndOfFile;
     };
 
+    // # Blocks at the start of a line
+    if let Some(action_result) = line_start(state_data) {
+        return action_result;
+    }
+
     match i {
         ' ' => match parsers::inline_code_block(&mut state_data.it, index) {
             parsers::ParseResult::Failed => ActionResult::NextState(State::EmptyLine),
@@ -16,6 +22,19 @@ pub(crate) fn yaml_frontmatter(state_data: &mut StateData) -> ActionResult {
                 ActionResult::YieldState(State::InlCodeBlock, Yield::CodeBlock(s, e))
             }
         },
+        '#' => {
+            // save position of iterator ... needed for backtracking
+            let it_pos = state_data.it.get_pos();
+            match parsers::heading(&mut state_data.it, index) {
+                parsers::ParseResult::Failed => {
+                    state_data.it.set_pos(it_pos);
+                    ActionResult::NextState(State::Text)
+                }
+                parsers::ParseResult::Yield(s, e) => {
+                    ActionResult::YieldState(State::Text, Yield::Heading(s, e))
+                }
+            }
+        }
         _ => ActionResult::NextState(State::Text),
     }
 }
@@ -493,6 +493,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_iter_with_empty_yaml_frontmatter() {
+        let test_str = "---\n---\nText";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(out_vec, [YamlFrontmatter("---\n---\n")]);
+    }
+
+    #[test]
+    fn test_iter_with_empty_yaml_frontmatter_at_end_of_file() {
+        let test_str = "---\n---";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(out_vec, [YamlFrontmatter("---\n---")]);
+    }
+
     #[test]
     fn test_iter_with_yaml_frontmatter_without_end_delimiter() {
         let test_str = "---\n\
@@ -513,7 +531,8 @@ mod tests {
         let output = MarkdownAnalyzerIter::new(&test_str);
         let out_vec: Vec<_> = output.collect();
 
-        assert_eq!(out_vec, []);
+        // not a frontmatter ... the dashes underline a setext heading
+        assert_eq!(out_vec, [Heading("yaml: true\n---")]);
     }
 
     #[test]
@@ -577,4 +596,570 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_iter_with_wiki_link_embed() {
+        let test_str = "abc ![[note]] def";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(out_vec, [Embed("![[note]]")]);
+    }
+
+    #[test]
+    fn test_iter_with_link_embed() {
+        let test_str = "abc ![alt](image.png) def";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(out_vec, [Embed("![alt](image.png)")]);
+    }
+
+    #[test]
+    fn test_iter_with_embed_at_start_of_line() {
+        let test_str = "text\n![[note]]";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(out_vec, [Embed("![[note]]")]);
+    }
+
+    #[test]
+    fn test_iter_with_exclamation_mark_before_space_and_link() {
+        let test_str = "Hello! [[link]]";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(out_vec, [WikiLink("[[link]]")]);
+    }
+
+    #[test]
+    fn test_iter_with_illegal_embed_followed_by_link() {
+        let test_str = "![illegal][[link]]";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(out_vec, [WikiLink("[[link]]")]);
+    }
+
+    #[test]
+    fn test_iter_with_embed_in_code_block() {
+        let test_str = "`![[no_embed]]`![[embed]]";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(out_vec, [CodeBlock("`![[no_embed]]`"), Embed("![[embed]]")]);
+    }
+
+    #[test]
+    fn test_iter_with_atx_heading() {
+        let test_str = "# Heading";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(out_vec, [Heading("# Heading")]);
+    }
+
+    #[test]
+    fn test_iter_with_atx_heading_level_6_and_line_end() {
+        let test_str = "text\n###### Heading 6\ntext";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(out_vec, [Heading("###### Heading 6")]);
+    }
+
+    #[test]
+    fn test_iter_with_too_many_hashes_is_no_heading() {
+        let test_str = "####### no heading";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert!(out_vec.is_empty());
+    }
+
+    #[test]
+    fn test_iter_with_hash_without_space_is_no_heading() {
+        let test_str = "#no_heading";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(out_vec, [Tag("#no_heading")]);
+    }
+
+    #[test]
+    fn test_iter_with_hash_in_text_is_no_heading() {
+        let test_str = "text # no heading";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert!(out_vec.is_empty());
+    }
+
+    #[test]
+    fn test_iter_with_atx_heading_after_empty_line() {
+        let test_str = "text\n\n## Heading\r\ntext";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(out_vec, [Heading("## Heading")]);
+    }
+
+    #[test]
+    fn test_iter_with_atx_heading_containing_link() {
+        let test_str = "# Heading [[link]]";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(
+            out_vec,
+            [Heading("# Heading [[link]]"), WikiLink("[[link]]")]
+        );
+    }
+
+    #[test]
+    fn test_iter_with_setext_heading_level_1() {
+        let test_str = "Heading\n===\ntext";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(out_vec, [Heading("Heading\n===")]);
+    }
+
+    #[test]
+    fn test_iter_with_setext_heading_level_2() {
+        let test_str = "text\n\nHeading\n---  \ntext";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(out_vec, [Heading("Heading\n---")]);
+    }
+
+    #[test]
+    fn test_iter_with_underline_after_empty_line_is_no_heading() {
+        let test_str = "text\n\n===\n";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert!(out_vec.is_empty());
+    }
+
+    #[test]
+    fn test_iter_with_list_item_is_no_setext_heading() {
+        let test_str = "text\n- item\n";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert!(out_vec.is_empty());
+    }
+
+    #[test]
+    fn test_iter_with_list_item_before_dashes_is_no_setext_heading() {
+        let test_str = "- item\n---\n";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert!(out_vec.is_empty());
+    }
+
+    #[test]
+    fn test_iter_with_ordered_list_item_before_dashes_is_no_setext_heading() {
+        let test_str = "1. item\n---\n";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert!(out_vec.is_empty());
+    }
+
+    #[test]
+    fn test_iter_with_blockquote_before_dashes_is_no_setext_heading() {
+        let test_str = "> quote\n---\n";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert!(out_vec.is_empty());
+    }
+
+    #[test]
+    fn test_iter_with_emphasis_in_setext_heading() {
+        let test_str = "*emphasis* heading\n---\n";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(out_vec, [Heading("*emphasis* heading\n---")]);
+    }
+
+    #[test]
+    fn test_iter_with_code_fence_is_no_setext_heading() {
+        let test_str = "```\ncode\n```\n---\n";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(out_vec, [CodeBlock("```\ncode\n```")]);
+    }
+
+    #[test]
+    fn test_iter_with_task_is_no_setext_heading() {
+        let test_str = "- [ ] task\n===\n";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(out_vec.len(), 1);
+        assert!(matches!(out_vec[0], Task { .. }));
+    }
+
+    #[test]
+    fn test_iter_with_inline_code_in_setext_heading() {
+        let test_str = "`code` heading\n===\n";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(
+            out_vec,
+            [CodeBlock("`code`"), Heading("`code` heading\n===")]
+        );
+    }
+
+    #[test]
+    fn test_iter_with_heading_in_code_block() {
+        let test_str = "```\n# no heading\n```\n# Heading";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(
+            out_vec,
+            [CodeBlock("```\n# no heading\n```"), Heading("# Heading")]
+        );
+    }
+
+    #[test]
+    fn test_iter_with_yaml_frontmatter_and_heading() {
+        let test_str = "---\nkey: value\n---\n# Heading";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(
+            out_vec,
+            [
+                YamlFrontmatter("---\nkey: value\n---\n"),
+                Heading("# Heading")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_iter_with_failed_link_before_hash_is_no_heading() {
+        let test_str = "text [# no heading";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert!(out_vec.is_empty());
+    }
+
+    #[test]
+    fn test_iter_with_block_id_at_end_of_paragraph() {
+        let test_str = "A paragraph ^block-1\nnext line";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(out_vec, [BlockId("^block-1")]);
+    }
+
+    #[test]
+    fn test_iter_with_block_id_at_end_of_file() {
+        let test_str = "A paragraph ^abc  ";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(out_vec, [BlockId("^abc")]);
+    }
+
+    #[test]
+    fn test_iter_with_block_id_on_own_line() {
+        let test_str = "> quote\n\n^quote-id\r\n";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(out_vec, [BlockId("^quote-id")]);
+    }
+
+    #[test]
+    fn test_iter_with_caret_inside_word_is_no_block_id() {
+        let test_str = "2^10 is 1024";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert!(out_vec.is_empty());
+    }
+
+    #[test]
+    fn test_iter_with_caret_not_at_end_of_line_is_no_block_id() {
+        let test_str = "^abc text";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert!(out_vec.is_empty());
+    }
+
+    #[test]
+    fn test_iter_with_block_id_in_wiki_link() {
+        let test_str = "[[note#^abc]]";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(out_vec, [WikiLink("[[note#^abc]]")]);
+    }
+
+    #[test]
+    fn test_iter_with_tag() {
+        let test_str = "text #tag text";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(out_vec, [Tag("#tag")]);
+    }
+
+    #[test]
+    fn test_iter_with_nested_tag_at_start_of_line() {
+        let test_str = "#project/alpha";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(out_vec, [Tag("#project/alpha")]);
+    }
+
+    #[test]
+    fn test_iter_with_tag_followed_by_punctuation() {
+        let test_str = "Done #status-ok, next";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(out_vec, [Tag("#status-ok")]);
+    }
+
+    #[test]
+    fn test_iter_with_unicode_tag() {
+        let test_str = "#übersicht";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(out_vec, [Tag("#übersicht")]);
+    }
+
+    #[test]
+    fn test_iter_with_number_is_no_tag() {
+        let test_str = "issue #123";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert!(out_vec.is_empty());
+    }
+
+    #[test]
+    fn test_iter_with_hash_inside_word_is_no_tag() {
+        let test_str = "C# and url.com/#anchor";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert!(out_vec.is_empty());
+    }
+
+    #[test]
+    fn test_iter_with_tag_in_code_block() {
+        let test_str = "`#no_tag` #tag";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(out_vec, [CodeBlock("`#no_tag`"), Tag("#tag")]);
+    }
+
+    #[test]
+    fn test_iter_with_heading_is_no_tag() {
+        let test_str = "# Heading\n## Other #tag";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(
+            out_vec,
+            [Heading("# Heading"), Heading("## Other #tag"), Tag("#tag")]
+        );
+    }
+
+    #[test]
+    fn test_iter_with_tag_in_wiki_link_section() {
+        let test_str = "[[note#section]]";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(out_vec, [WikiLink("[[note#section]]")]);
+    }
+
+    #[test]
+    fn test_iter_with_open_task() {
+        let test_str = "- [ ] open task";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(
+            out_vec,
+            [Task {
+                checked: false,
+                text: "open task",
+                line: 1,
+                due: None
+            }]
+        );
+    }
+
+    #[test]
+    fn test_iter_with_done_task_after_text() {
+        let test_str = "text\n* [x] done task\n";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(
+            out_vec,
+            [Task {
+                checked: true,
+                text: "done task",
+                line: 2,
+                due: None
+            }]
+        );
+    }
+
+    #[test]
+    fn test_iter_with_nested_ordered_task() {
+        let test_str = "- [ ] a\n  1. [X] b";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(
+            out_vec,
+            [
+                Task {
+                    checked: false,
+                    text: "a",
+                    line: 1,
+                    due: None
+                },
+                Task {
+                    checked: true,
+                    text: "b",
+                    line: 2,
+                    due: None
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_iter_with_task_with_due_date_emoji() {
+        let test_str = "- [ ] pay 📅 2026-10-20";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(
+            out_vec,
+            [Task {
+                checked: false,
+                text: "pay 📅 2026-10-20",
+                line: 1,
+                due: Some("2026-10-20")
+            }]
+        );
+    }
+
+    #[test]
+    fn test_iter_with_task_with_due_date_field() {
+        let test_str = "- [ ] pay [due:: 2026-10-20]";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(
+            out_vec,
+            [Task {
+                checked: false,
+                text: "pay [due:: 2026-10-20]",
+                line: 1,
+                due: Some("2026-10-20")
+            }]
+        );
+    }
+
+    #[test]
+    fn test_iter_with_task_with_invalid_due_date() {
+        let test_str = "- [ ] pay due:: soon";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(
+            out_vec,
+            [Task {
+                checked: false,
+                text: "pay due:: soon",
+                line: 1,
+                due: None
+            }]
+        );
+    }
+
+    #[test]
+    fn test_iter_with_task_containing_link_and_tag() {
+        let test_str = "- [ ] call [[bob]] #work";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(
+            out_vec,
+            [
+                Task {
+                    checked: false,
+                    text: "call [[bob]] #work",
+                    line: 1,
+                    due: None
+                },
+                WikiLink("[[bob]]"),
+                Tag("#work")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_iter_with_list_item_is_no_task() {
+        let test_str = "- item\n- [link](x)";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(out_vec, [Link("[link](x)")]);
+    }
+
+    #[test]
+    fn test_iter_with_checkbox_in_text_is_no_task() {
+        let test_str = "text - [ ] no task";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert!(out_vec.is_empty());
+    }
+
+    #[test]
+    fn test_iter_with_task_after_frontmatter() {
+        let test_str = "---\na: b\n---\n- [ ] task";
+        let output = MarkdownAnalyzerIter::new(test_str);
+        let out_vec: Vec<_> = output.collect();
+
+        assert_eq!(
+            out_vec,
+            [
+                YamlFrontmatter("---\na: b\n---\n"),
+                Task {
+                    checked: false,
+                    text: "task",
+                    line: 4,
+                    due: None
+                }
+            ]
+        );
+    }
 }
@@ -55,6 +55,11 @@ impl<'a> Utf8Iterator<'a> {
         self.next_char = self.peek_next();
     }
 
+    /// Returns the complete string slice the iterator operates on.
+    pub fn as_str(&self) -> &'a str {
+        self.slice
+    }
+
     pub fn peek(&self) -> Option<&(usize, char)> {
         self.next_char.as_ref()
     }
@@ -75,6 +80,7 @@ impl<'a> Iterator for Utf8Iterator<'a> {
 }
 
 #[cfg(test)]
+#[allow(clippy::clone_on_copy)]
 mod tests {
     use super::Utf8Iterator;
 
@@ -113,7 +113,7 @@ macro_rules! consume_expected_chars{
 /// # Arguments
 /// * `$iter` - An expression yielding a `Peekable<CharIndices>` iterator.
 /// * `$limit` - An optional limit for the number of characters to count.
-///             If `None`, the macro counts until a non-matching character is encountered.
+///   If `None`, the macro counts until a non-matching character is encountered.
 /// * `$($a:pat)+` - One or more patterns to match against each character.
 ///
 /// # Returns
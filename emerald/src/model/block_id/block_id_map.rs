@@ -0,0 +1,60 @@
+use super::block_id_retriever::BlockIdRetriever;
+use crate::types;
+use std::collections::HashMap;
+
+#[derive(Clone)]
+pub struct BlockIdMap {
+    block_ids_map: HashMap<types::ResourceId, Vec<String>>,
+}
+
+impl BlockIdMap {
+    pub fn new(it_src: impl IntoIterator<Item = (types::ResourceId, Vec<String>)>) -> Self {
+        let mut block_ids_map = HashMap::<types::ResourceId, Vec<String>>::new();
+        for (rid, block_ids) in it_src.into_iter() {
+            if block_ids_map.insert(rid, block_ids).is_some() {
+                panic!("This should not happen. No duplicate entries allowed.")
+            }
+        }
+        Self { block_ids_map }
+    }
+
+    pub fn update(
+        &mut self,
+        it_src: impl IntoIterator<Item = (types::ResourceId, Vec<String>)>,
+        removed: &[types::ResourceId],
+    ) {
+        for rid in removed {
+            self.block_ids_map.remove(rid);
+        }
+        self.block_ids_map.extend(it_src);
+    }
+}
+
+impl BlockIdRetriever for BlockIdMap {
+    fn retrieve(&self, rid: &types::ResourceId) -> Option<&[String]> {
+        self.block_ids_map.get(rid).map(|f| f.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BlockIdMap;
+    use super::BlockIdRetriever;
+    use crate::types::ResourceId;
+
+    #[test]
+    fn test_retrieve_known_rid() {
+        let dut = BlockIdMap::new(vec![("note.md".into(), vec!["block-1".into()])]);
+
+        let rid: ResourceId = "note.md".into();
+        assert_eq!(dut.retrieve(&rid), Some(["block-1".to_owned()].as_slice()));
+    }
+
+    #[test]
+    fn test_retrieve_unknown_rid_returns_none() {
+        let dut = BlockIdMap::new(vec![]);
+
+        let rid: ResourceId = "image.png".into();
+        assert!(dut.retrieve(&rid).is_none());
+    }
+}
@@ -0,0 +1,8 @@
+use crate::types;
+
+/// This trait is used to query a resource id for all contained block identifiers.
+pub trait BlockIdRetriever {
+    /// Returns the block identifiers (without the leading `^`) of the resource.
+    /// Returns `None` if the resource is no markdown file.
+    fn retrieve(&self, rid: &types::ResourceId) -> Option<&[String]>;
+}
@@ -0,0 +1,32 @@
+use super::block_id_map::BlockIdMap;
+use super::block_id_retriever::BlockIdRetriever;
+use crate::types;
+
+#[derive(Clone)]
+pub struct DefaultBlockIdModel {
+    block_id_map: BlockIdMap,
+}
+
+impl DefaultBlockIdModel {
+    pub fn new(it_src: impl IntoIterator<Item = (types::ResourceId, Vec<String>)>) -> Self {
+        Self {
+            block_id_map: BlockIdMap::new(it_src),
+        }
+    }
+
+    /// Replaces the block ids of the given notes and removes the block ids of the deleted
+    /// ones.
+    pub fn update(
+        &mut self,
+        it_src: impl IntoIterator<Item = (types::ResourceId, Vec<String>)>,
+        removed: &[types::ResourceId],
+    ) {
+        self.block_id_map.update(it_src, removed);
+    }
+}
+
+impl BlockIdRetriever for DefaultBlockIdModel {
+    fn retrieve(&self, rid: &types::ResourceId) -> Option<&[String]> {
+        self.block_id_map.retrieve(rid)
+    }
+}
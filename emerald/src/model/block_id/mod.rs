@@ -0,0 +1,6 @@
+mod block_id_map;
+mod block_id_retriever;
+mod default_block_id_model;
+
+pub use block_id_retriever::BlockIdRetriever;
+pub use default_block_id_model::DefaultBlockIdModel;
@@ -13,6 +13,16 @@ impl DefaultContentModel {
             md_content_map: MdContentMap::new(it_src),
         }
     }
+
+    /// Replaces the contents of the given notes and drops the contents of the
+    /// removed ones.
+    pub fn update(
+        &mut self,
+        it_src: impl IntoIterator<Item = (types::ResourceId, types::Content)>,
+        removed: &[types::ResourceId],
+    ) {
+        self.md_content_map.update(it_src, removed);
+    }
 }
 
 impl MdContentRetriever for DefaultContentModel {
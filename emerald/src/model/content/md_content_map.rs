@@ -22,6 +22,17 @@ impl MdContentMap {
 
         Self { res_id_to_content }
     }
+
+    pub fn update(
+        &mut self,
+        it_src: impl IntoIterator<Item = (types::ResourceId, types::Content)>,
+        removed: &[types::ResourceId],
+    ) {
+        for rid in removed {
+            self.res_id_to_content.remove(rid);
+        }
+        self.res_id_to_content.extend(it_src);
+    }
 }
 
 impl MdContentRetriever for MdContentMap {
@@ -1,5 +1,5 @@
 use crate::types;
 
-pub trait MdContentRetriever {
+pub trait MdContentRetriever: Send + Sync {
     fn retrieve(&self, rid: &types::ResourceId) -> &types::Content;
 }
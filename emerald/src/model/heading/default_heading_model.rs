@@ -0,0 +1,38 @@
+use super::heading_map::HeadingMap;
+use super::heading_retriever::HeadingRetriever;
+use crate::types;
+
+#[derive(Clone)]
+pub struct DefaultHeadingModel {
+    heading_map: HeadingMap,
+}
+
+impl DefaultHeadingModel {
+    pub fn new(it_src: impl IntoIterator<Item = (types::ResourceId, Vec<types::Heading>)>) -> Self {
+        Self {
+            heading_map: HeadingMap::new(it_src),
+        }
+    }
+
+    /// Replaces the headings of the given notes and removes the headings of the deleted
+    /// ones.
+    pub fn update(
+        &mut self,
+        it_src: impl IntoIterator<Item = (types::ResourceId, Vec<types::Heading>)>,
+        removed: &[types::ResourceId],
+    ) {
+        self.heading_map.update(it_src, removed);
+    }
+
+    /// Returns the outline tree of the resource.
+    pub fn get_outline(&self, rid: &types::ResourceId) -> Vec<types::OutlineNode> {
+        let headings = self.retrieve(rid).unwrap_or_default();
+        types::OutlineNode::create_outline(headings.iter().cloned())
+    }
+}
+
+impl HeadingRetriever for DefaultHeadingModel {
+    fn retrieve(&self, rid: &types::ResourceId) -> Option<&[types::Heading]> {
+        self.heading_map.retrieve(rid)
+    }
+}
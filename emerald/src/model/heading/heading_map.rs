@@ -0,0 +1,61 @@
+use super::heading_retriever::HeadingRetriever;
+use crate::types;
+use std::collections::HashMap;
+
+#[derive(Clone)]
+pub struct HeadingMap {
+    headings_map: HashMap<types::ResourceId, Vec<types::Heading>>,
+}
+
+impl HeadingMap {
+    pub fn new(it_src: impl IntoIterator<Item = (types::ResourceId, Vec<types::Heading>)>) -> Self {
+        let mut headings_map = HashMap::<types::ResourceId, Vec<types::Heading>>::new();
+        for (rid, headings) in it_src.into_iter() {
+            if headings_map.insert(rid, headings).is_some() {
+                panic!("This should not happen. No duplicate entries allowed.")
+            }
+        }
+        Self { headings_map }
+    }
+
+    pub fn update(
+        &mut self,
+        it_src: impl IntoIterator<Item = (types::ResourceId, Vec<types::Heading>)>,
+        removed: &[types::ResourceId],
+    ) {
+        for rid in removed {
+            self.headings_map.remove(rid);
+        }
+        self.headings_map.extend(it_src);
+    }
+}
+
+impl HeadingRetriever for HeadingMap {
+    fn retrieve(&self, rid: &types::ResourceId) -> Option<&[types::Heading]> {
+        self.headings_map.get(rid).map(|f| f.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HeadingMap;
+    use super::HeadingRetriever;
+    use crate::types::{Heading, ResourceId};
+
+    #[test]
+    fn test_retrieve_known_rid() {
+        let heading = Heading::new(1, "Heading".into(), (0, 9));
+        let dut = HeadingMap::new(vec![("note.md".into(), vec![heading.clone()])]);
+
+        let rid: ResourceId = "note.md".into();
+        assert_eq!(dut.retrieve(&rid), Some([heading].as_slice()));
+    }
+
+    #[test]
+    fn test_retrieve_unknown_rid_returns_none() {
+        let dut = HeadingMap::new(vec![]);
+
+        let rid: ResourceId = "image.png".into();
+        assert!(dut.retrieve(&rid).is_none());
+    }
+}
@@ -0,0 +1,8 @@
+use crate::types;
+
+/// This trait is used to query a resource id for all contained headings.
+pub trait HeadingRetriever {
+    /// Returns the headings of the resource in document order. Returns `None` if
+    /// the resource is no markdown file.
+    fn retrieve(&self, rid: &types::ResourceId) -> Option<&[types::Heading]>;
+}
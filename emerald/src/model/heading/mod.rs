@@ -0,0 +1,6 @@
+mod default_heading_model;
+mod heading_map;
+mod heading_retriever;
+
+pub use default_heading_model::DefaultHeadingModel;
+pub use heading_retriever::HeadingRetriever;
@@ -4,7 +4,9 @@ use super::src_links_map::SrcLinksMap;
 use super::tgt_iter_retriever::TgtIterRetriever;
 use super::tgt_links_map::TgtLinksMap;
 use crate::types;
+use std::collections::HashSet;
 
+#[derive(Clone)]
 pub struct DefaultLinkModel {
     link_index: Vec<types::LinkSrc2Tgt>,
     src_links_map: SrcLinksMap,
@@ -23,6 +25,37 @@ impl DefaultLinkModel {
             tgt_links_map,
         }
     }
+
+    /// Iterates over all links without copying them.
+    pub fn iter(&self) -> impl Iterator<Item = &types::LinkSrc2Tgt> {
+        self.link_index.iter()
+    }
+
+    /// Replaces all links of the given sources with the new links. Only the
+    /// entries of the sources and their targets are touched in the lookup maps.
+    ///
+    /// # Returns
+    ///
+    /// The replaced links.
+    pub fn update(
+        &mut self,
+        it_links_src_2_tgt: impl IntoIterator<Item = types::LinkSrc2Tgt>,
+        srcs: &[types::ResourceId],
+    ) -> Vec<types::LinkSrc2Tgt> {
+        let src_set: HashSet<_> = srcs.iter().collect();
+        let (old_links, link_index): (Vec<_>, Vec<_>) = std::mem::take(&mut self.link_index)
+            .into_iter()
+            .partition(|f| src_set.contains(&f.src));
+        self.src_links_map.remove(old_links.iter());
+        self.tgt_links_map.remove(srcs);
+
+        let new_links: Vec<_> = it_links_src_2_tgt.into_iter().collect();
+        self.src_links_map.insert(new_links.iter());
+        self.tgt_links_map.insert(new_links.iter());
+        self.link_index = link_index;
+        self.link_index.extend(new_links);
+        old_links
+    }
 }
 
 impl TgtIterRetriever for DefaultLinkModel {
@@ -49,3 +82,37 @@ impl LinksIterSrc for DefaultLinkModel {
         self.link_index.clone().into_iter()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::DefaultLinkModel;
+    use super::SrcIterRetriever;
+    use super::TgtIterRetriever;
+    use crate::types::{LinkSrc2Tgt, ResourceId};
+
+    #[test]
+    fn test_update_replaces_links_of_sources() {
+        let mut dut = DefaultLinkModel::new(
+            [("a", "a->b", "b"), ("a", "a->c", "c"), ("b", "b->c", "c")].map(LinkSrc2Tgt::from),
+        );
+        let old_links = dut.update(
+            [LinkSrc2Tgt::from(("a", "a->d", "d"))],
+            &["a".into(), "b".into()],
+        );
+        assert_eq!(old_links.len(), 3);
+
+        let tgts: Vec<_> = TgtIterRetriever::retrieve(&dut, &ResourceId::from("a"))
+            .unwrap()
+            .map(|f| f.tgt)
+            .collect();
+        assert_eq!(tgts, vec![Some("d".into())]);
+        assert!(TgtIterRetriever::retrieve(&dut, &ResourceId::from("b")).is_none());
+        assert!(SrcIterRetriever::retrieve(&dut, &ResourceId::from("c")).is_none());
+        assert_eq!(
+            SrcIterRetriever::retrieve(&dut, &ResourceId::from("d"))
+                .unwrap()
+                .count(),
+            1
+        );
+    }
+}
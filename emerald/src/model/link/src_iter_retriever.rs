@@ -1,7 +1,7 @@
 use crate::types;
 
 /// This trait is used to query an target id for all contained links and their pointing resource ids.
-pub trait SrcIterRetriever {
+pub trait SrcIterRetriever: Send + Sync {
     fn retrieve(
         &self,
         tgt: &types::ResourceId,
@@ -11,7 +11,15 @@ pub struct SrcLinksMap {
 
 impl SrcLinksMap {
     pub fn new<'a>(it_src: impl IntoIterator<Item = &'a types::LinkSrc2Tgt>) -> Self {
-        let mut src_2_tgt_map = Tgt2LinkFrmSrcMap::new();
+        let mut ret = Self {
+            src_2_tgt_map: Tgt2LinkFrmSrcMap::new(),
+        };
+        ret.insert(it_src);
+        ret
+    }
+
+    /// Adds the given links.
+    pub fn insert<'a>(&mut self, it_src: impl IntoIterator<Item = &'a types::LinkSrc2Tgt>) {
         for s2t in it_src.into_iter() {
             let link_from_source = s2t.get_link_from_source();
             let tgt = if let Some(tgt) = s2t.tgt.clone() {
@@ -19,7 +27,7 @@ impl SrcLinksMap {
             } else {
                 continue;
             };
-            match src_2_tgt_map.entry(tgt) {
+            match self.src_2_tgt_map.entry(tgt) {
                 Entry::Occupied(mut e) => {
                     e.get_mut().push(link_from_source);
                 }
@@ -28,7 +36,21 @@ impl SrcLinksMap {
                 }
             }
         }
-        Self { src_2_tgt_map }
+    }
+
+    /// Removes all links of the sources of the given links.
+    pub fn remove<'a>(&mut self, it_src: impl IntoIterator<Item = &'a types::LinkSrc2Tgt>) {
+        for s2t in it_src.into_iter() {
+            let Some(tgt) = s2t.tgt.as_ref() else {
+                continue;
+            };
+            if let Some(vec) = self.src_2_tgt_map.get_mut(tgt) {
+                vec.retain(|f| f.src != s2t.src);
+                if vec.is_empty() {
+                    self.src_2_tgt_map.remove(tgt);
+                }
+            }
+        }
     }
 }
 
@@ -61,7 +83,11 @@ mod tests {
 
         assert_eq!(
             res,
-            vec![types::LinkFrmSrc::new("o1->d1".into(), "o1".into())]
+            vec![types::LinkFrmSrc::new(
+                "o1->d1".into(),
+                "o1".into(),
+                types::LinkKind::WikiLink
+            )]
         );
     }
 
@@ -75,7 +101,11 @@ mod tests {
 
         assert_eq!(
             res,
-            vec![types::LinkFrmSrc::new("o1->d1".into(), "o1".into())]
+            vec![types::LinkFrmSrc::new(
+                "o1->d1".into(),
+                "o1".into(),
+                types::LinkKind::WikiLink
+            )]
         );
     }
 
@@ -94,7 +124,11 @@ mod tests {
 
         assert_eq!(
             res,
-            vec![types::LinkFrmSrc::new("o1->d1".into(), "o1".into(),)]
+            vec![types::LinkFrmSrc::new(
+                "o1->d1".into(),
+                "o1".into(),
+                types::LinkKind::WikiLink
+            )]
         );
     }
 }
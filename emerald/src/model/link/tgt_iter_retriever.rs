@@ -1,7 +1,7 @@
 use crate::types;
 
 /// This trait is used to query an source id and and return all links which points to this source
-pub trait TgtIterRetriever {
+pub trait TgtIterRetriever: Send + Sync {
     fn retrieve(
         &self,
         src: &types::ResourceId,
@@ -11,11 +11,19 @@ pub struct TgtLinksMap {
 
 impl TgtLinksMap {
     pub fn new<'a>(it_src: impl IntoIterator<Item = &'a types::LinkSrc2Tgt>) -> Self {
-        let mut link_2_tgt_map = Src2Link2TgtMap::new();
+        let mut ret = Self {
+            link_2_tgt_map: Src2Link2TgtMap::new(),
+        };
+        ret.insert(it_src);
+        ret
+    }
+
+    /// Adds the given links.
+    pub fn insert<'a>(&mut self, it_src: impl IntoIterator<Item = &'a types::LinkSrc2Tgt>) {
         for s2t in it_src.into_iter() {
             let link_to_target = s2t.get_link_to_target();
 
-            match link_2_tgt_map.entry(s2t.src.clone()) {
+            match self.link_2_tgt_map.entry(s2t.src.clone()) {
                 Entry::Occupied(mut e) => {
                     e.get_mut().push(link_to_target);
                 }
@@ -24,7 +32,13 @@ impl TgtLinksMap {
                 }
             }
         }
-        Self { link_2_tgt_map }
+    }
+
+    /// Removes all links of the given sources.
+    pub fn remove<'a>(&mut self, srcs: impl IntoIterator<Item = &'a types::ResourceId>) {
+        for src in srcs.into_iter() {
+            self.link_2_tgt_map.remove(src);
+        }
     }
 }
 
@@ -46,6 +60,7 @@ mod tests {
     use super::TgtIterRetriever;
     use super::TgtLinksMap;
     use crate::types::Link2Tgt;
+    use crate::types::LinkKind;
     use crate::types::LinkSrc2Tgt;
 
     #[test]
@@ -54,7 +69,14 @@ mod tests {
         let dut = TgtLinksMap::new(test_data.iter());
         let res: Vec<Link2Tgt> = dut.retrieve(&("o1".into())).unwrap().collect();
 
-        assert_eq!(res, vec![Link2Tgt::new("o1->d1".into(), Some("d1".into()))]);
+        assert_eq!(
+            res,
+            vec![Link2Tgt::new(
+                "o1->d1".into(),
+                Some("d1".into()),
+                LinkKind::WikiLink
+            )]
+        );
     }
 
     #[test]
@@ -68,8 +90,8 @@ mod tests {
         assert_eq!(
             res,
             vec![
-                Link2Tgt::new("o1->d1".into(), Some("d1".into())),
-                Link2Tgt::new("o1->d2".into(), Some("d2".into()))
+                Link2Tgt::new("o1->d1".into(), Some("d1".into()), LinkKind::WikiLink),
+                Link2Tgt::new("o1->d2".into(), Some("d2".into()), LinkKind::WikiLink)
             ]
         );
     }
@@ -90,8 +112,8 @@ mod tests {
         assert_eq!(
             res,
             vec![
-                Link2Tgt::new("o1->d1".into(), Some("d1".into())),
-                Link2Tgt::new("o1->d2".into(), Some("d2".into()))
+                Link2Tgt::new("o1->d1".into(), Some("d1".into()), LinkKind::WikiLink),
+                Link2Tgt::new("o1->d2".into(), Some("d2".into()), LinkKind::WikiLink)
             ]
         );
     }
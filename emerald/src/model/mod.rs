@@ -1,8 +1,12 @@
+pub mod block_id;
 pub mod content;
+pub mod heading;
 pub mod link;
 pub mod note;
 pub mod note_updater;
 pub mod resource;
 pub mod resource_id_resolver;
+pub mod tag;
+pub mod task;
 pub mod unique_id;
 pub mod vault;
@@ -6,6 +6,7 @@ use super::notes_iter_src::NotesIterSrc;
 
 use crate::types;
 
+#[derive(Clone)]
 pub struct DefaultNoteModel {
     note_index: Vec<types::ResourceId>,
     meta_data_map: NoteMetadataMap,
@@ -31,6 +32,33 @@ impl DefaultNoteModel {
             meta_data_map: NoteMetadataMap::new(it_note_meta),
         }
     }
+
+    /// Replaces the meta data of the given notes and removes the deleted notes.
+    /// New notes are appended to the index.
+    pub fn update(
+        &mut self,
+        it_note_meta_data: impl IntoIterator<
+            Item = (
+                types::ResourceId,
+                types::FilesystemMetadata,
+                types::DocumentMetadata,
+            ),
+        >,
+        removed: &[types::ResourceId],
+    ) {
+        let it_note_meta: Vec<(_, NoteMetadata)> = it_note_meta_data
+            .into_iter()
+            .map(|f| (f.0, (f.1, f.2).into()))
+            .collect();
+
+        self.note_index.retain(|rid| !removed.contains(rid));
+        for (rid, _) in it_note_meta.iter() {
+            if !self.meta_data_map.contains(rid) {
+                self.note_index.push(rid.clone());
+            }
+        }
+        self.meta_data_map.update(it_note_meta, removed);
+    }
 }
 
 impl NoteMetadataRetriever for DefaultNoteModel {
@@ -17,12 +17,12 @@ impl From<(types::FilesystemMetadata, types::DocumentMetadata)> for NoteMetadata
         };
 
         let doc_md = DocumentMetadata {
-            uid: value.1.uid,
+            uid: value.1.uid.and_then(|f| f.to_uid_string()),
             aliases: value.1.aliases.unwrap_or_default(),
             keywords: value.1.keywords.unwrap_or_default(),
             modified: value.1.modified,
             created: value.1.created,
-            tags: value.1.tags,
+            tags: value.1.tags.map(|f| f.to_vec()).unwrap_or_default(),
         };
 
         // get name of file
@@ -2,6 +2,7 @@ use super::{note_metadata::NoteMetadata, note_metadata_retriever::NoteMetadataRe
 use crate::types;
 use std::collections::HashMap;
 
+#[derive(Clone)]
 pub struct NoteMetadataMap {
     meta_data_map: HashMap<types::ResourceId, NoteMetadata>,
 }
@@ -16,6 +17,21 @@ impl NoteMetadataMap {
         }
         Self { meta_data_map }
     }
+
+    pub fn contains(&self, rid: &types::ResourceId) -> bool {
+        self.meta_data_map.contains_key(rid)
+    }
+
+    pub fn update(
+        &mut self,
+        it_src: impl IntoIterator<Item = (types::ResourceId, NoteMetadata)>,
+        removed: &[types::ResourceId],
+    ) {
+        for rid in removed {
+            self.meta_data_map.remove(rid);
+        }
+        self.meta_data_map.extend(it_src);
+    }
 }
 
 impl NoteMetadataRetriever for NoteMetadataMap {
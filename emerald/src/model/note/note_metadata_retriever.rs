@@ -3,6 +3,6 @@ use crate::types;
 use super::note_metadata::NoteMetadata;
 
 /// This trait is used to query an target id for all contained links and their pointing resource ids.
-pub trait NoteMetadataRetriever {
+pub trait NoteMetadataRetriever: Send + Sync {
     fn retrieve(&self, tgt: &types::ResourceId) -> &NoteMetadata;
 }
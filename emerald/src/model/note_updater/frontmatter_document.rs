@@ -0,0 +1,763 @@
+use super::yaml_emitter::{emit_flow, emit_string};
+use serde_yaml::{Mapping, Value};
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+const DEFAULT_ITEM_PREFIX: &str = "- ";
+
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+/// Blank lines and comments don't belong to an entry by themselves.
+fn is_filler(line: &str) -> bool {
+    is_blank(line) || line.starts_with('#')
+}
+
+/// Returns true if the line continues the value of the preceding entry. These are
+/// indented lines and items of a block sequence.
+fn is_continuation(line: &str) -> bool {
+    if is_blank(line) {
+        return false;
+    }
+    line.starts_with([' ', '\t']) || is_sequence_item(line)
+}
+
+fn is_sequence_item(line: &str) -> bool {
+    line.trim_start()
+        .strip_prefix('-')
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+}
+
+/// Returns the prefix of a block sequence item like `  - `.
+fn item_prefix_of(line: &str) -> Option<&str> {
+    if !is_sequence_item(line) {
+        return None;
+    }
+    let indent = line.len() - line.trim_start().len();
+    let rest = &line[indent + 1..];
+    let ws = rest.len() - rest.trim_start_matches([' ', '\t']).len();
+    Some(&line[..indent + 1 + ws]).filter(|f| !f.ends_with('-'))
+}
+
+/// Returns the line ending of the line.
+fn eol_of(line: &str) -> &str {
+    if line.ends_with("\r\n") {
+        "\r\n"
+    } else if line.ends_with('\n') {
+        "\n"
+    } else {
+        ""
+    }
+}
+
+/// Finds the end of a quoted scalar starting at the beginning of the text.
+/// Returns the byte index after the closing quote.
+fn end_of_quoted(text: &str) -> Option<usize> {
+    let quote = text.chars().next()?;
+    let mut chars = text.char_indices().skip(1).peekable();
+    while let Some((idx, ch)) = chars.next() {
+        match ch {
+            '\\' if quote == '"' => {
+                chars.next();
+            }
+            '\'' if quote == '\'' && chars.peek().is_some_and(|f| f.1 == '\'') => {
+                chars.next();
+            }
+            ch if ch == quote => return Some(idx + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parses the key of a top level entry.
+///
+/// # Returns
+///
+/// The key and the length of its raw representation in the line.
+fn parse_key(line: &str) -> Option<(String, usize)> {
+    if line.starts_with([' ', '\t', '#', '?', '[', '{', '|', '>']) || is_sequence_item(line) {
+        return None;
+    }
+
+    let (key, raw_len) = if line.starts_with(['"', '\'']) {
+        let end = end_of_quoted(line)?;
+        (serde_yaml::from_str::<String>(&line[..end]).ok()?, end)
+    } else {
+        let colon = line.char_indices().find_map(|(idx, ch)| {
+            let next = line[idx + ch.len_utf8()..].chars().next();
+            (ch == ':' && next.is_none_or(char::is_whitespace)).then_some(idx)
+        })?;
+        let raw = line[..colon].trim_end();
+        (raw.to_owned(), raw.len())
+    };
+
+    // the key must be followed by a colon
+    line[raw_len..]
+        .trim_start_matches([' ', '\t'])
+        .starts_with(':')
+        .then_some((key, raw_len))
+}
+
+/// The first line of an entry split into its parts.
+struct FirstLine<'a> {
+    /// Key, colon and the whitespaces in between.
+    head: &'a str,
+    sep: &'a str,
+    value: &'a str,
+
+    /// Comment including its leading whitespaces.
+    comment: &'a str,
+    eol: &'a str,
+}
+
+impl<'a> FirstLine<'a> {
+    fn new(line: &'a str, key_len: usize) -> Self {
+        let colon = key_len
+            + line[key_len..]
+                .find(':')
+                .expect("Key is followed by a colon");
+        let head = &line[..colon + 1];
+        let eol = eol_of(line);
+        let body = &line[colon + 1..line.len() - eol.len()];
+        let sep_len = body.len() - body.trim_start_matches([' ', '\t']).len();
+        let (sep, rest) = body.split_at(sep_len);
+
+        let value_len = if rest.starts_with(['"', '\'']) {
+            end_of_quoted(rest).unwrap_or(rest.len())
+        } else if rest.starts_with('#') {
+            0
+        } else {
+            rest.find(" #")
+                .or_else(|| rest.find("\t#"))
+                .unwrap_or(rest.len())
+        };
+        let value = rest[..value_len].trim_end();
+        let comment = &rest[value.len()..];
+
+        Self {
+            head,
+            sep,
+            value,
+            comment,
+            eol,
+        }
+    }
+}
+
+/// A top level entry of the frontmatter together with all lines of its value.
+#[derive(Debug, Clone)]
+struct Entry {
+    key: String,
+    key_len: usize,
+    lines: Vec<String>,
+}
+
+impl Entry {
+    fn first_line(&self) -> FirstLine<'_> {
+        FirstLine::new(&self.lines[0], self.key_len)
+    }
+
+    /// Returns the parsed value of the entry or None if it can't be parsed on its own.
+    fn value(&self) -> Option<Value> {
+        let mapping = serde_yaml::from_str::<Mapping>(&self.lines.concat()).ok()?;
+        mapping.into_iter().next().map(|(_, value)| value)
+    }
+
+    /// Returns true if the value is a flow sequence written on a single line.
+    fn is_flow_sequence(&self) -> bool {
+        let first_line = self.first_line();
+        self.lines.len() == 1
+            && first_line.value.starts_with('[')
+            && first_line.value.ends_with(']')
+    }
+
+    fn item_prefix(&self) -> Option<&str> {
+        self.lines[1..].iter().find_map(|f| item_prefix_of(f))
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Item {
+    /// Blank lines, comments and all other lines which aren't part of an entry.
+    Trivia(String),
+    Entry(Entry),
+}
+
+/// Line based representation of a yaml frontmatter.
+///
+/// Only the entries touched by an edit are rewritten. Comments, blank lines, the
+/// order of the keys and the formatting of all other entries are kept as they are.
+#[derive(Debug, Clone)]
+pub struct FrontmatterDocument {
+    opening: String,
+    items: Vec<Item>,
+    closing: String,
+    newline: String,
+    modified: bool,
+}
+
+impl FrontmatterDocument {
+    /// Creates a new empty frontmatter.
+    pub fn new() -> Self {
+        Self {
+            opening: "---\n".into(),
+            items: vec![],
+            closing: "---\n".into(),
+            newline: "\n".into(),
+            modified: true,
+        }
+    }
+
+    /// Parses a frontmatter including its delimiters.
+    pub fn parse(raw: &str) -> Self {
+        let mut lines: Vec<&str> = raw.split_inclusive('\n').collect();
+        let opening = if lines.is_empty() {
+            ""
+        } else {
+            lines.remove(0)
+        };
+        let closing = match lines.last() {
+            Some(last) if last.trim_end() == "---" => lines.pop().unwrap_or_default(),
+            _ => "",
+        };
+        let newline = match eol_of(opening) {
+            "" => "\n",
+            eol => eol,
+        };
+
+        Self {
+            opening: opening.into(),
+            items: Self::group_lines(&lines),
+            closing: closing.into(),
+            newline: newline.into(),
+            modified: false,
+        }
+    }
+
+    fn group_lines(lines: &[&str]) -> Vec<Item> {
+        let mut items = Vec::<Item>::new();
+        let mut idx = 0;
+        while idx < lines.len() {
+            let line = lines[idx];
+            if let Some(Item::Entry(entry)) = items.last_mut() {
+                if is_continuation(line) {
+                    entry.lines.push(line.into());
+                    idx += 1;
+                    continue;
+                }
+
+                // blank lines and comments belong to the entry if its value goes on
+                // after them
+                if is_filler(line) {
+                    let next = (idx..lines.len()).find(|f| !is_filler(lines[*f]));
+                    if let Some(next) = next.filter(|f| is_continuation(lines[*f])) {
+                        entry
+                            .lines
+                            .extend(lines[idx..next].iter().map(|f| f.to_string()));
+                        idx = next;
+                        continue;
+                    }
+                }
+            }
+
+            match parse_key(line) {
+                Some((key, key_len)) => items.push(Item::Entry(Entry {
+                    key,
+                    key_len,
+                    lines: vec![line.into()],
+                })),
+                None => items.push(Item::Trivia(line.into())),
+            }
+            idx += 1;
+        }
+        items
+    }
+
+    /// Returns true if an edit changed the frontmatter.
+    pub fn is_modified(&self) -> bool {
+        self.modified
+    }
+
+    /// Returns the frontmatter including its delimiters.
+    pub fn render(&self) -> String {
+        let mut out = self.opening.clone();
+        for item in self.items.iter() {
+            match item {
+                Item::Trivia(line) => out.push_str(line),
+                Item::Entry(entry) => entry.lines.iter().for_each(|f| out.push_str(f)),
+            }
+        }
+        out.push_str(&self.closing);
+        out
+    }
+
+    fn index_of(&self, key: &str) -> Option<usize> {
+        self.items
+            .iter()
+            .position(|f| matches!(f, Item::Entry(entry) if entry.key == key))
+    }
+
+    fn entry(&self, idx: usize) -> &Entry {
+        match &self.items[idx] {
+            Item::Entry(entry) => entry,
+            Item::Trivia(_) => panic!("Item is no entry"),
+        }
+    }
+
+    fn entry_mut(&mut self, idx: usize) -> &mut Entry {
+        self.modified = true;
+        match &mut self.items[idx] {
+            Item::Entry(entry) => entry,
+            Item::Trivia(_) => panic!("Item is no entry"),
+        }
+    }
+
+    /// Returns the prefix used for items of block sequences in this document.
+    fn default_item_prefix(&self) -> String {
+        self.items
+            .iter()
+            .find_map(|f| match f {
+                Item::Entry(entry) => entry.item_prefix(),
+                Item::Trivia(_) => None,
+            })
+            .unwrap_or(DEFAULT_ITEM_PREFIX)
+            .to_owned()
+    }
+
+    /// Renders the lines of an entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `head`: Key followed by a colon.
+    /// * `comment`: Comment of the first line including its leading whitespaces.
+    /// * `value`: Value of the entry.
+    /// * `item_prefix`: Prefix of block sequence items. Sequences are written in
+    ///   flow style if None.
+    fn render_entry(
+        &self,
+        head: &str,
+        comment: &str,
+        value: &Value,
+        item_prefix: Option<&str>,
+    ) -> Vec<String> {
+        match (value, item_prefix) {
+            (Value::Sequence(seq), Some(item_prefix)) if !seq.is_empty() => {
+                let items: Vec<String> = seq.iter().map(|f| emit_flow(f, false)).collect();
+                self.render_block_sequence(head, comment, &items, item_prefix)
+            }
+            (value, _) => {
+                vec![format!(
+                    "{} {}{}{}",
+                    head,
+                    emit_flow(value, false),
+                    comment,
+                    self.newline
+                )]
+            }
+        }
+    }
+
+    fn render_block_sequence(
+        &self,
+        head: &str,
+        comment: &str,
+        items: &[String],
+        item_prefix: &str,
+    ) -> Vec<String> {
+        let mut lines = vec![format!("{}{}{}", head, comment, self.newline)];
+        lines.extend(
+            items
+                .iter()
+                .map(|f| format!("{}{}{}", item_prefix, f, self.newline)),
+        );
+        lines
+    }
+
+    fn insert_entry(&mut self, key: &str, lines: Vec<String>) {
+        let idx = self
+            .items
+            .iter()
+            .rposition(|f| matches!(f, Item::Entry(_)))
+            .map(|f| f + 1)
+            .unwrap_or(self.items.len());
+
+        // the preceding line might be the last line of an unterminated frontmatter
+        let newline = self.newline.clone();
+        if let Some(prev) = idx.checked_sub(1).map(|f| &mut self.items[f]) {
+            let prev_line = match prev {
+                Item::Trivia(line) => line,
+                Item::Entry(entry) => entry.lines.last_mut().expect("Entry has lines"),
+            };
+            if !prev_line.ends_with('\n') {
+                prev_line.push_str(&newline);
+            }
+        }
+
+        self.items.insert(
+            idx,
+            Item::Entry(Entry {
+                key: key.into(),
+                key_len: emit_string(key, false).len(),
+                lines,
+            }),
+        );
+        self.modified = true;
+    }
+
+    /// Sets the value of an entry. The entry is left untouched if it already has
+    /// the value.
+    pub fn set(&mut self, key: &str, value: &Value) {
+        let Some(idx) = self.index_of(key) else {
+            let head = format!("{}:", emit_string(key, false));
+            let item_prefix = self.default_item_prefix();
+            let lines = self.render_entry(&head, "", value, Some(&item_prefix));
+            self.insert_entry(key, lines);
+            return;
+        };
+
+        let entry = self.entry(idx);
+        if entry.value().is_some_and(|f| is_same_value(&f, value)) {
+            return;
+        }
+
+        let item_prefix = if entry.is_flow_sequence() {
+            None
+        } else {
+            Some(
+                entry
+                    .item_prefix()
+                    .map(|f| f.to_owned())
+                    .unwrap_or_else(|| self.default_item_prefix()),
+            )
+        };
+        let first_line = entry.first_line();
+        let lines = self.render_entry(
+            first_line.head,
+            first_line.comment,
+            value,
+            item_prefix.as_deref(),
+        );
+        self.entry_mut(idx).lines = lines;
+    }
+
+    /// Removes an entry.
+    pub fn remove(&mut self, key: &str) {
+        if let Some(idx) = self.index_of(key) {
+            self.items.remove(idx);
+            self.modified = true;
+        }
+    }
+
+    /// Renames the key of an entry. An existing entry with the new key is removed.
+    pub fn rename(&mut self, key: &str, new_key: &str) {
+        if key == new_key || self.index_of(key).is_none() {
+            return;
+        }
+        self.remove(new_key);
+
+        let idx = self.index_of(key).expect("Entry exists");
+        let entry = self.entry_mut(idx);
+        let raw_key = emit_string(new_key, false);
+        entry.lines[0] = raw_key.clone() + &entry.lines[0][entry.key_len..];
+        entry.key = new_key.into();
+        entry.key_len = raw_key.len();
+    }
+
+    /// Appends a value to a sequence. Single values are turned into a sequence.
+    pub fn append_to_list(&mut self, key: &str, value: &Value) {
+        let Some(idx) = self.index_of(key) else {
+            self.set(key, &Value::Sequence(vec![value.clone()]));
+            return;
+        };
+
+        let entry = self.entry(idx);
+        let item = emit_flow(value, entry.is_flow_sequence());
+        match entry.value() {
+            Some(Value::Sequence(seq)) if seq.contains(value) => {}
+            Some(Value::Sequence(seq)) if entry.is_flow_sequence() => {
+                let first_line = entry.first_line();
+                let flow_value = match seq.is_empty() {
+                    true => format!("[{}]", item),
+                    false => {
+                        let value = first_line.value;
+                        format!("{}, {}]", value[..value.len() - 1].trim_end(), item)
+                    }
+                };
+                let line = format!(
+                    "{}{}{}{}{}",
+                    first_line.head, first_line.sep, flow_value, first_line.comment, first_line.eol
+                );
+                self.entry_mut(idx).lines = vec![line];
+            }
+            Some(Value::Sequence(_)) => {
+                let item_prefix = entry
+                    .item_prefix()
+                    .map(|f| f.to_owned())
+                    .unwrap_or_else(|| self.default_item_prefix());
+                let line = format!("{}{}{}", item_prefix, item, self.newline);
+
+                let entry = self.entry_mut(idx);
+                let last = entry.lines.last_mut().expect("Entry has lines");
+                if !last.ends_with('\n') {
+                    last.push('\n');
+                }
+                entry.lines.push(line);
+            }
+            Some(Value::Null) => self.set(key, &Value::Sequence(vec![value.clone()])),
+            Some(single_value) if single_value == *value => {}
+            Some(_) if entry.lines.len() == 1 => {
+                // keep the formatting of the existing value
+                let first_line = entry.first_line();
+                let items = [first_line.value.to_owned(), item];
+                let item_prefix = self.default_item_prefix();
+                let lines = self.render_block_sequence(
+                    first_line.head,
+                    first_line.comment,
+                    &items,
+                    &item_prefix,
+                );
+                self.entry_mut(idx).lines = lines;
+            }
+            Some(single_value) => {
+                let seq = Value::Sequence(vec![single_value, value.clone()]);
+                self.set(key, &seq);
+            }
+            None => warn!(
+                "Value of {} can't be interpreted. It is left unchanged.",
+                key
+            ),
+        }
+    }
+
+    /// Removes all occurrences of a value from a sequence. Single values which are
+    /// equal to the value are removed together with their key.
+    pub fn remove_from_list(&mut self, key: &str, value: &Value) {
+        let Some(idx) = self.index_of(key) else {
+            return;
+        };
+
+        let entry = self.entry(idx);
+        match entry.value() {
+            Some(Value::Sequence(seq)) if !seq.contains(value) => {}
+            Some(Value::Sequence(seq)) if entry.is_flow_sequence() => {
+                let remaining = seq.into_iter().filter(|f| f != value).collect();
+                self.set(key, &Value::Sequence(remaining));
+            }
+            Some(Value::Sequence(_)) => {
+                let (head, groups) = group_items(&entry.lines[1..]);
+                let mut lines = vec![entry.lines[0].clone()];
+                lines.extend(head.iter().map(|f| f.to_string()));
+                for group in groups {
+                    let item = serde_yaml::from_str::<Vec<Value>>(&group.concat()).ok();
+                    if item.is_some_and(|f| f.first() == Some(value)) {
+                        continue;
+                    }
+                    lines.extend(group.iter().map(|f| f.to_string()));
+                }
+
+                if lines.len() == 1 + head.len() {
+                    self.set(key, &Value::Sequence(vec![]));
+                } else {
+                    self.entry_mut(idx).lines = lines;
+                }
+            }
+            Some(single_value) if single_value == *value => self.remove(key),
+            _ => {}
+        }
+    }
+}
+
+/// Groups the lines of a block sequence by item.
+///
+/// Comments and blank lines directly above an item belong to the item. Lines
+/// before the first item are returned separately.
+fn group_items(lines: &[String]) -> (&[String], Vec<&[String]>) {
+    let indent = |line: &str| line.len() - line.trim_start().len();
+    let item_indent = lines
+        .iter()
+        .find(|f| is_sequence_item(f))
+        .map(|f| indent(f));
+    let is_item = |line: &str| is_sequence_item(line) && Some(indent(line)) == item_indent;
+
+    let Some(first) = lines.iter().position(|f| is_item(f)) else {
+        return (lines, vec![]);
+    };
+
+    let mut groups = Vec::<&[String]>::new();
+    let mut start = first;
+    for (idx, line) in lines.iter().enumerate().skip(first + 1) {
+        if is_item(line) {
+            let mut end = idx;
+            while end > start + 1 && is_filler(lines[end - 1].trim_start()) {
+                end -= 1;
+            }
+            groups.push(&lines[start..end]);
+            start = end;
+        }
+    }
+    groups.push(&lines[start..]);
+    (&lines[..first], groups)
+}
+
+/// Returns true if the existing value equals the new one. Texts are considered
+/// equal to numbers and booleans with the same representation.
+fn is_same_value(existing: &Value, new: &Value) -> bool {
+    match (existing, new) {
+        (Value::Number(_) | Value::Bool(_), Value::String(text)) => {
+            emit_flow(existing, false) == *text
+        }
+        _ => existing == new,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_key, FrontmatterDocument};
+    use serde_yaml::Value;
+
+    fn text(text: &str) -> Value {
+        Value::String(text.into())
+    }
+
+    #[test]
+    fn test_parse_key() {
+        assert_eq!(parse_key("key: value\n"), Some(("key".into(), 3)));
+        assert_eq!(parse_key("key:\n"), Some(("key".into(), 3)));
+        assert_eq!(parse_key("key : value\n"), Some(("key".into(), 3)));
+        assert_eq!(parse_key("url: http://x\n"), Some(("url".into(), 3)));
+        assert_eq!(parse_key("'my key': value\n"), Some(("my key".into(), 8)));
+        assert_eq!(parse_key("\"a:b\": value\n"), Some(("a:b".into(), 5)));
+    }
+
+    #[test]
+    fn test_parse_key_fails() {
+        assert_eq!(parse_key("  indented: value\n"), None);
+        assert_eq!(parse_key("- item\n"), None);
+        assert_eq!(parse_key("# comment: value\n"), None);
+        assert_eq!(parse_key("no key\n"), None);
+    }
+
+    #[test]
+    fn test_render_unchanged() {
+        let raw = "---\n# comment\na: 1 # one\n\nlist:\n  - x\n\n  - y\nb: 'q'\n---\n";
+        let dut = FrontmatterDocument::parse(raw);
+        assert_eq!(dut.render(), raw);
+        assert!(!dut.is_modified());
+    }
+
+    #[test]
+    fn test_set_keeps_comment() {
+        let mut dut = FrontmatterDocument::parse("---\na: 1 # one\nb: 2\n---\n");
+        dut.set("a", &text("x y"));
+        assert_eq!(dut.render(), "---\na: x y # one\nb: 2\n---\n");
+    }
+
+    #[test]
+    fn test_set_same_value_is_no_change() {
+        let mut dut = FrontmatterDocument::parse("---\nuid: 202310181530\n---\n");
+        dut.set("uid", &text("202310181530"));
+        assert!(!dut.is_modified());
+    }
+
+    #[test]
+    fn test_set_list_uses_style_of_document() {
+        let mut dut = FrontmatterDocument::parse("---\na:\n  - x\nb: 2\n---\n");
+        dut.set(
+            "b",
+            &Value::Sequence(vec![text("1"), Value::Number(2.into())]),
+        );
+        assert_eq!(dut.render(), "---\na:\n  - x\nb:\n  - '1'\n  - 2\n---\n");
+    }
+
+    #[test]
+    fn test_set_inserts_after_last_entry() {
+        let mut dut = FrontmatterDocument::parse("---\na: 1\n\n# trailing\n---\n");
+        dut.set("b", &Value::Bool(true));
+        assert_eq!(dut.render(), "---\na: 1\nb: true\n\n# trailing\n---\n");
+    }
+
+    #[test]
+    fn test_set_with_crlf() {
+        let mut dut = FrontmatterDocument::parse("---\r\na: 1\r\n---\r\n");
+        dut.set("b", &Value::Bool(true));
+        assert_eq!(dut.render(), "---\r\na: 1\r\nb: true\r\n---\r\n");
+    }
+
+    #[test]
+    fn test_rename_keeps_value_formatting() {
+        let mut dut = FrontmatterDocument::parse("---\na: [x, 'y'] # c\n---\n");
+        dut.rename("a", "b c");
+        assert_eq!(dut.render(), "---\nb c: [x, 'y'] # c\n---\n");
+    }
+
+    #[test]
+    fn test_append_to_flow_sequence() {
+        let mut dut = FrontmatterDocument::parse("---\ntags: [x, 'y'] # c\n---\n");
+        dut.append_to_list("tags", &text("z"));
+        assert_eq!(dut.render(), "---\ntags: [x, 'y', z] # c\n---\n");
+    }
+
+    #[test]
+    fn test_append_to_empty_flow_sequence() {
+        let mut dut = FrontmatterDocument::parse("---\ntags: []\n---\n");
+        dut.append_to_list("tags", &text("z"));
+        assert_eq!(dut.render(), "---\ntags: [z]\n---\n");
+    }
+
+    #[test]
+    fn test_append_to_block_sequence() {
+        let mut dut = FrontmatterDocument::parse("---\ntags:\n  - x # c\n\n  - 'y'\nb: 1\n---\n");
+        dut.append_to_list("tags", &text("z"));
+        assert_eq!(
+            dut.render(),
+            "---\ntags:\n  - x # c\n\n  - 'y'\n  - z\nb: 1\n---\n"
+        );
+    }
+
+    #[test]
+    fn test_append_to_quoted_single_value() {
+        let mut dut = FrontmatterDocument::parse("---\naliases: \"a\"\n---\n");
+        dut.append_to_list("aliases", &text("b"));
+        assert_eq!(dut.render(), "---\naliases:\n- \"a\"\n- b\n---\n");
+    }
+
+    #[test]
+    fn test_remove_from_block_sequence() {
+        let mut dut =
+            FrontmatterDocument::parse("---\ntags:\n  # first\n  - x\n  - 'y' # c\n  - x\n---\n");
+        dut.remove_from_list("tags", &text("x"));
+        assert_eq!(dut.render(), "---\ntags:\n  # first\n  - 'y' # c\n---\n");
+    }
+
+    #[test]
+    fn test_remove_from_block_sequence_with_comment_above_item() {
+        let mut dut =
+            FrontmatterDocument::parse("---\ntags:\n  - x\n\n  # about y\n  - y\n  - z\n---\n");
+        dut.remove_from_list("tags", &text("y"));
+        assert_eq!(dut.render(), "---\ntags:\n  - x\n  - z\n---\n");
+    }
+
+    #[test]
+    fn test_remove_from_flow_sequence() {
+        let mut dut = FrontmatterDocument::parse("---\ntags: [x, y] # c\n---\n");
+        dut.remove_from_list("tags", &text("x"));
+        assert_eq!(dut.render(), "---\ntags: [y] # c\n---\n");
+    }
+
+    #[test]
+    fn test_remove_keeps_comments() {
+        let mut dut = FrontmatterDocument::parse("---\n# about a\na: 1\n# about b\nb: 2\n---\n");
+        dut.remove("a");
+        assert_eq!(dut.render(), "---\n# about a\n# about b\nb: 2\n---\n");
+    }
+
+    #[test]
+    fn test_block_scalar_is_one_entry() {
+        let mut dut = FrontmatterDocument::parse("---\ntext: |\n  line 1\n\n  line 2\nb: 2\n---\n");
+        dut.set("text", &text("short"));
+        assert_eq!(dut.render(), "---\ntext: short\nb: 2\n---\n");
+    }
+}
@@ -0,0 +1,42 @@
+use serde_yaml::Value;
+
+/// Typed value of a frontmatter entry.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FrontmatterValue {
+    Text(String),
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+
+    /// Date in the format `YYYY-MM-DD`.
+    Date(String),
+
+    List(Vec<FrontmatterValue>),
+}
+
+impl FrontmatterValue {
+    pub fn to_yaml_value(&self) -> Value {
+        match self {
+            FrontmatterValue::Text(text) => Value::String(text.clone()),
+            FrontmatterValue::Bool(flag) => Value::Bool(*flag),
+            FrontmatterValue::Integer(number) => Value::Number((*number).into()),
+            FrontmatterValue::Float(number) => Value::Number((*number).into()),
+            FrontmatterValue::Date(date) => Value::String(date.clone()),
+            FrontmatterValue::List(list) => {
+                Value::Sequence(list.iter().map(|f| f.to_yaml_value()).collect())
+            }
+        }
+    }
+}
+
+impl From<&str> for FrontmatterValue {
+    fn from(value: &str) -> Self {
+        FrontmatterValue::Text(value.to_owned())
+    }
+}
+
+impl From<String> for FrontmatterValue {
+    fn from(value: String) -> Self {
+        FrontmatterValue::Text(value)
+    }
+}
@@ -1,6 +1,6 @@
 use super::resource_id_trait::ResourceIdTrait;
 
-pub trait MdContentRetriever<T: ResourceIdTrait> {
+pub trait MdContentRetriever<T: ResourceIdTrait>: Send + Sync {
     /// Retrieves content for the specified resource identifier.
     ///
     /// # Arguments
@@ -1,13 +1,19 @@
+mod frontmatter_document;
+mod frontmatter_value;
 mod md_content_retriever;
 mod note_update_command;
 mod resource_id_trait;
+mod yaml_emitter;
 
+use self::frontmatter_document::FrontmatterDocument;
+pub use self::frontmatter_value::FrontmatterValue;
 pub use self::md_content_retriever::MdContentRetriever;
 use self::resource_id_trait::ResourceIdTrait;
+use crate::error::{EmeraldError, Result};
 use crate::markdown::{DefaultMarkdownFrontmatterSplitter, MarkdownFrontmatterSplitter};
 pub use note_update_command::NoteUpdateCommand;
 use serde_yaml::Value;
-use std::rc::Rc;
+use std::sync::Arc;
 use NoteUpdateCommand::*;
 
 #[allow(unused_imports)]
@@ -16,10 +22,10 @@ use log::{debug, error, info, trace, warn};
 trait Command {
     fn execute(&self, note_updater: &mut dyn YamlCommandHandler);
 }
-#[derive(Debug, Clone, PartialEq, Hash, Default)]
+#[derive(Debug, Clone, PartialEq)]
 struct UpdateOrInsertCommand {
     key: String,
-    value: String,
+    value: FrontmatterValue,
 }
 
 impl Command for UpdateOrInsertCommand {
@@ -28,6 +34,62 @@ impl Command for UpdateOrInsertCommand {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Hash, Default)]
+struct RemoveCommand {
+    key: String,
+}
+
+impl Command for RemoveCommand {
+    fn execute(&self, note_updater: &mut dyn YamlCommandHandler) {
+        note_updater.remove_entry(&self.key);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Hash, Default)]
+struct RenameCommand {
+    key: String,
+    new_key: String,
+}
+
+impl Command for RenameCommand {
+    fn execute(&self, note_updater: &mut dyn YamlCommandHandler) {
+        note_updater.rename_entry(&self.key, &self.new_key);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct AppendToListCommand {
+    key: String,
+    value: FrontmatterValue,
+}
+
+impl Command for AppendToListCommand {
+    fn execute(&self, note_updater: &mut dyn YamlCommandHandler) {
+        note_updater.append_to_list(&self.key, &self.value);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct RemoveFromListCommand {
+    key: String,
+    value: FrontmatterValue,
+}
+
+impl Command for RemoveFromListCommand {
+    fn execute(&self, note_updater: &mut dyn YamlCommandHandler) {
+        note_updater.remove_from_list(&self.key, &self.value);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Hash, Default)]
+struct CreateFrontmatterCommand {}
+
+impl Command for CreateFrontmatterCommand {
+    fn execute(&self, note_updater: &mut dyn YamlCommandHandler) {
+        note_updater.create_frontmatter();
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Hash, Default)]
 struct DefaultDoNothingCommand {}
 
@@ -35,49 +97,80 @@ impl Command for DefaultDoNothingCommand {
     fn execute(&self, _note_updater: &mut dyn YamlCommandHandler) {}
 }
 trait YamlCommandHandler {
-    fn update_entry(&mut self, entry: &str, value: &str);
+    fn update_entry(&mut self, entry: &str, value: &FrontmatterValue);
+    fn remove_entry(&mut self, entry: &str);
+    fn rename_entry(&mut self, entry: &str, new_entry: &str);
+    fn append_to_list(&mut self, entry: &str, value: &FrontmatterValue);
+    fn remove_from_list(&mut self, entry: &str, value: &FrontmatterValue);
+    fn create_frontmatter(&mut self);
 }
 
 struct DefaultYamlCommandHandler {
-    val: Value,
+    document: Option<FrontmatterDocument>,
 }
 
 impl DefaultYamlCommandHandler {
-    fn new(val: Value) -> Self {
-        Self { val }
+    fn new(document: Option<FrontmatterDocument>) -> Self {
+        Self { document }
     }
 
-    fn into_value(self) -> Value {
-        self.val
+    /// Returns the frontmatter. It is created if the note has none.
+    fn document_mut(&mut self) -> &mut FrontmatterDocument {
+        self.document.get_or_insert_with(FrontmatterDocument::new)
+    }
+
+    fn into_document(self) -> Option<FrontmatterDocument> {
+        self.document
     }
 }
 impl YamlCommandHandler for DefaultYamlCommandHandler {
-    fn update_entry(&mut self, entry: &str, value: &str) {
-        // update yaml
-        // ...
-        let prop = self.val.get_mut(entry);
-
-        if let Some(mut prop) = prop {
-            if let Value::String(string) = &mut prop {
-                string.clear();
-                string.push_str(value)
-            }
-        } else {
-            let mapping = self.val.as_mapping_mut().unwrap();
-            let _ = mapping.insert(Value::String(entry.into()), Value::String(value.into()));
+    fn update_entry(&mut self, entry: &str, value: &FrontmatterValue) {
+        self.document_mut().set(entry, &value.to_yaml_value());
+    }
+
+    fn remove_entry(&mut self, entry: &str) {
+        if let Some(document) = self.document.as_mut() {
+            document.remove(entry);
         }
     }
+
+    fn rename_entry(&mut self, entry: &str, new_entry: &str) {
+        if let Some(document) = self.document.as_mut() {
+            document.rename(entry, new_entry);
+        }
+    }
+
+    fn append_to_list(&mut self, entry: &str, value: &FrontmatterValue) {
+        self.document_mut()
+            .append_to_list(entry, &value.to_yaml_value());
+    }
+
+    fn remove_from_list(&mut self, entry: &str, value: &FrontmatterValue) {
+        if let Some(document) = self.document.as_mut() {
+            document.remove_from_list(entry, &value.to_yaml_value());
+        }
+    }
+
+    fn create_frontmatter(&mut self) {
+        self.document_mut();
+    }
 }
 pub struct NoteUpdater<T: ResourceIdTrait> {
-    content_retriever: Rc<dyn MdContentRetriever<T>>,
+    content_retriever: Arc<dyn MdContentRetriever<T>>,
 }
 
 impl<T: ResourceIdTrait> NoteUpdater<T> {
-    pub fn new(content_retriever: Rc<dyn MdContentRetriever<T>>) -> Self {
+    pub fn new(content_retriever: Arc<dyn MdContentRetriever<T>>) -> Self {
         Self { content_retriever }
     }
 
-    pub fn update_note(&self, rid: &T, cmd: note_update_command::NoteUpdateCommand) -> String {
+    /// Applies the command to the frontmatter of the note and returns the updated
+    /// content. Fails if the frontmatter of the note is not a valid yaml mapping.
+    pub fn update_note(
+        &self,
+        rid: &T,
+        cmd: note_update_command::NoteUpdateCommand,
+    ) -> Result<String> {
         // read content
         let content = self.content_retriever.retrieve(rid);
         let markdown_splitter = DefaultMarkdownFrontmatterSplitter::new();
@@ -85,41 +178,46 @@ impl<T: ResourceIdTrait> NoteUpdater<T> {
         // split
         let (yaml, markdown) = markdown_splitter.split(content);
 
-        let val = match yaml {
-            Some(yaml_str) => {
-                let res = serde_yaml::from_str::<Value>(yaml_str);
-                res.unwrap()
-            }
-            None => {
-                let mapping = serde_yaml::Mapping::new();
-                serde_yaml::Value::Mapping(mapping)
+        // only valid frontmatters are edited
+        if let Some(yaml_str) = yaml {
+            match serde_yaml::from_str::<Value>(yaml_str) {
+                Ok(Value::Mapping(_)) | Ok(Value::Null) => {}
+                Ok(_) => {
+                    return Err(EmeraldError::InvalidFrontmatter(format!(
+                        "{:?} is not a mapping",
+                        rid
+                    )))
+                }
+                Err(err) => {
+                    return Err(EmeraldError::InvalidFrontmatter(format!(
+                        "{:?}: {}",
+                        rid, err
+                    )))
+                }
             }
-        };
+        }
 
-        let mut yaml_updater = DefaultYamlCommandHandler::new(val);
+        let raw_yaml = &content[..content.len() - markdown.len()];
+        let document = yaml.map(|_| FrontmatterDocument::parse(raw_yaml));
+        let mut yaml_updater = DefaultYamlCommandHandler::new(document);
         let concrete_cmd: Box<dyn Command> = match cmd {
             UpdateOrInsert { key: entry, value } => {
                 Box::new(UpdateOrInsertCommand { key: entry, value })
             }
+            Remove { key } => Box::new(RemoveCommand { key }),
+            Rename { key, new_key } => Box::new(RenameCommand { key, new_key }),
+            AppendToList { key, value } => Box::new(AppendToListCommand { key, value }),
+            RemoveFromList { key, value } => Box::new(RemoveFromListCommand { key, value }),
+            CreateFrontmatter => Box::new(CreateFrontmatterCommand {}),
             DoNothing => Box::new(DefaultDoNothingCommand {}),
         };
         concrete_cmd.execute(&mut yaml_updater);
-        let own_yaml = yaml_updater.into_value();
-
-        let yaml_string = if let serde_yaml::Value::Mapping(new_mapping) = own_yaml {
-            let len = new_mapping.len();
-            if len > 0 {
-                let new_yaml = serde_yaml::to_string(&new_mapping).unwrap();
-                "---\n".to_string() + new_yaml.as_str() + "---\n"
-            } else {
-                "".to_string()
-            }
-        } else {
-            "".to_string()
-        };
 
         // Output
-        yaml_string + markdown
+        match yaml_updater.into_document() {
+            Some(document) if document.is_modified() => Ok(document.render() + markdown),
+            _ => Ok(content.to_owned()),
+        }
     }
 }
 
@@ -136,7 +234,7 @@ mod note_updater_tests {
         }
     }
 
-    fn setup_md_content_retriever_mock(inp_str: String) -> Rc<MockMdContentRetrieverImpl> {
+    fn setup_md_content_retriever_mock(inp_str: String) -> Arc<MockMdContentRetrieverImpl> {
         let mut mock_cnt_retriever = MockMdContentRetrieverImpl::new();
         mock_cnt_retriever
             .expect_retrieve()
@@ -155,7 +253,7 @@ Text Test"
         let sut = NoteUpdater::new(mock_cnt_retriever);
         let rid = types::ResourceId("ex_resource_id_1".to_string().into_boxed_str());
 
-        let out = sut.update_note(&rid, DoNothing);
+        let out = sut.update_note(&rid, DoNothing).unwrap();
 
         assert_eq!(out, inp_str)
     }
@@ -173,7 +271,7 @@ Text Test"
         let mock_cnt_retriever = setup_md_content_retriever_mock(inp_str.clone());
         let sut = NoteUpdater::new(mock_cnt_retriever);
         let rid = types::ResourceId("ex_resource_id_1".to_string().into_boxed_str());
-        let out = sut.update_note(&rid, DoNothing);
+        let out = sut.update_note(&rid, DoNothing).unwrap();
         assert_eq!(out, inp_str)
     }
 
@@ -190,16 +288,8 @@ Text Test"
         let mock_cnt_retriever = setup_md_content_retriever_mock(inp_str.clone());
         let sut = NoteUpdater::new(mock_cnt_retriever);
         let rid = types::ResourceId("ex_resource_id_1".to_string().into_boxed_str());
-        let out = sut.update_note(&rid, DoNothing);
-        let out_str: String = "\
----
-yaml1: text1
-yaml2: null
----
-Test Text
-Text Test"
-            .into();
-        assert_eq!(out, out_str)
+        let out = sut.update_note(&rid, DoNothing).unwrap();
+        assert_eq!(out, inp_str)
     }
 
     #[test]
@@ -216,13 +306,15 @@ Text Test"
         let sut = NoteUpdater::new(mock_cnt_retriever);
         let rid = types::ResourceId("ex_resource_id_1".to_string().into_boxed_str());
 
-        let out = sut.update_note(
-            &rid,
-            UpdateOrInsert {
-                key: "yaml1".into(),
-                value: "replace".into(),
-            },
-        );
+        let out = sut
+            .update_note(
+                &rid,
+                UpdateOrInsert {
+                    key: "yaml1".into(),
+                    value: "replace".into(),
+                },
+            )
+            .unwrap();
 
         let out_str: String = "\
 ---
@@ -248,13 +340,15 @@ Text Test"
         let mock_cnt_retriever = setup_md_content_retriever_mock(inp_str.clone());
         let sut = NoteUpdater::new(mock_cnt_retriever);
         let rid = types::ResourceId("ex_resource_id_1".to_string().into_boxed_str());
-        let out = sut.update_note(
-            &rid,
-            UpdateOrInsert {
-                key: "yaml2".into(),
-                value: "replace".into(),
-            },
-        );
+        let out = sut
+            .update_note(
+                &rid,
+                UpdateOrInsert {
+                    key: "yaml2".into(),
+                    value: "replace".into(),
+                },
+            )
+            .unwrap();
 
         let out_str: String = "\
 ---
@@ -280,13 +374,15 @@ Text Test"
         let mock_cnt_retriever = setup_md_content_retriever_mock(inp_str.clone());
         let sut = NoteUpdater::new(mock_cnt_retriever);
         let rid = types::ResourceId("ex_resource_id_1".to_string().into_boxed_str());
-        let out = sut.update_note(
-            &rid,
-            UpdateOrInsert {
-                key: "yaml3".into(),
-                value: "insert".into(),
-            },
-        );
+        let out = sut
+            .update_note(
+                &rid,
+                UpdateOrInsert {
+                    key: "yaml3".into(),
+                    value: "insert".into(),
+                },
+            )
+            .unwrap();
 
         let out_str: String = "\
 ---
@@ -308,13 +404,15 @@ Text Test"
         let mock_cnt_retriever = setup_md_content_retriever_mock(inp_str.clone());
         let sut = NoteUpdater::new(mock_cnt_retriever);
         let rid = types::ResourceId("ex_resource_id_1".to_string().into_boxed_str());
-        let out = sut.update_note(
-            &rid,
-            UpdateOrInsert {
-                key: "yaml1".into(),
-                value: "insert".into(),
-            },
-        );
+        let out = sut
+            .update_note(
+                &rid,
+                UpdateOrInsert {
+                    key: "yaml1".into(),
+                    value: "insert".into(),
+                },
+            )
+            .unwrap();
 
         let out_str: String = "\
 ---
@@ -325,4 +423,186 @@ Text Test"
             .into();
         assert_eq!(out, out_str)
     }
+
+    fn update(inp_str: &str, cmd: NoteUpdateCommand) -> Result<String> {
+        let mock_cnt_retriever = setup_md_content_retriever_mock(inp_str.into());
+        let sut = NoteUpdater::new(mock_cnt_retriever);
+        let rid = types::ResourceId("ex_resource_id_1".to_string().into_boxed_str());
+        sut.update_note(&rid, cmd)
+    }
+
+    #[test]
+    fn test_update_note_replaces_non_string_value() {
+        let out = update(
+            "---\ndraft: [a, b]\n---\nText",
+            UpdateOrInsert {
+                key: "draft".into(),
+                value: FrontmatterValue::Bool(false),
+            },
+        );
+        assert_eq!(out.unwrap(), "---\ndraft: false\n---\nText");
+    }
+
+    #[test]
+    fn test_update_note_inserts_typed_values() {
+        let value = FrontmatterValue::List(vec![
+            FrontmatterValue::Integer(3),
+            FrontmatterValue::Float(1.5),
+            FrontmatterValue::Date("2023-10-18".into()),
+            FrontmatterValue::Text("text".into()),
+        ]);
+        let out = update(
+            "Text",
+            UpdateOrInsert {
+                key: "values".into(),
+                value,
+            },
+        );
+        assert_eq!(
+            out.unwrap(),
+            "---\nvalues:\n- 3\n- 1.5\n- 2023-10-18\n- text\n---\nText"
+        );
+    }
+
+    #[test]
+    fn test_update_note_remove_key() {
+        let out = update(
+            "---\nyaml1: text1\nyaml2: text2\n---\nText",
+            Remove {
+                key: "yaml1".into(),
+            },
+        );
+        assert_eq!(out.unwrap(), "---\nyaml2: text2\n---\nText");
+    }
+
+    #[test]
+    fn test_update_note_remove_last_key_keeps_frontmatter() {
+        let out = update(
+            "---\nyaml1: text1\n---\nText",
+            Remove {
+                key: "yaml1".into(),
+            },
+        );
+        assert_eq!(out.unwrap(), "---\n---\nText");
+    }
+
+    #[test]
+    fn test_update_note_rename_key_keeps_position() {
+        let out = update(
+            "---\nyaml1: text1\nyaml2: text2\n---\nText",
+            Rename {
+                key: "yaml1".into(),
+                new_key: "renamed".into(),
+            },
+        );
+        assert_eq!(out.unwrap(), "---\nrenamed: text1\nyaml2: text2\n---\nText");
+    }
+
+    #[test]
+    fn test_update_note_rename_key_replaces_existing_key() {
+        let out = update(
+            "---\nyaml1: text1\nyaml2: text2\n---\nText",
+            Rename {
+                key: "yaml2".into(),
+                new_key: "yaml1".into(),
+            },
+        );
+        assert_eq!(out.unwrap(), "---\nyaml1: text2\n---\nText");
+    }
+
+    #[test]
+    fn test_update_note_append_to_list() {
+        let out = update(
+            "---\ntags:\n- a\n---\nText",
+            AppendToList {
+                key: "tags".into(),
+                value: "b".into(),
+            },
+        );
+        assert_eq!(out.unwrap(), "---\ntags:\n- a\n- b\n---\nText");
+    }
+
+    #[test]
+    fn test_update_note_append_existing_value_to_list() {
+        let out = update(
+            "---\ntags:\n- a\n---\nText",
+            AppendToList {
+                key: "tags".into(),
+                value: "a".into(),
+            },
+        );
+        assert_eq!(out.unwrap(), "---\ntags:\n- a\n---\nText");
+    }
+
+    #[test]
+    fn test_update_note_append_to_single_value() {
+        let out = update(
+            "---\naliases: a\n---\nText",
+            AppendToList {
+                key: "aliases".into(),
+                value: "b".into(),
+            },
+        );
+        assert_eq!(out.unwrap(), "---\naliases:\n- a\n- b\n---\nText");
+    }
+
+    #[test]
+    fn test_update_note_append_to_missing_list() {
+        let out = update(
+            "Text",
+            AppendToList {
+                key: "aliases".into(),
+                value: "a".into(),
+            },
+        );
+        assert_eq!(out.unwrap(), "---\naliases:\n- a\n---\nText");
+    }
+
+    #[test]
+    fn test_update_note_remove_from_list() {
+        let out = update(
+            "---\ntags:\n- a\n- b\n- a\n---\nText",
+            RemoveFromList {
+                key: "tags".into(),
+                value: "a".into(),
+            },
+        );
+        assert_eq!(out.unwrap(), "---\ntags:\n- b\n---\nText");
+    }
+
+    #[test]
+    fn test_update_note_remove_from_single_value() {
+        let out = update(
+            "---\ntags: a\nyaml1: text1\n---\nText",
+            RemoveFromList {
+                key: "tags".into(),
+                value: "a".into(),
+            },
+        );
+        assert_eq!(out.unwrap(), "---\nyaml1: text1\n---\nText");
+    }
+
+    #[test]
+    fn test_update_note_create_frontmatter() {
+        let out = update("Text", CreateFrontmatter);
+        assert_eq!(out.unwrap(), "---\n---\nText");
+    }
+
+    #[test]
+    fn test_update_note_create_frontmatter_keeps_existing_one() {
+        let out = update("---\nyaml1: text1\n---\nText", CreateFrontmatter);
+        assert_eq!(out.unwrap(), "---\nyaml1: text1\n---\nText");
+    }
+
+    #[test]
+    fn test_update_note_identity_with_empty_frontmatter() {
+        let out = update("---\n---\nText", DoNothing);
+        assert_eq!(out.unwrap(), "---\n---\nText");
+    }
+
+    #[test]
+    fn test_update_note_with_invalid_frontmatter() {
+        let out = update("---\n- a list\n---\nText", DoNothing);
+        assert!(matches!(out, Err(EmeraldError::InvalidFrontmatter(_))));
+    }
 }
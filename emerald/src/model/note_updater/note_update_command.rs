@@ -1,4 +1,43 @@
+use super::frontmatter_value::FrontmatterValue;
+
+/// Changes which can be applied to the frontmatter of a note.
+///
+/// Notes without frontmatter get one as soon as a command adds an entry.
+#[derive(Debug, Clone, PartialEq)]
 pub enum NoteUpdateCommand {
-    UpdateOrInsert { key: String, value: String },
+    /// Sets the value of a key. Existing values are replaced regardless of their type.
+    UpdateOrInsert {
+        key: String,
+        value: FrontmatterValue,
+    },
+
+    /// Removes a key together with its value.
+    Remove {
+        key: String,
+    },
+
+    /// Renames a key while keeping its value and position. An existing entry with
+    /// the new name is replaced.
+    Rename {
+        key: String,
+        new_key: String,
+    },
+
+    /// Appends a value to a list. Missing keys are created, single values are
+    /// turned into a list. Values already contained in the list aren't added twice.
+    AppendToList {
+        key: String,
+        value: FrontmatterValue,
+    },
+
+    /// Removes all occurrences of a value from a list.
+    RemoveFromList {
+        key: String,
+        value: FrontmatterValue,
+    },
+
+    /// Adds an empty frontmatter to notes which have none.
+    CreateFrontmatter,
+
     DoNothing,
 }
@@ -1,4 +1,4 @@
-pub trait ResourceIdTrait: std::fmt::Debug {}
+pub trait ResourceIdTrait: std::fmt::Debug + Send + Sync {}
 
 // Blanket impl
-impl<T> ResourceIdTrait for T where T: std::fmt::Debug {}
+impl<T> ResourceIdTrait for T where T: std::fmt::Debug + Send + Sync {}
@@ -0,0 +1,134 @@
+use serde_yaml::Value;
+
+/// Characters which can't start a plain yaml scalar.
+const INDICATORS: &[char] = &[
+    '-', '?', ':', ',', '[', ']', '{', '}', '#', '&', '*', '!', '|', '>', '\'', '"', '%', '@', '`',
+];
+
+/// Characters which can't be part of a plain scalar inside a flow collection.
+const FLOW_INDICATORS: &[char] = &[',', '[', ']', '{', '}'];
+
+/// Returns true if the text can be written without quotes.
+fn is_plain_safe(text: &str, flow: bool) -> bool {
+    if text.is_empty()
+        || text.trim() != text
+        || text.starts_with(INDICATORS)
+        || text.ends_with(':')
+        || text.contains(": ")
+        || text.contains(" #")
+        || text.chars().any(|ch| ch.is_control())
+        || (flow && text.contains(FLOW_INDICATORS))
+    {
+        return false;
+    }
+
+    // texts like `true`, `null` or `42` would change their type
+    matches!(serde_yaml::from_str::<Value>(text), Ok(Value::String(parsed)) if parsed == text)
+}
+
+fn emit_double_quoted(text: &str) -> String {
+    let mut out = String::from('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            ch if ch.is_control() => out.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Emits a string as plain scalar if possible. Otherwise it is quoted.
+pub fn emit_string(text: &str, flow: bool) -> String {
+    if is_plain_safe(text, flow) {
+        text.to_owned()
+    } else if text.chars().any(|ch| ch.is_control()) {
+        emit_double_quoted(text)
+    } else {
+        format!("'{}'", text.replace('\'', "''"))
+    }
+}
+
+/// Emits a value in flow style, i.e. on a single line.
+///
+/// # Arguments
+///
+/// * `value`: Value to emit.
+/// * `flow`: True if the value is part of a flow collection.
+pub fn emit_flow(value: &Value, flow: bool) -> String {
+    match value {
+        Value::Null => "null".to_owned(),
+        Value::Bool(flag) => flag.to_string(),
+        Value::Number(number) => {
+            let text = number.to_string();
+            // keep floats recognizable as such
+            if number.is_f64() && text.chars().all(|ch| ch.is_ascii_digit() || ch == '-') {
+                text + ".0"
+            } else {
+                text
+            }
+        }
+        Value::String(text) => emit_string(text, flow),
+        Value::Sequence(seq) => {
+            let items: Vec<String> = seq.iter().map(|f| emit_flow(f, true)).collect();
+            format!("[{}]", items.join(", "))
+        }
+        Value::Mapping(mapping) => {
+            let items: Vec<String> = mapping
+                .iter()
+                .map(|(key, value)| format!("{}: {}", emit_flow(key, true), emit_flow(value, true)))
+                .collect();
+            format!("{{{}}}", items.join(", "))
+        }
+        Value::Tagged(tagged) => format!("{} {}", tagged.tag, emit_flow(&tagged.value, flow)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{emit_flow, emit_string};
+    use serde_yaml::Value;
+
+    #[test]
+    fn test_emit_string_plain() {
+        assert_eq!(emit_string("text with spaces", false), "text with spaces");
+        assert_eq!(emit_string("2023-10-18", false), "2023-10-18");
+        assert_eq!(emit_string("#tag/child", false), "'#tag/child'");
+    }
+
+    #[test]
+    fn test_emit_string_which_looks_like_other_type() {
+        assert_eq!(emit_string("0", false), "'0'");
+        assert_eq!(emit_string("true", false), "'true'");
+        assert_eq!(emit_string("null", false), "'null'");
+        assert_eq!(emit_string("", false), "''");
+    }
+
+    #[test]
+    fn test_emit_string_with_special_chars() {
+        assert_eq!(emit_string("a: b", false), "'a: b'");
+        assert_eq!(emit_string("it's", false), "it's");
+        assert_eq!(emit_string("'quoted'", false), "'''quoted'''");
+        assert_eq!(emit_string("line\nbreak", false), "\"line\\nbreak\"");
+    }
+
+    #[test]
+    fn test_emit_string_in_flow_collection() {
+        assert_eq!(emit_string("a, b", false), "a, b");
+        assert_eq!(emit_string("a, b", true), "'a, b'");
+    }
+
+    #[test]
+    fn test_emit_flow() {
+        let value: Value = serde_yaml::from_str("[1, 1.0, true, text, 'a, b', {k: v}]").unwrap();
+        assert_eq!(
+            emit_flow(&value, false),
+            "[1, 1.0, true, text, 'a, b', {k: v}]"
+        );
+    }
+}
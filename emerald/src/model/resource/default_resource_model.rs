@@ -5,6 +5,7 @@ use super::resource_metadata_map::ResourceMetadataMap;
 use super::resource_metadata_retriever::ResourceMetadataRetriever;
 use crate::types;
 
+#[derive(Clone)]
 pub struct DefaultResourceModel {
     resource_index: Vec<types::ResourceId>,
     meta_data_map: ResourceMetadataMap,
@@ -24,6 +25,31 @@ impl DefaultResourceModel {
             meta_data_map: ResourceMetadataMap::new(files),
         }
     }
+
+    pub fn contains(&self, rid: &types::ResourceId) -> bool {
+        self.meta_data_map.contains(rid)
+    }
+
+    /// Replaces the meta data of the given resources and removes the deleted
+    /// resources. New resources are appended to the index.
+    pub fn update<'a>(
+        &mut self,
+        it_files: impl IntoIterator<Item = &'a (types::ResourceId, types::FilesystemMetadata)> + 'a,
+        removed: &[types::ResourceId],
+    ) {
+        let files: Vec<_> = it_files
+            .into_iter()
+            .map(|(rid, fs_md)| (rid, ResourceMetadata::from(fs_md)))
+            .collect();
+
+        self.resource_index.retain(|rid| !removed.contains(rid));
+        for (rid, _) in files.iter() {
+            if !self.meta_data_map.contains(rid) {
+                self.resource_index.push((*rid).clone());
+            }
+        }
+        self.meta_data_map.update(files, removed);
+    }
 }
 impl ResourceIterSrc for DefaultResourceModel {
     type Iter = std::vec::IntoIter<types::ResourceId>;
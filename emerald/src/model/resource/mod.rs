@@ -7,5 +7,6 @@ mod resource_metadata_retriever;
 
 pub use default_resource_model::DefaultResourceModel;
 pub use resource_count::ResourceCount;
+#[allow(unused_imports)]
 pub use resource_iter_src::ResourceIterSrc;
 pub use resource_metadata_retriever::ResourceMetadataRetriever;
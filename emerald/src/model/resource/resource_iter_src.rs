@@ -1,5 +1,6 @@
 use crate::types;
 
+#[allow(dead_code)]
 pub trait ResourceIterSrc {
     type Iter: Iterator<Item = types::ResourceId>;
     fn create_iter(&self) -> Self::Iter;
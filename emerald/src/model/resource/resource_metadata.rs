@@ -3,6 +3,7 @@ use std::path::PathBuf;
 use crate::types;
 
 #[derive(Clone)]
+#[allow(dead_code)]
 pub struct ResourceMetadata {
     pub path: PathBuf,
     pub resource_type: types::ResourceType,
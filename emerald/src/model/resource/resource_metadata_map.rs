@@ -4,6 +4,7 @@ use super::{
 use crate::types;
 use std::collections::HashMap;
 
+#[derive(Clone)]
 pub struct ResourceMetadataMap {
     meta_data_map: HashMap<types::ResourceId, ResourceMetadata>,
 }
@@ -20,6 +21,22 @@ impl ResourceMetadataMap {
         }
         Self { meta_data_map }
     }
+
+    pub fn contains(&self, rid: &types::ResourceId) -> bool {
+        self.meta_data_map.contains_key(rid)
+    }
+
+    pub fn update<'a>(
+        &mut self,
+        it_src: impl IntoIterator<Item = (&'a types::ResourceId, ResourceMetadata)> + 'a,
+        removed: &[types::ResourceId],
+    ) {
+        for rid in removed {
+            self.meta_data_map.remove(rid);
+        }
+        self.meta_data_map
+            .extend(it_src.into_iter().map(|(rid, f)| (rid.to_owned(), f)));
+    }
 }
 
 impl ResourceMetadataRetriever for ResourceMetadataMap {
@@ -0,0 +1,7 @@
+use crate::types;
+
+/// This trait is used to query the aliases which are shadowed by filenames.
+pub trait AliasConflictRetriever {
+    /// Returns every alias which matches the filename of another note.
+    fn retrieve_alias_conflicts(&self) -> &[types::AliasConflict];
+}
@@ -1,29 +1,59 @@
+use super::alias_conflict_retriever::AliasConflictRetriever;
 use super::resource_id_link_map::ResourceIdLinkMap;
-use super::resource_id_resolver_trait::ResourceIdResolver;
+use super::resource_id_resolver_trait::{Hint, ResourceIdResolver};
 use super::ResourceLoc;
 use crate::types;
 
 #[allow(unused_imports)]
 use log::{debug, error, info, trace, warn};
 
+#[derive(Clone)]
 pub struct DefaultResourceIdResolverModel {
     link_map: ResourceIdLinkMap,
 }
 
 impl DefaultResourceIdResolverModel {
-    pub fn new<'a>(it_src: impl IntoIterator<Item = ResourceLoc> + 'a) -> Self {
+    /// Creates the model from the locations of all resources and the aliases of
+    /// the notes.
+    pub fn new<'a>(
+        it_src: impl IntoIterator<Item = ResourceLoc> + 'a,
+        it_aliases: impl IntoIterator<Item = (types::ResourceId, String)> + 'a,
+    ) -> Self {
         Self {
-            link_map: ResourceIdLinkMap::new(it_src),
+            link_map: ResourceIdLinkMap::new(it_src, it_aliases),
         }
     }
+
+    /// Replaces the locations and the aliases of the given resources. Resources
+    /// without a new location are removed.
+    pub fn update<'a>(
+        &mut self,
+        it_src: impl IntoIterator<Item = ResourceLoc> + 'a,
+        it_aliases: impl IntoIterator<Item = (types::ResourceId, String)> + 'a,
+        rids: &[types::ResourceId],
+    ) {
+        self.link_map.update(it_src, it_aliases, rids);
+    }
+
+    /// Returns the resources whose filename or alias matches the normalized name,
+    /// with or without the markdown extension.
+    pub fn rids_with_name(&self, name: &str) -> Vec<&types::ResourceId> {
+        self.link_map.rids_with_name(name)
+    }
 }
 
 impl ResourceIdResolver for DefaultResourceIdResolverModel {
     fn resolve_with_hint(
         &self,
         link: &types::Link,
-        hint: super::resource_id_resolver_trait::Hint,
+        hint: Hint,
     ) -> crate::Result<&types::ResourceId> {
         self.link_map.resolve_with_hint(link, hint)
     }
 }
+
+impl AliasConflictRetriever for DefaultResourceIdResolverModel {
+    fn retrieve_alias_conflicts(&self) -> &[types::AliasConflict] {
+        self.link_map.alias_conflicts()
+    }
+}
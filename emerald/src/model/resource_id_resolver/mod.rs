@@ -1,8 +1,11 @@
+mod alias_conflict_retriever;
 mod default_link_resolver_model;
 mod resource_id_link_map;
 mod resource_id_resolver_trait;
 mod resource_loc;
 
+pub use alias_conflict_retriever::AliasConflictRetriever;
 pub use default_link_resolver_model::DefaultResourceIdResolverModel;
-pub use resource_id_resolver_trait::ResourceIdResolver;
+pub use resource_id_link_map::{norm_filename, norm_name};
+pub use resource_id_resolver_trait::{Hint, ResourceIdResolver};
 pub use resource_loc::ResourceLoc;
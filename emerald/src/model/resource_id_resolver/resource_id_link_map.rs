@@ -16,13 +16,84 @@ type NameToResourceIdList = HashMap<Name, Vec<(types::ResourceId, PathStr)>>;
 #[derive(Clone)]
 pub struct ResourceIdLinkMap {
     name_to_rid_list: NameToResourceIdList,
+    alias_to_rid_list: NameToResourceIdList,
+    alias_conflicts: Vec<types::AliasConflict>,
+    aliases: Vec<(types::ResourceId, String)>,
 }
 
 impl ResourceIdLinkMap {
-    pub fn new<'a>(it_src: impl IntoIterator<Item = ResourceLoc> + 'a) -> Self {
+    pub fn new<'a>(
+        it_src: impl IntoIterator<Item = ResourceLoc> + 'a,
+        it_aliases: impl IntoIterator<Item = (types::ResourceId, String)> + 'a,
+    ) -> Self {
         // Assumption: All resource ids are encoded in utf8 nfc
         let mut name_to_rid_list: NameToResourceIdList = NameToResourceIdList::new();
+        Self::insert_locations(&mut name_to_rid_list, it_src);
 
+        let aliases: Vec<_> = it_aliases.into_iter().collect();
+        let (alias_to_rid_list, alias_conflicts) = Self::index_aliases(&name_to_rid_list, &aliases);
+
+        ResourceIdLinkMap {
+            name_to_rid_list,
+            alias_to_rid_list,
+            alias_conflicts,
+            aliases,
+        }
+    }
+
+    /// Replaces the locations and the aliases of the given resources. Resources
+    /// without a new location are removed. The aliases are indexed again since a
+    /// changed filename may shadow any of them.
+    pub fn update<'a>(
+        &mut self,
+        it_src: impl IntoIterator<Item = ResourceLoc> + 'a,
+        it_aliases: impl IntoIterator<Item = (types::ResourceId, String)> + 'a,
+        rids: &[types::ResourceId],
+    ) {
+        for rid in rids {
+            let name = norm_filename(rid);
+            if let Some(rid_list) = self.name_to_rid_list.get_mut(name.as_str()) {
+                rid_list.retain(|(other, _)| other != rid);
+                if rid_list.is_empty() {
+                    self.name_to_rid_list.remove(name.as_str());
+                }
+            }
+        }
+        Self::insert_locations(&mut self.name_to_rid_list, it_src);
+
+        self.aliases.retain(|(rid, _)| !rids.contains(rid));
+        self.aliases.extend(it_aliases);
+        (self.alias_to_rid_list, self.alias_conflicts) =
+            Self::index_aliases(&self.name_to_rid_list, &self.aliases);
+    }
+
+    /// Returns the resources whose filename or alias matches the normalized name,
+    /// with or without the markdown extension.
+    pub fn rids_with_name(&self, name: &str) -> Vec<&types::ResourceId> {
+        let stem = name.strip_suffix(".md").unwrap_or(name);
+        let name_md = format!("{}.md", stem);
+        [stem, name_md.as_str()]
+            .into_iter()
+            .flat_map(|f| {
+                self.name_to_rid_list
+                    .get(f)
+                    .into_iter()
+                    .chain(self.alias_to_rid_list.get(f))
+            })
+            .flatten()
+            .map(|(rid, _)| rid)
+            .collect()
+    }
+
+    /// Returns every alias which matches the filename of another note.
+    pub fn alias_conflicts(&self) -> &[types::AliasConflict] {
+        &self.alias_conflicts
+    }
+
+    fn insert_locations(
+        name_to_rid_list: &mut NameToResourceIdList,
+        it_src: impl IntoIterator<Item = ResourceLoc>,
+    ) {
         for link_data in it_src.into_iter() {
             // this is an interesting way to mutate an element in a HashMap
             match name_to_rid_list.entry(link_data.norm_filename) {
@@ -34,16 +105,135 @@ impl ResourceIdLinkMap {
                 }
             }
         }
+    }
+
+    /// Normalizes the aliases like filenames and indexes them. Aliases which
+    /// match the filename of another note are reported as conflict.
+    fn index_aliases(
+        name_to_rid_list: &NameToResourceIdList,
+        aliases: &[(types::ResourceId, String)],
+    ) -> (NameToResourceIdList, Vec<types::AliasConflict>) {
+        let rid_to_dir: HashMap<&types::ResourceId, &PathStr> = name_to_rid_list
+            .values()
+            .flatten()
+            .map(|(rid, dir_path)| (rid, dir_path))
+            .collect();
+
+        let mut alias_to_rid_list = NameToResourceIdList::new();
+        let mut alias_conflicts: Vec<types::AliasConflict> = vec![];
+
+        for (rid, alias) in aliases {
+            let norm_alias = norm_name(alias);
+            if norm_alias.is_empty() {
+                continue;
+            }
+            let Some(dir_path) = rid_to_dir.get(rid) else {
+                warn!(
+                    "Alias {} belongs to the unknown resource {:?}",
+                    &alias, &rid
+                );
+                continue;
+            };
+
+            // filenames take precedence over aliases
+            let shadowing_names = [norm_alias.clone(), norm_alias.clone() + ".md"];
+            for (other, _) in shadowing_names
+                .iter()
+                .filter_map(|f| name_to_rid_list.get(f.as_str()))
+                .flatten()
+                .filter(|(other, _)| other != rid)
+            {
+                warn!(
+                    "The alias {} of {:?} is shadowed by the filename of {:?}",
+                    &alias, &rid, other
+                );
+                alias_conflicts.push(types::AliasConflict {
+                    alias: alias.clone(),
+                    alias_of: rid.clone(),
+                    shadowed_by: other.clone(),
+                });
+            }
+
+            let rid_list = alias_to_rid_list
+                .entry(norm_alias.into_boxed_str())
+                .or_default();
+            if !rid_list.iter().any(|(other, _)| other == rid) {
+                rid_list.push((rid.clone(), (*dir_path).clone()));
+            }
+        }
 
-        ResourceIdLinkMap { name_to_rid_list }
+        (alias_to_rid_list, alias_conflicts)
     }
 }
 
+/// Normalizes the name of a link or an alias for a lookup in the index.
+pub fn norm_name(name: &str) -> String {
+    utils::normalize_str(&name.trim().to_lowercase())
+}
+
+/// Returns the normalized filename of a resource as it is stored in the index.
+pub fn norm_filename(rid: &types::ResourceId) -> String {
+    let filename = rid.0.rsplit(['/', '\\']).next().unwrap_or_default();
+    utils::normalize_str(&filename.to_lowercase())
+}
+
+/// Splits a directory path into its components.
+fn dir_components(dir: &str) -> impl Iterator<Item = &str> {
+    dir.split(['/', '\\']).filter(|f| !f.is_empty())
+}
+
+/// Returns true if `suffix` equals the trailing directories of `dir`.
+fn is_dir_suffix(dir: &str, suffix: &str) -> bool {
+    let dir: Vec<&str> = dir_components(dir).collect();
+    let suffix: Vec<&str> = dir_components(suffix).collect();
+    !suffix.is_empty() && dir.ends_with(&suffix)
+}
+
+/// Returns the number of directory changes needed to get from one directory to
+/// another one.
+fn dir_distance(from: &str, to: &str) -> usize {
+    let from: Vec<&str> = dir_components(from).collect();
+    let to: Vec<&str> = dir_components(to).collect();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+    from.len() + to.len() - 2 * common
+}
+
+/// Selects the candidate Obsidian would pick for an ambiguous link.
+///
+/// With a source directory the nearest candidate wins. Otherwise, or if several
+/// candidates are equally near, the candidate closest to the vault root wins.
+/// The remaining ties are broken by the directory name.
+fn select_nearest<'a>(
+    candidates: impl IntoIterator<Item = &'a (types::ResourceId, PathStr)>,
+    source_dir: Option<&str>,
+) -> Option<&'a (types::ResourceId, PathStr)> {
+    let rank = |dir: &PathStr| {
+        let distance = source_dir.map_or(0, |src| dir_distance(src, dir));
+        (distance, dir_components(dir).count())
+    };
+
+    let mut candidates: Vec<_> = candidates.into_iter().collect();
+    candidates.sort_by(|a, b| rank(&a.1).cmp(&rank(&b.1)).then_with(|| a.1.cmp(&b.1)));
+
+    if candidates.len() > 1 && rank(&candidates[0].1) == rank(&candidates[1].1) {
+        warn!(
+            "The candidates {:?} and {:?} are equally near.",
+            candidates[0].0, candidates[1].0
+        );
+    }
+    candidates.first().copied()
+}
+
 impl ResourceIdResolver for ResourceIdLinkMap {
-    fn resolve_with_hint(&self, link: &types::Link, _hint: Hint) -> Result<&types::ResourceId> {
+    fn resolve_with_hint(&self, link: &types::Link, hint: Hint) -> Result<&types::ResourceId> {
         // convert string to internal link format
         let link_comp = link.split()?;
-        let link_name_lc = utils::normalize_str(&link_comp.name.trim().to_lowercase());
+        let link_name_lc = norm_name(&link_comp.name);
+
+        let source_dir = match hint {
+            Hint::NoHint => None,
+            Hint::SourceDir(dir) => Some(utils::normalize_str(dir)),
+        };
 
         // check if md files in our hashmap are matching the given link
         let matches_of_exact_name = self
@@ -73,6 +263,12 @@ impl ResourceIdResolver for ResourceIdLinkMap {
             if let Some(link_path) = &link_comp.path {
                 let link_path_norm = utils::normalize_str(link_path);
 
+                // a leading slash denotes a path relative to the vault root
+                let (link_path_norm, is_absolute) = match link_path_norm.strip_prefix('/') {
+                    Some(abs_path) => (abs_path.to_owned(), true),
+                    None => (link_path_norm, false),
+                };
+
                 // if it has one ... try to match it with the result list.
                 for (rid, plink_path) in match_list {
                     // Assumption: plink_path is already utf8 nfc encoded
@@ -80,15 +276,34 @@ impl ResourceIdResolver for ResourceIdLinkMap {
                         return Ok(rid);
                     }
                 }
+
+                // a relative path may be the trailing part of the directory
+                if !is_absolute {
+                    let suffix_matches = match_list
+                        .iter()
+                        .filter(|(_, plink_path)| is_dir_suffix(plink_path, &link_path_norm));
+                    if let Some((rid, _)) = select_nearest(suffix_matches, source_dir.as_deref()) {
+                        return Ok(rid);
+                    }
+                }
                 // no link found
-            } else {
+            } else if let Some((rid, _)) = select_nearest(match_list, source_dir.as_deref()) {
                 // not path was specified
-                if match_list.len() > 1 {
-                    warn!("The link {} is not unique.", &link_comp);
-                }
+                return Ok(rid);
+            }
+        }
 
-                let match_link = &match_list[0];
-                return Ok(&match_link.0);
+        // links without a path may use the alias of a note
+        if link_comp.path.is_none() {
+            if let Some(match_list) = self.alias_to_rid_list.get(link_name_lc.as_str()) {
+                trace!(
+                    "Name of link {} found in alias index. Resulting match_list: {:?}",
+                    &link_comp,
+                    &match_list
+                );
+                if let Some((rid, _)) = select_nearest(match_list, source_dir.as_deref()) {
+                    return Ok(rid);
+                }
             }
         }
 
@@ -98,10 +313,11 @@ impl ResourceIdResolver for ResourceIdLinkMap {
 }
 
 #[cfg(test)]
+#[allow(clippy::useless_conversion)]
 mod link_mapper_tests {
 
     use super::*;
-    use crate::types::ResourceId;
+    use crate::types::{AliasConflict, ResourceId};
     use std::iter::zip;
 
     fn create_dut(
@@ -115,7 +331,7 @@ mod link_mapper_tests {
             norm_filename: name.into_boxed_str(),
             dir_path: dir_path.into_boxed_str(),
         });
-        ResourceIdLinkMap::new(iter)
+        ResourceIdLinkMap::new(iter, vec![])
     }
 
     #[test]
@@ -274,4 +490,215 @@ mod link_mapper_tests {
         let result = dut.resolve(&"[[path2/nöte1.md]]".into()).unwrap();
         assert_eq!(result, &"[[path2/nöte1.md]]".into());
     }
+
+    fn create_dut_with_dirs(dirs: &[&str]) -> ResourceIdLinkMap {
+        let iter = dirs.iter().map(|dir| ResourceLoc {
+            rid: if dir.is_empty() {
+                "note1.md".into()
+            } else {
+                format!("{}/note1.md", dir).into()
+            },
+            norm_filename: "note1.md".into(),
+            dir_path: (*dir).into(),
+        });
+        ResourceIdLinkMap::new(iter, vec![])
+    }
+
+    #[test]
+    fn test_link_without_hint_resolves_to_candidate_closest_to_root() {
+        let dut = create_dut_with_dirs(&["b/c", "a", "b"]);
+        let result = dut.resolve(&"[[note1]]".into()).unwrap();
+        assert_eq!(result, &"a/note1.md".into());
+    }
+
+    #[test]
+    fn test_link_resolves_to_candidate_in_source_dir() {
+        let dut = create_dut_with_dirs(&["", "a", "b/c"]);
+        let result = dut
+            .resolve_with_hint(&"[[note1]]".into(), Hint::SourceDir("b/c"))
+            .unwrap();
+        assert_eq!(result, &"b/c/note1.md".into());
+    }
+
+    #[test]
+    fn test_link_resolves_to_nearest_candidate() {
+        let dut = create_dut_with_dirs(&["", "a/b/c", "a/d"]);
+        let result = dut
+            .resolve_with_hint(&"[[note1]]".into(), Hint::SourceDir("a/b"))
+            .unwrap();
+        assert_eq!(result, &"a/b/c/note1.md".into());
+
+        let result = dut
+            .resolve_with_hint(&"[[note1]]".into(), Hint::SourceDir("x/y"))
+            .unwrap();
+        assert_eq!(result, &"note1.md".into());
+    }
+
+    #[test]
+    fn test_partial_path_matches_suffix_of_dir() {
+        let dut = create_dut_with_dirs(&["a/sub", "b/other"]);
+        let result = dut.resolve(&"[[sub/note1]]".into()).unwrap();
+        assert_eq!(result, &"a/sub/note1.md".into());
+    }
+
+    #[test]
+    fn test_partial_path_matches_whole_dir_names_only() {
+        let dut = create_dut_with_dirs(&["a/mysub"]);
+        let result = dut.resolve(&"[[sub/note1]]".into()).unwrap_err();
+        assert!(matches!(result, LinkNotFound(_)));
+    }
+
+    #[test]
+    fn test_partial_path_prefers_exact_dir() {
+        let dut = create_dut_with_dirs(&["a/sub", "sub"]);
+        let result = dut
+            .resolve_with_hint(&"[[sub/note1]]".into(), Hint::SourceDir("a"))
+            .unwrap();
+        assert_eq!(result, &"sub/note1.md".into());
+    }
+
+    #[test]
+    fn test_ambiguous_partial_path_resolves_to_nearest_candidate() {
+        let dut = create_dut_with_dirs(&["a/sub", "b/sub"]);
+        let result = dut
+            .resolve_with_hint(&"[[sub/note1]]".into(), Hint::SourceDir("b"))
+            .unwrap();
+        assert_eq!(result, &"b/sub/note1.md".into());
+    }
+
+    #[test]
+    fn test_absolute_path_must_match_exactly() {
+        let dut = create_dut_with_dirs(&["a/sub"]);
+        let result = dut.resolve(&"[[/sub/note1]]".into()).unwrap_err();
+        assert!(matches!(result, LinkNotFound(_)));
+
+        let result = dut.resolve(&"[[/a/sub/note1]]".into()).unwrap();
+        assert_eq!(result, &"a/sub/note1.md".into());
+    }
+
+    #[test]
+    fn test_hint_from_source() {
+        let src: ResourceId = "a/b/note.md".into();
+        assert_eq!(Hint::from_source(&src), Hint::SourceDir("a/b"));
+
+        let src: ResourceId = "note.md".into();
+        assert_eq!(Hint::from_source(&src), Hint::SourceDir(""));
+    }
+
+    fn create_dut_with_aliases(aliases: Vec<(ResourceId, String)>) -> ResourceIdLinkMap {
+        let iter = ["a/note1.md", "b/note2.md", "c/note3.md"].map(|rid| {
+            let (dir, name) = rid.rsplit_once('/').unwrap();
+            ResourceLoc {
+                rid: rid.into(),
+                norm_filename: name.into(),
+                dir_path: dir.into(),
+            }
+        });
+        ResourceIdLinkMap::new(iter, aliases)
+    }
+
+    #[test]
+    fn test_link_match_alias() {
+        let dut = create_dut_with_aliases(vec![("a/note1.md".into(), "My Älias".into())]);
+        // Attention: The "Ä" from above is coded differently than the following Ä
+        let result = dut.resolve(&"[[my Älias|label]]".into()).unwrap();
+        assert_eq!(result, &"a/note1.md".into());
+    }
+
+    #[test]
+    fn test_link_with_path_doesnt_match_alias() {
+        let dut = create_dut_with_aliases(vec![("a/note1.md".into(), "alias".into())]);
+        let result = dut.resolve(&"[[a/alias]]".into()).unwrap_err();
+        assert!(matches!(result, LinkNotFound(_)));
+    }
+
+    #[test]
+    fn test_filename_takes_precedence_over_alias() {
+        let dut = create_dut_with_aliases(vec![("a/note1.md".into(), "Note2".into())]);
+        let result = dut.resolve(&"[[note2]]".into()).unwrap();
+        assert_eq!(result, &"b/note2.md".into());
+    }
+
+    #[test]
+    fn test_alias_shadowed_by_filename_is_reported() {
+        let dut = create_dut_with_aliases(vec![
+            ("a/note1.md".into(), "Note2".into()),
+            ("a/note1.md".into(), "note1".into()),
+            ("a/note1.md".into(), "unique".into()),
+        ]);
+        assert_eq!(
+            dut.alias_conflicts(),
+            [AliasConflict {
+                alias: "Note2".into(),
+                alias_of: "a/note1.md".into(),
+                shadowed_by: "b/note2.md".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_update_replaces_locations_and_aliases() {
+        let mut dut = create_dut_with_aliases(vec![("a/note1.md".into(), "alias".into())]);
+        dut.update(
+            [ResourceLoc {
+                rid: "d/note4.md".into(),
+                norm_filename: "note4.md".into(),
+                dir_path: "d".into(),
+            }],
+            vec![("d/note4.md".into(), "Note1".into())],
+            &["a/note1.md".into(), "d/note4.md".into()],
+        );
+
+        assert!(dut.resolve(&"[[alias]]".into()).is_err());
+        let result = dut.resolve(&"[[note1]]".into()).unwrap();
+        assert_eq!(result, &"d/note4.md".into());
+        let result = dut.resolve(&"[[note4]]".into()).unwrap();
+        assert_eq!(result, &"d/note4.md".into());
+    }
+
+    #[test]
+    fn test_update_reports_aliases_shadowed_by_new_filename() {
+        let mut dut = create_dut_with_aliases(vec![("a/note1.md".into(), "note4".into())]);
+        assert!(dut.alias_conflicts().is_empty());
+
+        dut.update(
+            [ResourceLoc {
+                rid: "d/note4.md".into(),
+                norm_filename: "note4.md".into(),
+                dir_path: "d".into(),
+            }],
+            vec![],
+            &["d/note4.md".into()],
+        );
+        assert_eq!(dut.alias_conflicts().len(), 1);
+        let result = dut.resolve(&"[[note4]]".into()).unwrap();
+        assert_eq!(result, &"d/note4.md".into());
+    }
+
+    #[test]
+    fn test_rids_with_name() {
+        let dut = create_dut_with_aliases(vec![("a/note1.md".into(), "note2".into())]);
+        let mut rids = dut.rids_with_name("note2.md");
+        rids.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(rids, [&"a/note1.md".into(), &"b/note2.md".into()]);
+        assert!(dut.rids_with_name("other").is_empty());
+    }
+
+    #[test]
+    fn test_norm_filename() {
+        assert_eq!(norm_filename(&"a/b/Nöte.md".into()), "nöte.md");
+        assert_eq!(norm_filename(&"Note.md".into()), "note.md");
+    }
+
+    #[test]
+    fn test_shared_alias_resolves_to_nearest_note() {
+        let dut = create_dut_with_aliases(vec![
+            ("a/note1.md".into(), "shared".into()),
+            ("c/note3.md".into(), "shared".into()),
+        ]);
+        let result = dut
+            .resolve_with_hint(&"[[shared]]".into(), Hint::SourceDir("c"))
+            .unwrap();
+        assert_eq!(result, &"c/note3.md".into());
+    }
 }
@@ -1,10 +1,26 @@
 use crate::error::Result;
 use crate::types;
 
-pub enum Hint {
-    #[allow(dead_code)]
+/// Additional information which helps to resolve ambiguous links.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint<'a> {
+    /// Nothing is known about the link. Ambiguous links resolve to the candidate
+    /// closest to the vault root.
     NoHint,
+
+    /// Directory of the note containing the link, relative to the vault root.
+    /// Ambiguous links resolve to the candidate nearest to this directory.
+    SourceDir(&'a str),
 }
+
+impl<'a> Hint<'a> {
+    /// Creates a hint from the resource id of the note containing the link.
+    pub fn from_source(src: &'a types::ResourceId) -> Self {
+        let dir = src.0.rsplit_once(['/', '\\']).map_or("", |(dir, _)| dir);
+        Hint::SourceDir(dir)
+    }
+}
+
 pub trait ResourceIdResolver {
     // This is a resolver instead of a Retriever because the link is interpreted
     fn resolve(&self, link: &types::Link) -> Result<&types::ResourceId> {
@@ -0,0 +1,213 @@
+use super::tag_retriever::TagRetriever;
+use crate::types;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Index of all tags of the vault.
+///
+/// Tags are compared case insensitive. Nested tags (`project/alpha`) are children
+/// of their parent tags (`project`).
+#[derive(Clone)]
+pub struct DefaultTagModel {
+    rid_to_tags: HashMap<types::ResourceId, Vec<String>>,
+
+    /// Maps the lowercase tag to its first seen spelling and the tagged resources.
+    tag_to_rids: BTreeMap<String, (String, Vec<types::ResourceId>)>,
+}
+
+impl DefaultTagModel {
+    pub fn new(it_src: impl IntoIterator<Item = (types::ResourceId, Vec<String>)>) -> Self {
+        let mut model = Self {
+            rid_to_tags: HashMap::new(),
+            tag_to_rids: BTreeMap::new(),
+        };
+        for (rid, tags) in it_src.into_iter() {
+            if model.rid_to_tags.contains_key(&rid) {
+                panic!("This should not happen. No duplicate entries allowed.")
+            }
+            model.insert(rid, tags);
+        }
+        model
+    }
+
+    /// Replaces the tags of the given notes and removes the tags of the deleted
+    /// ones.
+    pub fn update(
+        &mut self,
+        it_src: impl IntoIterator<Item = (types::ResourceId, Vec<String>)>,
+        removed: &[types::ResourceId],
+    ) {
+        let it_src: Vec<_> = it_src.into_iter().collect();
+        for rid in removed.iter().chain(it_src.iter().map(|f| &f.0)) {
+            self.remove(rid);
+        }
+        for (rid, tags) in it_src {
+            self.insert(rid, tags);
+        }
+    }
+
+    fn insert(&mut self, rid: types::ResourceId, tags: Vec<String>) {
+        let mut seen = HashSet::<String>::new();
+        let mut unique_tags = Vec::<String>::new();
+        for tag in tags {
+            let key = tag.to_lowercase();
+            if !seen.insert(key.clone()) {
+                continue;
+            }
+            self.tag_to_rids
+                .entry(key)
+                .or_insert_with(|| (tag.clone(), vec![]))
+                .1
+                .push(rid.clone());
+            unique_tags.push(tag);
+        }
+        self.rid_to_tags.insert(rid, unique_tags);
+    }
+
+    fn remove(&mut self, rid: &types::ResourceId) {
+        let Some(tags) = self.rid_to_tags.remove(rid) else {
+            return;
+        };
+        for tag in tags {
+            let key = tag.to_lowercase();
+            if let Some((_, rids)) = self.tag_to_rids.get_mut(&key) {
+                rids.retain(|f| f != rid);
+                if rids.is_empty() {
+                    self.tag_to_rids.remove(&key);
+                }
+            }
+        }
+    }
+
+    pub fn get_rids_with_tag(&self, tag: &str) -> Vec<types::ResourceId> {
+        let key = tag.trim_start_matches('#').to_lowercase();
+        let child_prefix = format!("{}/", key);
+
+        let mut seen = HashSet::<&types::ResourceId>::new();
+        self.tag_to_rids
+            .iter()
+            .filter(|(k, _)| **k == key || k.starts_with(&child_prefix))
+            .flat_map(|(_, (_, rids))| rids.iter())
+            .filter(|rid| seen.insert(rid))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns all tags including their implicit parent tags together with the
+    /// number of resources tagged with the tag or one of its child tags.
+    ///
+    /// Parent tags are ordered before their children.
+    pub fn get_tag_counts(&self) -> Vec<(String, usize)> {
+        let mut tree = BTreeMap::<Vec<String>, (String, HashSet<&types::ResourceId>)>::new();
+
+        for (key, (tag, rids)) in self.tag_to_rids.iter() {
+            let key_segments: Vec<_> = key.split('/').collect();
+            let tag_segments: Vec<_> = tag.split('/').collect();
+
+            for depth in 1..=key_segments.len() {
+                let path = key_segments[..depth]
+                    .iter()
+                    .map(|f| f.to_string())
+                    .collect();
+                tree.entry(path)
+                    .or_insert_with(|| (tag_segments[..depth].join("/"), HashSet::new()))
+                    .1
+                    .extend(rids.iter());
+            }
+        }
+
+        tree.into_values()
+            .map(|(tag, rids)| (tag, rids.len()))
+            .collect()
+    }
+}
+
+impl TagRetriever for DefaultTagModel {
+    fn retrieve(&self, rid: &types::ResourceId) -> &[String] {
+        self.rid_to_tags.get(rid).map_or(&[], |f| f.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DefaultTagModel;
+    use super::TagRetriever;
+    use crate::types::ResourceId;
+
+    fn create_dut() -> DefaultTagModel {
+        DefaultTagModel::new(vec![
+            ("a.md".into(), vec!["project/alpha".into(), "todo".into()]),
+            ("b.md".into(), vec!["Project".into(), "project".into()]),
+            (
+                "c.md".into(),
+                vec!["project/beta/x".into(), "project-2".into()],
+            ),
+            ("d.md".into(), vec![]),
+        ])
+    }
+
+    #[test]
+    fn test_retrieve_removes_duplicates() {
+        let dut = create_dut();
+        let rid: ResourceId = "b.md".into();
+        assert_eq!(dut.retrieve(&rid), ["Project"]);
+    }
+
+    #[test]
+    fn test_get_rids_with_tag_includes_child_tags() {
+        let dut = create_dut();
+        let rids = dut.get_rids_with_tag("#project");
+        assert_eq!(
+            rids,
+            ["b.md".into(), "a.md".into(), "c.md".into()] as [ResourceId; 3]
+        );
+    }
+
+    #[test]
+    fn test_get_rids_with_child_tag() {
+        let dut = create_dut();
+        let rids = dut.get_rids_with_tag("Project/Alpha");
+        assert_eq!(rids, ["a.md".into()] as [ResourceId; 1]);
+    }
+
+    #[test]
+    fn test_get_rids_with_unknown_tag() {
+        let dut = create_dut();
+        assert!(dut.get_rids_with_tag("proj").is_empty());
+    }
+
+    #[test]
+    fn test_get_tag_counts() {
+        let dut = create_dut();
+        let counts = dut.get_tag_counts();
+
+        assert_eq!(
+            counts,
+            [
+                ("Project".to_owned(), 3),
+                ("project/alpha".to_owned(), 1),
+                ("project/beta".to_owned(), 1),
+                ("project/beta/x".to_owned(), 1),
+                ("project-2".to_owned(), 1),
+                ("todo".to_owned(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_update_replaces_and_removes_tags() {
+        let mut dut = create_dut();
+        dut.update(vec![("a.md".into(), vec!["done".into()])], &["c.md".into()]);
+
+        let rid: ResourceId = "a.md".into();
+        assert_eq!(dut.retrieve(&rid), ["done"]);
+        assert_eq!(
+            dut.get_rids_with_tag("done"),
+            ["a.md".into()] as [ResourceId; 1]
+        );
+        assert!(dut.get_rids_with_tag("todo").is_empty());
+        assert_eq!(
+            dut.get_rids_with_tag("project"),
+            ["b.md".into()] as [ResourceId; 1]
+        );
+    }
+}
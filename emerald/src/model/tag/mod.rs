@@ -0,0 +1,5 @@
+mod default_tag_model;
+mod tag_retriever;
+
+pub use default_tag_model::DefaultTagModel;
+pub use tag_retriever::TagRetriever;
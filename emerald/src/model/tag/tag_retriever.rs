@@ -0,0 +1,8 @@
+use crate::types;
+
+/// This trait is used to query a resource id for all its tags.
+pub trait TagRetriever {
+    /// Returns the tags of the resource without their leading `#`. Every tag is
+    /// returned only once.
+    fn retrieve(&self, rid: &types::ResourceId) -> &[String];
+}
@@ -0,0 +1,39 @@
+use super::task_retriever::TaskRetriever;
+use crate::types;
+use std::collections::HashMap;
+
+#[derive(Clone)]
+pub struct DefaultTaskModel {
+    rid_to_tasks: HashMap<types::ResourceId, Vec<types::Task>>,
+}
+
+impl DefaultTaskModel {
+    pub fn new(it_src: impl IntoIterator<Item = (types::ResourceId, Vec<types::Task>)>) -> Self {
+        let mut rid_to_tasks = HashMap::<types::ResourceId, Vec<types::Task>>::new();
+        for (rid, tasks) in it_src.into_iter() {
+            if rid_to_tasks.insert(rid, tasks).is_some() {
+                panic!("This should not happen. No duplicate entries allowed.")
+            }
+        }
+        Self { rid_to_tasks }
+    }
+
+    /// Replaces the tasks of the given notes and removes the tasks of the deleted
+    /// ones.
+    pub fn update(
+        &mut self,
+        it_src: impl IntoIterator<Item = (types::ResourceId, Vec<types::Task>)>,
+        removed: &[types::ResourceId],
+    ) {
+        for rid in removed {
+            self.rid_to_tasks.remove(rid);
+        }
+        self.rid_to_tasks.extend(it_src);
+    }
+}
+
+impl TaskRetriever for DefaultTaskModel {
+    fn retrieve(&self, rid: &types::ResourceId) -> &[types::Task] {
+        self.rid_to_tasks.get(rid).map_or(&[], |f| f.as_slice())
+    }
+}
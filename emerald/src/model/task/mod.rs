@@ -0,0 +1,5 @@
+mod default_task_model;
+mod task_retriever;
+
+pub use default_task_model::DefaultTaskModel;
+pub use task_retriever::TaskRetriever;
@@ -0,0 +1,7 @@
+use crate::types;
+
+/// This trait is used to query a resource id for all contained tasks.
+pub trait TaskRetriever {
+    /// Returns the tasks of the resource in document order.
+    fn retrieve(&self, rid: &types::ResourceId) -> &[types::Task];
+}
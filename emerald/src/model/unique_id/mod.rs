@@ -2,45 +2,204 @@ pub mod resource_id_trait;
 pub mod uid;
 pub mod uid_map;
 pub mod uid_metadata_retriever;
+pub mod uid_minter;
 pub mod uid_retriever;
+pub mod uid_strategy;
 
-use std::rc::Rc;
+use crate::error::{EmeraldError::*, Result};
+use std::collections::HashSet;
 
 pub use uid::Uid;
 pub use uid_map::UidMap;
 pub use uid_metadata_retriever::UidMetadataRetriever;
+pub use uid_minter::UidMinter;
 pub use uid_retriever::UidRetriever;
+pub use uid_strategy::UidStrategy;
 
 #[derive(Clone)]
 pub struct UniqueId<T>
 where
     T: resource_id_trait::ResourceIdTrait,
 {
-    uid_metadata_retriever: Rc<dyn UidMetadataRetriever<T>>,
-    uid_map: Rc<UidMap<T>>,
+    uid_map: UidMap<T>,
+    uid_strategy: UidStrategy,
 }
 
 impl<T> UniqueId<T>
 where
     T: resource_id_trait::ResourceIdTrait,
 {
+    /// Assigns a uid to every note.
+    ///
+    /// Uids stored in the frontmatter of a note take precedence. All other notes
+    /// get a uid minted according to the given strategy.
+    ///
+    /// # Returns
+    ///
+    /// An error if the same uid is stored in the frontmatter of several notes.
     pub fn new(
         note_rid_iter: impl IntoIterator<Item = T>,
-        uid_metadata_retriever: Rc<dyn UidMetadataRetriever<T>>,
-    ) -> Self {
+        uid_metadata_retriever: &dyn UidMetadataRetriever<T>,
+        uid_strategy: UidStrategy,
+    ) -> Result<Self> {
         let mut uid_map = UidMap::<T>::new();
+        let mut rids_without_uid = Vec::<T>::new();
 
         for rid in note_rid_iter.into_iter() {
-            uid_map.assign_uid(&rid);
+            match uid_metadata_retriever.retrieve(&rid) {
+                Some(uid) => uid_map.assign_uid(&rid, Uid(uid.into_boxed_str()))?,
+                None => rids_without_uid.push(rid),
+            }
         }
 
-        Self {
-            uid_map: Rc::new(uid_map),
-            uid_metadata_retriever,
+        sort_by_creation(&mut rids_without_uid, uid_metadata_retriever);
+        let mut uid_minter = UidMinter::new(uid_strategy);
+        for rid in rids_without_uid.into_iter() {
+            let uid = uid_minter.mint(
+                rid.as_ref(),
+                uid_metadata_retriever.retrieve_created(&rid),
+                uid_metadata_retriever.retrieve_content(&rid),
+                |f| uid_map.contains_uid(f),
+            );
+            uid_map.assign_uid(&rid, uid)?;
+        }
+
+        Ok(Self {
+            uid_map,
+            uid_strategy,
+        })
+    }
+
+    /// Checks that the uids stored in the frontmatter of the changed notes can be
+    /// assigned by [`UniqueId::update`].
+    ///
+    /// # Arguments
+    ///
+    /// * `changed`: The changed notes together with their new frontmatter uid.
+    /// * `removed`: The removed notes.
+    /// * `uid_metadata_retriever`: Metadata of the notes before the change.
+    ///
+    /// # Returns
+    ///
+    /// An error if several changed notes store the same uid, or if a changed note
+    /// stores the frontmatter uid of another note.
+    pub fn check_update(
+        &self,
+        changed: &[(T, Option<String>)],
+        removed: &[T],
+        uid_metadata_retriever: &dyn UidMetadataRetriever<T>,
+    ) -> Result<()> {
+        let replaced: HashSet<_> = changed.iter().map(|f| &f.0).chain(removed).collect();
+
+        let mut claims = UidMap::<T>::new();
+        for (rid, uid) in changed {
+            let Some(uid) = uid else {
+                continue;
+            };
+            claims.assign_uid(rid, Uid(uid.as_str().into()))?;
+
+            let Some(owner) = self.uid_map.get_rid_from_uid(&Uid(uid.as_str().into())) else {
+                continue;
+            };
+            if !replaced.contains(owner)
+                && uid_metadata_retriever.retrieve(owner).as_ref() == Some(uid)
+            {
+                return Err(DuplicateUid(
+                    uid.clone(),
+                    format!("{:?}", owner),
+                    format!("{:?}", rid),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Updates the uids after notes were changed or removed. The change must have
+    /// passed [`UniqueId::check_update`].
+    ///
+    /// Uids stored in the frontmatter of a changed note take precedence. Other
+    /// changed notes keep their uid, unless it is claimed by a frontmatter uid.
+    /// Notes without a uid get a minted one.
+    ///
+    /// # Arguments
+    ///
+    /// * `changed`: The changed notes.
+    /// * `removed`: The removed notes.
+    /// * `uid_metadata_retriever`: Metadata of the notes after the change.
+    pub fn update(
+        &mut self,
+        changed: &[T],
+        removed: &[T],
+        uid_metadata_retriever: &dyn UidMetadataRetriever<T>,
+    ) {
+        for rid in removed {
+            self.uid_map.unassign_uid(rid);
+        }
+        let old_uids: Vec<_> = changed
+            .iter()
+            .map(|rid| self.uid_map.unassign_uid(rid))
+            .collect();
+
+        let mut rids_without_uid = Vec::<T>::new();
+        for rid in changed.iter() {
+            let Some(uid) = uid_metadata_retriever.retrieve(rid) else {
+                continue;
+            };
+            let uid = Uid(uid.into_boxed_str());
+            // the minted uid of an unchanged note has to give way
+            if let Some(owner) = self.uid_map.get_rid_from_uid(&uid).cloned() {
+                self.uid_map.unassign_uid(&owner);
+                rids_without_uid.push(owner);
+            }
+            self.uid_map
+                .assign_uid(rid, uid)
+                .expect("Frontmatter uids were checked before");
+        }
+
+        for (rid, old_uid) in changed.iter().zip(old_uids) {
+            if uid_metadata_retriever.retrieve(rid).is_some() {
+                continue;
+            }
+            match old_uid.filter(|f| !self.uid_map.contains_uid(f)) {
+                Some(uid) => self
+                    .uid_map
+                    .assign_uid(rid, uid)
+                    .expect("Uid is not assigned"),
+                None => rids_without_uid.push(rid.clone()),
+            }
+        }
+
+        sort_by_creation(&mut rids_without_uid, uid_metadata_retriever);
+        let mut uid_minter = UidMinter::new(self.uid_strategy);
+        for rid in rids_without_uid.into_iter() {
+            let uid = uid_minter.mint(
+                rid.as_ref(),
+                uid_metadata_retriever.retrieve_created(&rid),
+                uid_metadata_retriever.retrieve_content(&rid),
+                |f| self.uid_map.contains_uid(f),
+            );
+            self.uid_map
+                .assign_uid(&rid, uid)
+                .expect("Minted uids are unique");
         }
     }
 }
 
+/// Sorts the notes by their creation time and their resource id. Uids are minted
+/// in this order, so that a colliding uid is given to the note created first and
+/// not to the note which happens to be loaded first.
+fn sort_by_creation<T>(rids: &mut [T], uid_metadata_retriever: &dyn UidMetadataRetriever<T>)
+where
+    T: resource_id_trait::ResourceIdTrait,
+{
+    rids.sort_by_cached_key(|rid| {
+        (
+            uid_metadata_retriever.retrieve_created(rid),
+            rid.as_ref().to_owned(),
+        )
+    });
+}
+
 impl<T> UidRetriever<T> for UniqueId<T>
 where
     T: resource_id_trait::ResourceIdTrait,
@@ -53,3 +212,177 @@ where
         self.uid_map.get_rid_from_uid(uid)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{UidMetadataRetriever, UidRetriever, UidStrategy, UniqueId};
+    use crate::error::EmeraldError;
+    use crate::model::unique_id::Uid;
+
+    struct MetadataStub(Vec<(&'static str, Option<&'static str>)>);
+
+    impl UidMetadataRetriever<String> for MetadataStub {
+        fn retrieve(&self, rid: &String) -> Option<String> {
+            let (_, uid) = self.0.iter().find(|f| f.0 == rid).unwrap();
+            uid.map(|f| f.to_owned())
+        }
+
+        fn retrieve_created(&self, _rid: &String) -> i64 {
+            0
+        }
+
+        fn retrieve_content(&self, rid: &String) -> &str {
+            self.0.iter().find(|f| f.0 == rid).unwrap().0
+        }
+    }
+
+    fn create_dut(
+        notes: Vec<(&'static str, Option<&'static str>)>,
+        uid_strategy: UidStrategy,
+    ) -> crate::error::Result<UniqueId<String>> {
+        let rids: Vec<String> = notes.iter().map(|f| f.0.to_owned()).collect();
+        UniqueId::new(rids, &MetadataStub(notes), uid_strategy)
+    }
+
+    fn uid_of(dut: &UniqueId<String>, rid: &str) -> String {
+        dut.get_uid_from_rid(&rid.to_owned()).unwrap().0.to_string()
+    }
+
+    #[test]
+    fn test_frontmatter_uid_is_preferred() {
+        let dut = create_dut(
+            vec![("a.md", None), ("b.md", Some("zk-1"))],
+            UidStrategy::Sequential,
+        )
+        .unwrap();
+
+        assert_eq!(uid_of(&dut, "a.md"), "0");
+        assert_eq!(uid_of(&dut, "b.md"), "zk-1");
+        assert_eq!(
+            dut.get_rid_from_uid(&Uid("zk-1".into())),
+            Some(&"b.md".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_minted_uids_dont_collide_with_frontmatter_uids() {
+        let dut = create_dut(
+            vec![("a.md", None), ("b.md", Some("0"))],
+            UidStrategy::Sequential,
+        )
+        .unwrap();
+
+        assert_eq!(uid_of(&dut, "a.md"), "1");
+        assert_eq!(uid_of(&dut, "b.md"), "0");
+    }
+
+    #[test]
+    fn test_minted_uids_dont_depend_on_other_notes() {
+        let dut1 = create_dut(vec![("a.md", None)], UidStrategy::ContentHash).unwrap();
+        let dut2 = create_dut(
+            vec![("0.md", None), ("a.md", None)],
+            UidStrategy::ContentHash,
+        )
+        .unwrap();
+
+        assert_eq!(uid_of(&dut1, "a.md"), uid_of(&dut2, "a.md"));
+    }
+
+    struct CreatedStub(Vec<(&'static str, i64)>);
+
+    impl UidMetadataRetriever<String> for CreatedStub {
+        fn retrieve(&self, _rid: &String) -> Option<String> {
+            None
+        }
+
+        fn retrieve_created(&self, rid: &String) -> i64 {
+            self.0.iter().find(|f| f.0 == rid).unwrap().1
+        }
+
+        fn retrieve_content(&self, rid: &String) -> &str {
+            self.0.iter().find(|f| f.0 == rid).unwrap().0
+        }
+    }
+
+    #[test]
+    fn test_zettel_uids_dont_depend_on_load_order() {
+        let create_dut = |notes: Vec<(&'static str, i64)>| {
+            let rids: Vec<String> = notes.iter().map(|f| f.0.to_owned()).collect();
+            UniqueId::new(rids, &CreatedStub(notes), UidStrategy::TimestampZettel).unwrap()
+        };
+        let dut1 = create_dut(vec![("b.md", 1697643000)]);
+        // a.md is loaded first, but created later in the same minute
+        let dut2 = create_dut(vec![("a.md", 1697643059), ("b.md", 1697643000)]);
+
+        assert_eq!(uid_of(&dut1, "b.md"), "202310181530");
+        assert_eq!(uid_of(&dut2, "b.md"), "202310181530");
+        assert!(uid_of(&dut2, "a.md").starts_with("202310181530-"));
+    }
+
+    #[test]
+    fn test_duplicate_frontmatter_uids_are_reported() {
+        let res = create_dut(
+            vec![("a.md", Some("zk-1")), ("b.md", Some("zk-1"))],
+            UidStrategy::Sequential,
+        );
+
+        assert!(matches!(
+            res,
+            Err(EmeraldError::DuplicateUid(uid, _, _)) if uid == "zk-1"
+        ));
+    }
+
+    #[test]
+    fn test_update_keeps_uids_of_changed_notes() {
+        let notes = vec![("a.md", None), ("b.md", None)];
+        let mut dut = create_dut(notes.clone(), UidStrategy::Sequential).unwrap();
+        let notes = vec![("a.md", None), ("b.md", None), ("c.md", None)];
+        let changed = ["b.md".to_owned(), "c.md".to_owned()];
+
+        let claims: Vec<_> = changed.iter().map(|f| (f.clone(), None)).collect();
+        dut.check_update(&claims, &[], &MetadataStub(notes.clone()))
+            .unwrap();
+        dut.update(&changed, &["a.md".to_owned()], &MetadataStub(notes));
+
+        assert_eq!(dut.get_uid_from_rid(&"a.md".to_owned()), None);
+        assert_eq!(uid_of(&dut, "b.md"), "1");
+        assert_eq!(uid_of(&dut, "c.md"), "0");
+    }
+
+    #[test]
+    fn test_update_moves_minted_uid_claimed_by_frontmatter() {
+        let notes = vec![("a.md", None), ("b.md", None)];
+        let mut dut = create_dut(notes, UidStrategy::Sequential).unwrap();
+        let notes = vec![("a.md", None), ("b.md", Some("0"))];
+        let changed = ["b.md".to_owned()];
+
+        let claims = [("b.md".to_owned(), Some("0".to_owned()))];
+        dut.check_update(&claims, &[], &MetadataStub(notes.clone()))
+            .unwrap();
+        dut.update(&changed, &[], &MetadataStub(notes));
+
+        assert_eq!(uid_of(&dut, "a.md"), "1");
+        assert_eq!(uid_of(&dut, "b.md"), "0");
+    }
+
+    #[test]
+    fn test_check_update_reports_duplicate_frontmatter_uids() {
+        let notes = vec![("a.md", Some("zk-1")), ("b.md", None), ("c.md", None)];
+        let dut = create_dut(notes.clone(), UidStrategy::Sequential).unwrap();
+
+        let claims = [("b.md".to_owned(), Some("zk-1".to_owned()))];
+        let res = dut.check_update(&claims, &[], &MetadataStub(notes.clone()));
+        assert!(matches!(res, Err(EmeraldError::DuplicateUid(uid, _, _)) if uid == "zk-1"));
+
+        // the uid is released by removing the other note
+        let res = dut.check_update(&claims, &["a.md".to_owned()], &MetadataStub(notes.clone()));
+        assert!(res.is_ok());
+
+        let claims = [
+            ("b.md".to_owned(), Some("zk-2".to_owned())),
+            ("c.md".to_owned(), Some("zk-2".to_owned())),
+        ];
+        let res = dut.check_update(&claims, &[], &MetadataStub(notes));
+        assert!(matches!(res, Err(EmeraldError::DuplicateUid(uid, _, _)) if uid == "zk-2"));
+    }
+}
@@ -1,6 +1,7 @@
 use super::resource_id_trait::ResourceIdTrait;
 use super::uid::Uid;
 use super::uid_retriever::UidRetriever;
+use crate::error::{EmeraldError::*, Result};
 use std::collections::HashMap;
 
 /// Manages mappings between UIDs (unique identifiers) and resource IDs.
@@ -11,7 +12,6 @@ where
 {
     uid_to_rid: HashMap<Uid, T>,
     rid_to_uid: HashMap<T, Uid>,
-    next_uid: u32,
 }
 
 impl<T> UidMap<T>
@@ -23,27 +23,48 @@ where
         Self {
             uid_to_rid: HashMap::new(),
             rid_to_uid: HashMap::new(),
-            next_uid: 0,
         }
     }
 
-    /// Assigns a new UID to the given resource ID.
+    /// Assigns a UID to the given resource ID.
     ///
     /// # Arguments
     ///
     /// * `rid`: The resource ID to assign a UID.
+    /// * `uid`: The UID to assign.
     ///
     /// # Returns
     ///
-    /// The new UID assigned to the resource ID.
-    pub fn assign_uid(&mut self, rid: &T) -> Uid {
-        let act_uid = self.next_uid;
-        let uid = Uid(act_uid.to_string().into_boxed_str());
+    /// An error if the UID is already assigned to another resource ID.
+    pub fn assign_uid(&mut self, rid: &T, uid: Uid) -> Result<()> {
+        if let Some(other_rid) = self.uid_to_rid.get(&uid) {
+            return Err(DuplicateUid(
+                uid.0.to_string(),
+                format!("{:?}", other_rid),
+                format!("{:?}", rid),
+            ));
+        }
         self.rid_to_uid.insert(rid.clone(), uid.clone());
-        self.uid_to_rid.insert(uid.clone(), rid.clone());
-        self.next_uid += 1;
-        uid
+        self.uid_to_rid.insert(uid, rid.clone());
+        Ok(())
+    }
+
+    /// Removes the UID of the given resource ID.
+    ///
+    /// # Returns
+    ///
+    /// The UID which was assigned to the resource ID.
+    pub fn unassign_uid(&mut self, rid: &T) -> Option<Uid> {
+        let uid = self.rid_to_uid.remove(rid)?;
+        self.uid_to_rid.remove(&uid);
+        Some(uid)
+    }
+
+    /// Returns true if the UID is assigned to a resource ID.
+    pub fn contains_uid(&self, uid: &Uid) -> bool {
+        self.uid_to_rid.contains_key(uid)
     }
+
     pub fn get_rid_from_uid(&self, uid: &Uid) -> Option<&T> {
         self.uid_to_rid.get(uid)
     }
@@ -65,3 +86,46 @@ where
         self.rid_to_uid.get(rid)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::UidMap;
+    use crate::error::EmeraldError;
+    use crate::model::unique_id::Uid;
+
+    #[test]
+    fn test_assign_uid() {
+        let mut dut = UidMap::<String>::new();
+        dut.assign_uid(&"a.md".to_string(), Uid("1".into()))
+            .unwrap();
+
+        assert!(dut.contains_uid(&Uid("1".into())));
+        assert_eq!(
+            dut.get_rid_from_uid(&Uid("1".into())),
+            Some(&"a.md".to_string())
+        );
+    }
+
+    #[test]
+    fn test_unassigned_uid_can_be_assigned_again() {
+        let mut dut = UidMap::<String>::new();
+        dut.assign_uid(&"a.md".to_string(), Uid("1".into()))
+            .unwrap();
+
+        assert_eq!(dut.unassign_uid(&"a.md".to_string()), Some(Uid("1".into())));
+        assert!(!dut.contains_uid(&Uid("1".into())));
+        assert_eq!(dut.get_uid_from_rid(&"a.md".to_string()), None);
+        dut.assign_uid(&"b.md".to_string(), Uid("1".into()))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_assign_uid_twice_fails() {
+        let mut dut = UidMap::<String>::new();
+        dut.assign_uid(&"a.md".to_string(), Uid("1".into()))
+            .unwrap();
+        let res = dut.assign_uid(&"b.md".to_string(), Uid("1".into()));
+
+        assert!(matches!(res, Err(EmeraldError::DuplicateUid(uid, _, _)) if uid == "1"));
+    }
+}
@@ -1,7 +1,7 @@
 use super::resource_id_trait::ResourceIdTrait;
 
 /// Trait for retrieving metadata associated with a note.
-pub trait UidMetadataRetriever<T>
+pub trait UidMetadataRetriever<T>: Send + Sync
 where
     T: ResourceIdTrait,
 {
@@ -15,4 +15,14 @@ where
     ///
     /// UID stored in meta data.
     fn retrieve(&self, rid: &T) -> Option<String>;
+
+    /// Retrieves the creation time of a given note.
+    ///
+    /// # Returns
+    ///
+    /// Seconds since the unix epoch.
+    fn retrieve_created(&self, rid: &T) -> i64;
+
+    /// Retrieves the content of a given note.
+    fn retrieve_content(&self, rid: &T) -> &str;
 }
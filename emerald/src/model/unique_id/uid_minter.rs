@@ -0,0 +1,234 @@
+use super::uid::Uid;
+use super::uid_strategy::UidStrategy;
+use sha2::{Digest, Sha256};
+
+const SECONDS_PER_DAY: i64 = 86400;
+
+/// Creates new uids according to a `UidStrategy`.
+pub struct UidMinter {
+    strategy: UidStrategy,
+    next_sequence: u64,
+}
+
+impl UidMinter {
+    pub fn new(strategy: UidStrategy) -> Self {
+        Self {
+            strategy,
+            next_sequence: 0,
+        }
+    }
+
+    /// Mints a uid which isn't taken yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `rid`: Resource id of the note.
+    /// * `created`: Creation time of the note in seconds since the unix epoch.
+    /// * `content`: Content of the note.
+    /// * `is_taken`: Returns true if a uid is already in use.
+    pub fn mint(
+        &mut self,
+        rid: &str,
+        created: i64,
+        content: &str,
+        is_taken: impl Fn(&Uid) -> bool,
+    ) -> Uid {
+        let mut attempt = 0;
+        loop {
+            let uid = Uid(self
+                .candidate(rid, created, content, attempt)
+                .into_boxed_str());
+            if !is_taken(&uid) {
+                return uid;
+            }
+            attempt += 1;
+        }
+    }
+
+    fn candidate(&mut self, rid: &str, created: i64, content: &str, attempt: u32) -> String {
+        match self.strategy {
+            UidStrategy::Sequential => {
+                let sequence = self.next_sequence;
+                self.next_sequence += 1;
+                sequence.to_string()
+            }
+            UidStrategy::Ulid => {
+                // the random part is derived from the rid, so that a note gets
+                // the same uid on every load
+                let random = digest(rid, attempt)[..16]
+                    .iter()
+                    .fold(0u128, |acc, f| acc << 8 | u128::from(*f));
+                ulid::Ulid::from_parts(created.max(0) as u64 * 1000, random).to_string()
+            }
+            UidStrategy::TimestampZettel => match attempt {
+                0 => format_zettel(created),
+                // colliding notes get a suffix derived from the rid, so that
+                // the uid doesn't depend on the other notes of the minute
+                _ => format!(
+                    "{}-{}",
+                    format_zettel(created),
+                    to_hex(&digest(rid, attempt - 1)[..2])
+                ),
+            },
+            UidStrategy::ContentHash => hash_content(content, attempt),
+        }
+    }
+}
+
+/// Formats seconds since the unix epoch as `YYYYMMDDhhmm` (UTC).
+fn format_zettel(timestamp: i64) -> String {
+    let days = timestamp.div_euclid(SECONDS_PER_DAY);
+    let secs_of_day = timestamp.rem_euclid(SECONDS_PER_DAY);
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{:04}{:02}{:02}{:02}{:02}",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60
+    )
+}
+
+/// Converts days since the unix epoch to a (year, month, day) date of the
+/// proleptic gregorian calendar.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Returns the sha256 of the data. Equal data is told apart by the attempt.
+fn digest(data: &str, attempt: u32) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(data.as_bytes());
+    if attempt > 0 {
+        hasher.update(attempt.to_le_bytes());
+    }
+    hasher.finalize().into()
+}
+
+/// Returns the first 12 hex digits of the sha256 of the content. Notes with
+/// equal content are told apart by the attempt.
+fn hash_content(content: &str, attempt: u32) -> String {
+    to_hex(&digest(content, attempt)[..6])
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|f| format!("{:02x}", f)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_zettel, UidMinter};
+    use crate::model::unique_id::{Uid, UidStrategy};
+
+    fn mint_all(strategy: UidStrategy, notes: &[(i64, &str)]) -> Vec<String> {
+        let mut dut = UidMinter::new(strategy);
+        let mut uids: Vec<Uid> = vec![];
+        for (idx, (created, content)) in notes.iter().enumerate() {
+            let rid = format!("note_{}.md", idx);
+            let uid = dut.mint(&rid, *created, content, |f| uids.contains(f));
+            uids.push(uid);
+        }
+        uids.into_iter().map(|f| f.0.into()).collect()
+    }
+
+    #[test]
+    fn test_format_zettel() {
+        // 2023-10-18 15:30:59 UTC
+        assert_eq!(format_zettel(1697643059), "202310181530");
+    }
+
+    #[test]
+    fn test_format_zettel_at_epoch() {
+        assert_eq!(format_zettel(0), "197001010000");
+    }
+
+    #[test]
+    fn test_format_zettel_in_leap_year() {
+        // 2024-02-29 23:59:00 UTC
+        assert_eq!(format_zettel(1709251140), "202402292359");
+    }
+
+    #[test]
+    fn test_mint_sequential() {
+        let uids = mint_all(UidStrategy::Sequential, &[(0, "a"), (0, "b")]);
+        assert_eq!(uids, ["0", "1"]);
+    }
+
+    #[test]
+    fn test_mint_sequential_skips_taken_uids() {
+        let mut dut = UidMinter::new(UidStrategy::Sequential);
+        let uid = dut.mint("a.md", 0, "", |f| f.0.as_ref() == "0");
+        assert_eq!(uid.0.as_ref(), "1");
+    }
+
+    #[test]
+    fn test_mint_zettel_suffixes_collisions_by_rid() {
+        let uids = mint_all(
+            UidStrategy::TimestampZettel,
+            &[(1697643059, "a"), (1697643000, "b"), (1697643010, "c")],
+        );
+        assert_eq!(uids[0], "202310181530");
+        assert!(uids[1].starts_with("202310181530-") && uids[1].len() == 17);
+        assert!(uids[2].starts_with("202310181530-") && uids[2].len() == 17);
+        assert_ne!(uids[1], uids[2]);
+
+        // the suffix only depends on the note itself
+        let mut dut = UidMinter::new(UidStrategy::TimestampZettel);
+        let taken = |f: &Uid| f.0.as_ref() == "202310181530" || f.0 == uids[2].as_str().into();
+        let uid = dut.mint("note_1.md", 1697643000, "b", taken);
+        assert_eq!(uid.0.as_ref(), uids[1]);
+    }
+
+    #[test]
+    fn test_mint_content_hash_is_stable() {
+        let first = mint_all(UidStrategy::ContentHash, &[(0, "content")]);
+        let second = mint_all(UidStrategy::ContentHash, &[(10, "content")]);
+        assert_eq!(first, second);
+        assert_eq!(first[0].len(), 12);
+    }
+
+    #[test]
+    fn test_mint_content_hash_of_equal_contents_differs() {
+        let uids = mint_all(UidStrategy::ContentHash, &[(0, "same"), (0, "same")]);
+        assert_ne!(uids[0], uids[1]);
+    }
+
+    #[test]
+    fn test_mint_ulid_encodes_creation_time() {
+        let uids = mint_all(UidStrategy::Ulid, &[(1697643059, "a"), (1697643059, "a")]);
+        assert_eq!(uids[0].len(), 26);
+        assert_ne!(uids[0], uids[1]);
+
+        // the first 10 characters encode the milliseconds since the epoch
+        let created: ulid::Ulid = uids[0].parse().unwrap();
+        assert_eq!(created.timestamp_ms(), 1697643059000);
+    }
+
+    #[test]
+    fn test_mint_ulid_is_stable() {
+        let notes = [(1697643059, "a"), (1697643059, "b")];
+        assert_eq!(
+            mint_all(UidStrategy::Ulid, &notes),
+            mint_all(UidStrategy::Ulid, &notes)
+        );
+    }
+
+    #[test]
+    fn test_mint_ulid_moves_collisions_to_other_random_part() {
+        let mut dut = UidMinter::new(UidStrategy::Ulid);
+        let first = dut.mint("a.md", 0, "", |_| false);
+        let second = dut.mint("a.md", 0, "", |f| *f == first);
+        assert_ne!(first, second);
+        assert_eq!(first.0[..10], second.0[..10]);
+    }
+}
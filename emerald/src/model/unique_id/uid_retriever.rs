@@ -1,6 +1,6 @@
 use super::{resource_id_trait::ResourceIdTrait, Uid};
 
-pub trait UidRetriever<T>
+pub trait UidRetriever<T>: Send + Sync
 where
     T: ResourceIdTrait,
 {
@@ -0,0 +1,19 @@
+/// Determines how uids are minted for notes without a uid in their frontmatter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UidStrategy {
+    /// Consecutive numbers in the order in which the notes were created. Adding a
+    /// note can change the uids of the other notes.
+    Sequential,
+
+    /// ULID derived from the creation time and the resource id of the note.
+    #[default]
+    Ulid,
+
+    /// Zettelkasten timestamp (`YYYYMMDDhhmm`) of the creation time of the note.
+    /// Notes created in the same minute are told apart by a suffix derived from
+    /// their resource id.
+    TimestampZettel,
+
+    /// Hash of the content of the note.
+    ContentHash,
+}
@@ -1,6 +1,6 @@
 use super::uid_trait::UidTrait;
 
-pub trait MdContentRetriever<U>
+pub trait MdContentRetriever<U>: Send + Sync
 where
     U: UidTrait,
 {
@@ -19,6 +19,7 @@ pub use note_factory::NoteFactory;
 pub use note_factory_impl::NoteFactoryImpl;
 pub use note_metadata_retriever::NoteMetadataRetriever;
 pub use note_types::NoteTypes;
+#[allow(unused_imports)]
 pub use uid_retriever::UidRetriever;
 pub use vault_impl::VaultImpl;
 pub use vault_trait::Vault;
@@ -1,6 +1,6 @@
 #[derive(Debug, Clone, PartialEq, Hash, Default)]
 pub struct DocumentMetadata {
-    pub tags: Option<String>,
+    pub tags: Vec<String>,
     pub aliases: Vec<String>,
     pub keywords: Vec<String>,
     pub created: Option<String>,
@@ -3,15 +3,15 @@ use super::uid_trait::UidTrait;
 use super::{MdContentRetriever, Note, NoteMetadataRetriever};
 use crate::markdown::{DefaultMarkdownFrontmatterSplitter, MarkdownFrontmatterSplitter};
 
-use std::rc::Rc;
+use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct NoteFactoryImpl<U>
 where
     U: UidTrait,
 {
-    metadata_retriever: Rc<dyn NoteMetadataRetriever<U>>,
-    content_retriever: Rc<dyn MdContentRetriever<U>>,
+    metadata_retriever: Arc<dyn NoteMetadataRetriever<U>>,
+    content_retriever: Arc<dyn MdContentRetriever<U>>,
 }
 
 impl<U> NoteFactoryImpl<U>
@@ -19,8 +19,8 @@ where
     U: UidTrait,
 {
     pub fn new(
-        metadata_retriever: Rc<dyn NoteMetadataRetriever<U>>,
-        content_retriever: Rc<dyn MdContentRetriever<U>>,
+        metadata_retriever: Arc<dyn NoteMetadataRetriever<U>>,
+        content_retriever: Arc<dyn MdContentRetriever<U>>,
     ) -> Self {
         Self {
             metadata_retriever,
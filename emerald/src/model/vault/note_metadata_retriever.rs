@@ -1,7 +1,7 @@
 use super::{uid_trait::UidTrait, DocumentMetadata, FilesystemMetadata};
 
 /// Trait for retrieving metadata associated with a note.
-pub trait NoteMetadataRetriever<U>
+pub trait NoteMetadataRetriever<U>: Send + Sync
 where
     U: UidTrait,
 {
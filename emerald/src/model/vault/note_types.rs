@@ -1,5 +1,6 @@
 use super::{resource_id_trait::ResourceIdTrait, uid_trait::UidTrait, Note};
 
+#[allow(clippy::large_enum_variant)]
 pub enum NoteTypes<T, U>
 where
     T: ResourceIdTrait,
@@ -7,4 +8,7 @@ where
 {
     Note(Note<U>),
     ResourceRef(T),
+
+    /// A note which exists but doesn't contain the section or block a link points to.
+    AnchorMissing(Note<U>),
 }
@@ -1,5 +1,6 @@
 use super::{resource_id_trait::ResourceIdTrait, uid_trait::UidTrait};
 
+#[allow(dead_code)]
 pub trait UidRetriever<T, U>
 where
     T: ResourceIdTrait,
@@ -1,4 +1,4 @@
-pub trait UidTrait: std::fmt::Debug + std::hash::Hash + Eq + Clone {}
+pub trait UidTrait: std::fmt::Debug + std::hash::Hash + Eq + Clone + Send + Sync {}
 
 // Blanket impl
-impl<T> UidTrait for T where T: std::fmt::Debug + std::hash::Hash + Eq + Clone {}
+impl<T> UidTrait for T where T: std::fmt::Debug + std::hash::Hash + Eq + Clone + Send + Sync {}
@@ -3,14 +3,14 @@ use super::uid_trait::UidTrait;
 use super::vault_trait::Vault;
 use super::NoteFactory;
 use super::{MdContentRetriever, NoteFactoryImpl, NoteMetadataRetriever};
-use std::rc::Rc;
+use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct VaultImpl<U>
 where
     U: UidTrait,
 {
-    note_factory: Rc<NoteFactoryImpl<U>>,
+    note_factory: Arc<NoteFactoryImpl<U>>,
 }
 
 impl<U> VaultImpl<U>
@@ -18,10 +18,10 @@ where
     U: UidTrait,
 {
     pub fn new(
-        metadata_retriever: Rc<dyn NoteMetadataRetriever<U>>,
-        content_retriever: Rc<dyn MdContentRetriever<U>>,
+        metadata_retriever: Arc<dyn NoteMetadataRetriever<U>>,
+        content_retriever: Arc<dyn MdContentRetriever<U>>,
     ) -> Self {
-        let note_factory = Rc::new(NoteFactoryImpl::<U>::new(
+        let note_factory = Arc::new(NoteFactoryImpl::<U>::new(
             metadata_retriever,
             content_retriever,
         ));
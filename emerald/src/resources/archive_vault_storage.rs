@@ -0,0 +1,356 @@
+use super::scan_options::ScanOptions;
+use super::vault_storage::{StorageMetadata, VaultStorage};
+use crate::error::{EmeraldError, Result};
+use crate::types;
+use flate2::read::GzDecoder;
+use ignore::overrides::{Override, OverrideBuilder};
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek};
+use std::path::{Component, Path};
+
+/// Formats of archives a vault can be loaded from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+impl ArchiveFormat {
+    /// Determines the format by the name of the archive, e.g. `vault.tar.gz`.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_lowercase();
+        if name.ends_with(".zip") {
+            Some(ArchiveFormat::Zip)
+        } else if name.ends_with(".tar") {
+            Some(ArchiveFormat::Tar)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(ArchiveFormat::TarGz)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ArchiveEntry {
+    content: Vec<u8>,
+    modified: u64,
+}
+
+/// Serves the resources of a vault from a zip or tar archive.
+///
+/// All entries are read into memory when the archive is opened, the archive
+/// isn't extracted. The storage is read only. Entries are filtered by the
+/// hidden, include and exclude settings of the scan options. Ignore files
+/// within the archive aren't considered.
+#[derive(Debug)]
+pub struct ArchiveVaultStorage {
+    entries: BTreeMap<Box<str>, ArchiveEntry>,
+}
+
+/// Converts a (year, month, day) date of the proleptic gregorian calendar to
+/// days since the unix epoch.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// Converts the modification time of a zip entry to seconds since the unix
+/// epoch. Zip archives store local times without a time zone, they are taken
+/// as UTC.
+fn zip_time_to_secs(time: zip::DateTime) -> u64 {
+    let days = days_from_civil(time.year().into(), time.month().into(), time.day().into());
+    let secs = days * 86400
+        + i64::from(time.hour()) * 3600
+        + i64::from(time.minute()) * 60
+        + i64::from(time.second());
+    secs.max(0) as u64
+}
+
+/// Converts the path of an entry into a resource id. Paths leaving the archive
+/// are rejected.
+fn to_rid(path: &Path) -> Option<Box<str>> {
+    let mut segments = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(segment) => segments.push(segment.to_str()?),
+            Component::CurDir => (),
+            _ => return None,
+        }
+    }
+    (!segments.is_empty()).then(|| segments.join("/").into())
+}
+
+/// Decides which entries of the archive belong to the vault.
+struct EntryFilter {
+    skip_hidden: bool,
+    overrides: Override,
+}
+
+impl EntryFilter {
+    fn new(scan_options: &ScanOptions) -> Result<Self> {
+        let mut overrides = OverrideBuilder::new("");
+        for glob in scan_options.include.iter() {
+            overrides.add(glob)?;
+        }
+        for glob in scan_options.exclude.iter() {
+            overrides.add(&format!("!{}", glob))?;
+        }
+        Ok(Self {
+            skip_hidden: scan_options.skip_hidden,
+            overrides: overrides.build()?,
+        })
+    }
+
+    fn is_included(&self, rid: &str) -> bool {
+        if self.skip_hidden && rid.split('/').any(|f| f.starts_with('.')) {
+            return false;
+        }
+        let excluded_dir = Path::new(rid)
+            .ancestors()
+            .skip(1)
+            .filter(|f| !f.as_os_str().is_empty())
+            .any(|f| self.overrides.matched(f, true).is_ignore());
+        !excluded_dir && !self.overrides.matched(rid, false).is_ignore()
+    }
+}
+
+impl ArchiveVaultStorage {
+    /// Opens the archive. The format is determined by the name of the file.
+    pub fn open(path: &Path, scan_options: &ScanOptions) -> Result<Self> {
+        let format = ArchiveFormat::from_path(path)
+            .ok_or_else(|| EmeraldError::UnknownArchiveFormat(path.to_path_buf()))?;
+        let file = BufReader::new(File::open(path)?);
+        match format {
+            ArchiveFormat::Zip => Self::from_zip(file, scan_options),
+            ArchiveFormat::Tar => Self::from_tar(file, scan_options),
+            ArchiveFormat::TarGz => Self::from_tar(GzDecoder::new(file), scan_options),
+        }
+    }
+
+    pub fn from_zip(reader: impl Read + Seek, scan_options: &ScanOptions) -> Result<Self> {
+        let filter = EntryFilter::new(scan_options)?;
+        let mut archive = zip::ZipArchive::new(reader)?;
+        let mut entries = BTreeMap::new();
+        for index in 0..archive.len() {
+            let mut file = archive.by_index(index)?;
+            if file.is_dir() {
+                continue;
+            }
+            let Some(rid) = file.enclosed_name().and_then(to_rid) else {
+                warn!("Skipped archive entry {:?}", file.name());
+                continue;
+            };
+            if !filter.is_included(&rid) {
+                continue;
+            }
+
+            let mut content = Vec::with_capacity(file.size() as usize);
+            file.read_to_end(&mut content)?;
+            let modified = zip_time_to_secs(file.last_modified());
+            entries.insert(rid, ArchiveEntry { content, modified });
+        }
+        Ok(Self { entries })
+    }
+
+    pub fn from_tar(reader: impl Read, scan_options: &ScanOptions) -> Result<Self> {
+        let filter = EntryFilter::new(scan_options)?;
+        let mut archive = tar::Archive::new(reader);
+        let mut entries = BTreeMap::new();
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            let path = entry.path()?.into_owned();
+            let Some(rid) = to_rid(&path) else {
+                warn!("Skipped archive entry {:?}", path);
+                continue;
+            };
+            if !filter.is_included(&rid) {
+                continue;
+            }
+
+            let mut content = Vec::new();
+            entry.read_to_end(&mut content)?;
+            let modified = entry.header().mtime()?;
+            entries.insert(rid, ArchiveEntry { content, modified });
+        }
+        Ok(Self { entries })
+    }
+
+    fn entry(&self, rid: &types::ResourceId) -> Result<&ArchiveEntry> {
+        self.entries
+            .get(&rid.0)
+            .ok_or(EmeraldError::ResourceObjectNotFound)
+    }
+}
+
+impl VaultStorage for ArchiveVaultStorage {
+    fn list(&self) -> Result<Vec<types::ResourceId>> {
+        Ok(self
+            .entries
+            .keys()
+            .map(|f| types::ResourceId(f.clone()))
+            .collect())
+    }
+
+    fn read(&self, rid: &types::ResourceId) -> Result<Vec<u8>> {
+        Ok(self.entry(rid)?.content.clone())
+    }
+
+    fn metadata(&self, rid: &types::ResourceId) -> Result<StorageMetadata> {
+        let entry = self.entry(rid)?;
+        // archives don't keep creation times
+        Ok(StorageMetadata {
+            size: entry.content.len() as u64,
+            modified: entry.modified,
+            created: entry.modified,
+        })
+    }
+
+    fn write(&self, _rid: &types::ResourceId, _content: &[u8]) -> Result<()> {
+        Err(EmeraldError::ReadOnlyStorage)
+    }
+
+    fn rename(&self, _rid: &types::ResourceId, _new_rid: &types::ResourceId) -> Result<()> {
+        Err(EmeraldError::ReadOnlyStorage)
+    }
+
+    fn root(&self) -> Option<&Path> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{days_from_civil, ArchiveFormat, ArchiveVaultStorage};
+    use crate::error::EmeraldError;
+    use crate::resources::{ScanOptions, VaultStorage};
+    use crate::types::ResourceId;
+    use std::io::{Cursor, Write};
+    use std::path::Path;
+
+    const FILES: [(&str, &str); 4] = [
+        ("vault/note.md", "# Note\n"),
+        ("vault/sub/other.md", "[[note]]\n"),
+        ("vault/.obsidian/app.json", "{}"),
+        ("../escape.md", "outside"),
+    ];
+
+    fn create_zip() -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let time = zip::DateTime::from_date_and_time(2023, 10, 18, 15, 30, 0).unwrap();
+        let options = zip::write::FileOptions::default().last_modified_time(time);
+        writer.add_directory("vault/", options).unwrap();
+        for (name, content) in FILES {
+            writer.start_file(name, options).unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    fn create_tar() -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (name, content) in FILES {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mtime(1697643000);
+            header.set_mode(0o644);
+            // set_path rejects paths leaving the archive
+            header.as_old_mut().name[..name.len()].copy_from_slice(name.as_bytes());
+            header.set_cksum();
+            builder.append(&header, content.as_bytes()).unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    fn assert_vault(dut: &ArchiveVaultStorage) {
+        assert_eq!(
+            dut.list().unwrap(),
+            vec![
+                ResourceId::from("vault/note.md"),
+                "vault/sub/other.md".into()
+            ]
+        );
+        let rid = ResourceId::from("vault/sub/other.md");
+        assert_eq!(dut.read(&rid).unwrap(), b"[[note]]\n");
+        let meta_data = dut.metadata(&rid).unwrap();
+        assert_eq!(meta_data.size, 9);
+        assert_eq!(meta_data.modified, 1697643000);
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        let format = |name: &str| ArchiveFormat::from_path(Path::new(name));
+        assert_eq!(format("dir/Vault.ZIP"), Some(ArchiveFormat::Zip));
+        assert_eq!(format("vault.tar"), Some(ArchiveFormat::Tar));
+        assert_eq!(format("vault.tar.gz"), Some(ArchiveFormat::TarGz));
+        assert_eq!(format("vault.tgz"), Some(ArchiveFormat::TarGz));
+        assert_eq!(format("vault"), None);
+    }
+
+    #[test]
+    fn test_days_from_civil() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11017);
+        assert_eq!(days_from_civil(2023, 10, 18), 19648);
+    }
+
+    #[test]
+    fn test_load_zip() {
+        let dut = ArchiveVaultStorage::from_zip(Cursor::new(create_zip()), &ScanOptions::default())
+            .unwrap();
+        assert_vault(&dut);
+    }
+
+    #[test]
+    fn test_load_tar() {
+        let dut = ArchiveVaultStorage::from_tar(Cursor::new(create_tar()), &ScanOptions::default())
+            .unwrap();
+        assert_vault(&dut);
+    }
+
+    #[test]
+    fn test_scan_options_filter_entries() {
+        let options = ScanOptions {
+            skip_hidden: false,
+            exclude: vec!["vault/sub/".into()],
+            ..Default::default()
+        };
+        let dut = ArchiveVaultStorage::from_tar(Cursor::new(create_tar()), &options).unwrap();
+
+        assert_eq!(
+            dut.list().unwrap(),
+            vec![
+                ResourceId::from("vault/.obsidian/app.json"),
+                "vault/note.md".into()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_storage_is_read_only() {
+        let dut = ArchiveVaultStorage::from_tar(Cursor::new(create_tar()), &ScanOptions::default())
+            .unwrap();
+        let rid = ResourceId::from("vault/note.md");
+
+        assert!(matches!(
+            dut.write(&rid, b""),
+            Err(EmeraldError::ReadOnlyStorage)
+        ));
+        assert!(matches!(
+            dut.rename(&rid, &"moved.md".into()),
+            Err(EmeraldError::ReadOnlyStorage)
+        ));
+    }
+}
@@ -3,4 +3,12 @@ use crate::types;
 
 pub trait ContentLoader {
     fn load(&self, rid: &types::ResourceId) -> Result<types::Content>;
+
+    /// Loads the content like `load` but replaces invalid UTF-8 sequences with
+    /// the replacement character. Returns the span of the first invalid
+    /// sequence if there was one.
+    fn load_lossy(
+        &self,
+        rid: &types::ResourceId,
+    ) -> Result<(types::Content, Option<(usize, usize)>)>;
 }
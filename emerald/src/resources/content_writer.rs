@@ -0,0 +1,6 @@
+use crate::error::Result;
+use crate::types;
+
+pub trait ContentWriter: Send + Sync {
+    fn write(&self, rid: &types::ResourceId, content: &str) -> Result<()>;
+}
@@ -0,0 +1,238 @@
+use super::get_path_list::{get_path_list, get_path_list_below};
+use super::scan_options::ScanOptions;
+use super::vault_storage::{StorageMetadata, VaultStorage};
+use crate::error::{EmeraldError, Result};
+use crate::types;
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Stores the resources of a vault as files below a directory.
+///
+/// Writes are atomic. The content is written to a temporary file next to the
+/// target which is then renamed to the target. Readers therefore see either the
+/// old or the new content but never a partially written file.
+#[derive(Debug, Clone)]
+pub struct FileVaultStorage {
+    root: PathBuf,
+    scan_options: ScanOptions,
+}
+
+impl FileVaultStorage {
+    /// # Arguments
+    ///
+    /// * `root`: Directory of the vault.
+    /// * `scan_options`: Determines which files of the directory are listed.
+    pub fn new(root: &Path, scan_options: ScanOptions) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            scan_options,
+        }
+    }
+
+    fn path_of(&self, rid: &types::ResourceId) -> PathBuf {
+        self.root.join(rid.0.as_ref())
+    }
+
+    fn rid_of(&self, path: &Path) -> Result<types::ResourceId> {
+        let rel_path = path
+            .strip_prefix(&self.root)
+            .map_err(|_| EmeraldError::NoCommonPath(path.to_path_buf(), self.root.clone()))?;
+        let rel_path = rel_path.to_str().ok_or(EmeraldError::NotAResourceId)?;
+        Ok(rel_path.into())
+    }
+}
+
+fn secs_since_epoch(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).unwrap().as_secs()
+}
+
+fn temp_path_of(path: &Path) -> Result<PathBuf> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| EmeraldError::NotAFile(path.to_owned()))?;
+    let mut temp_name = std::ffi::OsString::from(".");
+    temp_name.push(file_name);
+    temp_name.push(".emerald-tmp");
+    Ok(path.with_file_name(temp_name))
+}
+
+fn write_atomically(path: &Path, content: &[u8]) -> Result<()> {
+    let temp_path = temp_path_of(path)?;
+    let res = (|| {
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(content)?;
+        file.sync_all()?;
+
+        // keep the permissions of the original file
+        if let Ok(meta_data) = fs::metadata(path) {
+            fs::set_permissions(&temp_path, meta_data.permissions())?;
+        }
+        fs::rename(&temp_path, path)
+    })();
+
+    if res.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    Ok(res?)
+}
+
+impl VaultStorage for FileVaultStorage {
+    fn list(&self) -> Result<Vec<types::ResourceId>> {
+        get_path_list(&self.root, &self.scan_options)?
+            .iter()
+            .map(|path| self.rid_of(path))
+            .collect()
+    }
+
+    fn list_below(&self, rids: &[types::ResourceId]) -> Result<Vec<types::ResourceId>> {
+        let below: Vec<_> = rids.iter().map(|f| self.path_of(f)).collect();
+        get_path_list_below(&self.root, &self.scan_options, &below)?
+            .iter()
+            .map(|path| self.rid_of(path))
+            .collect()
+    }
+
+    fn read(&self, rid: &types::ResourceId) -> Result<Vec<u8>> {
+        Ok(fs::read(self.path_of(rid))?)
+    }
+
+    fn metadata(&self, rid: &types::ResourceId) -> Result<StorageMetadata> {
+        let path = self.path_of(rid);
+        let Ok(meta_data) = fs::metadata(&path) else {
+            return Err(EmeraldError::NoMetadata);
+        };
+        if !meta_data.is_file() {
+            return Err(EmeraldError::NotAFile(path));
+        }
+
+        Ok(StorageMetadata {
+            size: meta_data.len(),
+            modified: secs_since_epoch(meta_data.modified()?),
+            created: secs_since_epoch(meta_data.created()?),
+        })
+    }
+
+    fn write(&self, rid: &types::ResourceId, content: &[u8]) -> Result<()> {
+        let path = self.path_of(rid);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        trace!("Write {:?} to {:?}", rid, path);
+        write_atomically(&path, content)
+    }
+
+    fn rename(&self, rid: &types::ResourceId, new_rid: &types::ResourceId) -> Result<()> {
+        let path = self.path_of(rid);
+        let new_path = self.path_of(new_rid);
+
+        // never overwrite an existing file
+        if new_path.exists() {
+            return Err(EmeraldError::ResourceAlreadyExists(new_rid.0.to_string()));
+        }
+        if let Some(parent) = new_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        trace!("Move {:?} to {:?}", path, new_path);
+        fs::rename(&path, &new_path)?;
+        Ok(())
+    }
+
+    fn root(&self) -> Option<&Path> {
+        Some(&self.root)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{temp_path_of, FileVaultStorage};
+    use crate::error::EmeraldError;
+    use crate::resources::{ScanOptions, VaultStorage};
+    use crate::types::ResourceId;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn create_dut(name: &str) -> (FileVaultStorage, PathBuf) {
+        let dir = std::env::temp_dir().join(format!("emerald_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("note.md"), "content").unwrap();
+        (FileVaultStorage::new(&dir, ScanOptions::default()), dir)
+    }
+
+    #[test]
+    fn test_temp_path_of() {
+        let temp_path = temp_path_of(&PathBuf::from("dir/note.md")).unwrap();
+        assert_eq!(temp_path, PathBuf::from("dir/.note.md.emerald-tmp"));
+    }
+
+    #[test]
+    fn test_list_returns_relative_paths() {
+        let (dut, dir) = create_dut("list_returns_relative_paths");
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join("sub/other.md"), "").unwrap();
+
+        let mut rids = dut.list().unwrap();
+        rids.sort_by(|a, b| a.0.cmp(&b.0));
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            rids,
+            vec![ResourceId::from("note.md"), "sub/other.md".into()]
+        );
+    }
+
+    #[test]
+    fn test_write_replaces_content() {
+        let (dut, dir) = create_dut("write_replaces_content");
+        dut.write(&"note.md".into(), b"new content").unwrap();
+
+        assert_eq!(
+            fs::read_to_string(dir.join("note.md")).unwrap(),
+            "new content"
+        );
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1, "no temp file left");
+        assert_eq!(dut.metadata(&"note.md".into()).unwrap().size, 11);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_rename_into_new_dir() {
+        let (dut, dir) = create_dut("rename_into_new_dir");
+        dut.rename(&"note.md".into(), &"sub/moved.md".into())
+            .unwrap();
+
+        assert!(!dir.join("note.md").exists());
+        assert_eq!(dut.read(&"sub/moved.md".into()).unwrap(), b"content");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_rename_doesnt_overwrite() {
+        let (dut, dir) = create_dut("rename_doesnt_overwrite");
+        fs::write(dir.join("other.md"), "other").unwrap();
+
+        let err = dut
+            .rename(&"note.md".into(), &"other.md".into())
+            .unwrap_err();
+
+        assert!(matches!(err, EmeraldError::ResourceAlreadyExists(_)));
+        assert_eq!(fs::read_to_string(dir.join("other.md")).unwrap(), "other");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_metadata_of_directory_fails() {
+        let (dut, dir) = create_dut("metadata_of_directory_fails");
+        fs::create_dir_all(dir.join("sub")).unwrap();
+
+        let err = dut.metadata(&"sub".into()).unwrap_err();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(matches!(err, EmeraldError::NotAFile(_)));
+    }
+}
@@ -1,28 +1,194 @@
+use super::scan_options::ScanOptions;
 use crate::error::Result;
+use ignore::overrides::OverrideBuilder;
+use ignore::WalkBuilder;
 #[allow(unused_imports)]
 use log::{debug, error, info, trace, warn};
-use std::fs;
 use std::path::{Path, PathBuf};
 
-pub fn get_path_list(path: &Path) -> Result<Vec<PathBuf>> {
-    trace!("get_file_list of path: {:?}", path);
+/// Name of the vault local ignore file.
+const IGNORE_FILENAME: &str = ".emeraldignore";
 
-    // get iterator for the actual directory
-    let iter_dir = fs::read_dir(path)?;
-    let mut file_list = Vec::<PathBuf>::new();
+/// Returns the path of an entry which couldn't be read, e.g. a broken symlink.
+fn unreadable_path(err: &ignore::Error) -> Option<&Path> {
+    match err {
+        ignore::Error::WithPath { path, .. } => Some(path),
+        ignore::Error::WithDepth { err, .. } => unreadable_path(err),
+        _ => None,
+    }
+}
+
+pub fn get_path_list(path: &Path, options: &ScanOptions) -> Result<Vec<PathBuf>> {
+    get_path_list_below(path, options, &[path.to_path_buf()])
+}
+
+/// Returns the files of `get_path_list` which are at or below one of the given
+/// paths. Only the directories leading to the paths are read, so a path which the
+/// scan skips, e.g. a hidden or ignored one, yields no files.
+pub fn get_path_list_below(
+    path: &Path,
+    options: &ScanOptions,
+    below: &[PathBuf],
+) -> Result<Vec<PathBuf>> {
+    trace!("get_file_list of path: {:?} below {:?}", path, below);
 
-    for i in iter_dir {
-        let iter_path = i?.path();
+    let mut overrides = OverrideBuilder::new(path);
+    for glob in options.include.iter() {
+        overrides.add(glob)?;
+    }
+    for glob in options.exclude.iter() {
+        overrides.add(&format!("!{}", glob))?;
+    }
 
-        if iter_path.is_dir() {
-            let mut res = get_path_list(&iter_path)?;
-            file_list.append(&mut res);
-        } else {
-            trace!("Append {:?} to file_list", &iter_path);
+    // only ignore files within the vault are considered
+    let walker = WalkBuilder::new(path)
+        .hidden(options.skip_hidden)
+        .git_ignore(options.use_gitignore)
+        .require_git(false)
+        .git_global(false)
+        .git_exclude(false)
+        .ignore(false)
+        .parents(false)
+        .follow_links(true)
+        .add_custom_ignore_filename(IGNORE_FILENAME)
+        .overrides(overrides.build()?)
+        .filter_entry({
+            let below = below.to_vec();
+            move |entry| {
+                below
+                    .iter()
+                    .any(|f| entry.path().starts_with(f) || f.starts_with(entry.path()))
+            }
+        })
+        .build();
 
-            file_list.push(iter_path);
+    let mut file_list = Vec::<PathBuf>::new();
+    for entry in walker {
+        // unreadable files are kept, loading their metadata reports the error
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(err) => match unreadable_path(&err) {
+                Some(path) if !path.is_dir() => {
+                    file_list.push(path.to_path_buf());
+                    continue;
+                }
+                _ => return Err(err.into()),
+            },
+        };
+        if entry.file_type().is_some_and(|f| f.is_file()) {
+            trace!("Append {:?} to file_list", entry.path());
+            file_list.push(entry.into_path());
         }
     }
 
     Ok(file_list)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{get_path_list, get_path_list_below};
+    use crate::resources::ScanOptions;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    fn create_vault(name: &str) -> PathBuf {
+        let vault_path =
+            std::env::temp_dir().join(format!("emerald_scan_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&vault_path);
+        for (file, content) in [
+            ("note.md", ""),
+            ("sub/other.md", ""),
+            ("sub/image.png", ""),
+            (".obsidian/workspace.md", ""),
+            (".trash/deleted.md", ""),
+            ("node_modules/pkg/readme.md", ""),
+            ("drafts/draft.md", ""),
+            (".gitignore", "node_modules/\n"),
+            (".emeraldignore", "drafts/\n"),
+        ] {
+            let path = vault_path.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        vault_path
+    }
+
+    fn list(vault_path: &Path, options: &ScanOptions) -> Vec<String> {
+        to_sorted_rel_paths(vault_path, get_path_list(vault_path, options).unwrap())
+    }
+
+    fn to_sorted_rel_paths(vault_path: &Path, paths: Vec<PathBuf>) -> Vec<String> {
+        let mut paths: Vec<_> = paths
+            .into_iter()
+            .map(|f| {
+                let rel = f.strip_prefix(vault_path).unwrap();
+                rel.to_string_lossy().replace('\\', "/")
+            })
+            .collect();
+        paths.sort();
+        paths
+    }
+
+    #[test]
+    fn test_hidden_and_ignored_files_are_skipped() {
+        let vault_path = create_vault("default");
+        let paths = list(&vault_path, &ScanOptions::default());
+        fs::remove_dir_all(&vault_path).unwrap();
+
+        assert_eq!(paths, ["note.md", "sub/image.png", "sub/other.md"]);
+    }
+
+    #[test]
+    fn test_hidden_files_and_gitignore_can_be_enabled() {
+        let vault_path = create_vault("hidden");
+        let options = ScanOptions {
+            skip_hidden: false,
+            use_gitignore: false,
+            ..Default::default()
+        };
+        let paths = list(&vault_path, &options);
+        fs::remove_dir_all(&vault_path).unwrap();
+
+        assert_eq!(
+            paths,
+            [
+                ".emeraldignore",
+                ".gitignore",
+                ".obsidian/workspace.md",
+                ".trash/deleted.md",
+                "node_modules/pkg/readme.md",
+                "note.md",
+                "sub/image.png",
+                "sub/other.md"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_include_and_exclude_globs() {
+        let vault_path = create_vault("globs");
+        let options = ScanOptions {
+            include: vec!["*.md".into()],
+            exclude: vec!["sub/**".into()],
+            ..Default::default()
+        };
+        let paths = list(&vault_path, &options);
+        fs::remove_dir_all(&vault_path).unwrap();
+
+        assert_eq!(paths, ["note.md"]);
+    }
+
+    #[test]
+    fn test_list_below_skips_like_the_scan() {
+        let vault_path = create_vault("below");
+        let below: Vec<_> = ["sub", "drafts/draft.md", ".obsidian", "node_modules/pkg"]
+            .iter()
+            .map(|f| vault_path.join(f))
+            .collect();
+        let paths = get_path_list_below(&vault_path, &ScanOptions::default(), &below).unwrap();
+        let paths = to_sorted_rel_paths(&vault_path, paths);
+        fs::remove_dir_all(&vault_path).unwrap();
+
+        assert_eq!(paths, ["sub/image.png", "sub/other.md"]);
+    }
+}
@@ -0,0 +1,165 @@
+use super::vault_storage::{StorageMetadata, VaultStorage};
+use crate::error::{EmeraldError, Result};
+use crate::types;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::RwLock;
+
+#[derive(Debug, Clone)]
+struct MemoryResource {
+    content: Vec<u8>,
+    created: u64,
+    modified: u64,
+}
+
+#[derive(Debug, Default)]
+struct Resources {
+    by_path: BTreeMap<Box<str>, MemoryResource>,
+
+    /// Logical time which advances with every write.
+    clock: u64,
+}
+
+/// Keeps the resources of a vault in memory, e.g. for tests or to embed
+/// emerald into applications which don't store their notes as files.
+///
+/// Times are logical. Each write advances them by one, so refreshing a vault
+/// detects every change.
+#[derive(Debug, Default)]
+pub struct MemoryVaultStorage {
+    resources: RwLock<Resources>,
+}
+
+impl MemoryVaultStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a resource or replaces its content.
+    pub fn insert(&self, rid: impl Into<types::ResourceId>, content: impl Into<Vec<u8>>) {
+        let rid = rid.into();
+        let mut resources = self.resources.write().expect("Lock is poisoned");
+        resources.clock += 1;
+        let clock = resources.clock;
+        resources
+            .by_path
+            .entry(rid.0)
+            .and_modify(|f| f.modified = clock)
+            .or_insert_with(|| MemoryResource {
+                content: vec![],
+                created: clock,
+                modified: clock,
+            })
+            .content = content.into();
+    }
+
+    /// Removes a resource and returns its content.
+    pub fn remove(&self, rid: &types::ResourceId) -> Option<Vec<u8>> {
+        let mut resources = self.resources.write().expect("Lock is poisoned");
+        resources.by_path.remove(&rid.0).map(|f| f.content)
+    }
+}
+
+impl VaultStorage for MemoryVaultStorage {
+    fn list(&self) -> Result<Vec<types::ResourceId>> {
+        let resources = self.resources.read().expect("Lock is poisoned");
+        Ok(resources
+            .by_path
+            .keys()
+            .map(|f| types::ResourceId(f.clone()))
+            .collect())
+    }
+
+    fn read(&self, rid: &types::ResourceId) -> Result<Vec<u8>> {
+        let resources = self.resources.read().expect("Lock is poisoned");
+        resources
+            .by_path
+            .get(&rid.0)
+            .map(|f| f.content.clone())
+            .ok_or(EmeraldError::ResourceObjectNotFound)
+    }
+
+    fn metadata(&self, rid: &types::ResourceId) -> Result<StorageMetadata> {
+        let resources = self.resources.read().expect("Lock is poisoned");
+        resources
+            .by_path
+            .get(&rid.0)
+            .map(|f| StorageMetadata {
+                size: f.content.len() as u64,
+                modified: f.modified,
+                created: f.created,
+            })
+            .ok_or(EmeraldError::ResourceObjectNotFound)
+    }
+
+    fn write(&self, rid: &types::ResourceId, content: &[u8]) -> Result<()> {
+        self.insert(rid.clone(), content);
+        Ok(())
+    }
+
+    fn rename(&self, rid: &types::ResourceId, new_rid: &types::ResourceId) -> Result<()> {
+        let mut resources = self.resources.write().expect("Lock is poisoned");
+        if resources.by_path.contains_key(&new_rid.0) {
+            return Err(EmeraldError::ResourceAlreadyExists(new_rid.0.to_string()));
+        }
+        let resource = resources
+            .by_path
+            .remove(&rid.0)
+            .ok_or(EmeraldError::ResourceObjectNotFound)?;
+        resources.by_path.insert(new_rid.0.clone(), resource);
+        Ok(())
+    }
+
+    fn root(&self) -> Option<&Path> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MemoryVaultStorage;
+    use crate::error::EmeraldError;
+    use crate::resources::VaultStorage;
+    use crate::types::ResourceId;
+
+    fn create_dut() -> MemoryVaultStorage {
+        let dut = MemoryVaultStorage::new();
+        dut.insert("b.md", "# B");
+        dut.insert("a/c.md", "# C");
+        dut
+    }
+
+    #[test]
+    fn test_list_is_sorted() {
+        let dut = create_dut();
+        assert_eq!(
+            dut.list().unwrap(),
+            vec![ResourceId::from("a/c.md"), "b.md".into()]
+        );
+    }
+
+    #[test]
+    fn test_write_advances_modification_time() {
+        let dut = create_dut();
+        let rid = ResourceId::from("b.md");
+        let before = dut.metadata(&rid).unwrap();
+        dut.write(&rid, b"# Bb").unwrap();
+        let after = dut.metadata(&rid).unwrap();
+
+        assert_eq!(dut.read(&rid).unwrap(), b"# Bb");
+        assert_eq!(after.created, before.created);
+        assert!(after.modified > before.modified);
+        assert_eq!(after.size, 4);
+    }
+
+    #[test]
+    fn test_rename_doesnt_overwrite() {
+        let dut = create_dut();
+        let err = dut.rename(&"b.md".into(), &"a/c.md".into()).unwrap_err();
+        assert!(matches!(err, EmeraldError::ResourceAlreadyExists(_)));
+
+        dut.rename(&"b.md".into(), &"d.md".into()).unwrap();
+        assert!(dut.read(&"b.md".into()).is_err());
+        assert_eq!(dut.read(&"d.md".into()).unwrap(), b"# B");
+    }
+}
@@ -1,30 +1,36 @@
-mod adapter_to_ro;
-mod adapter_to_ro_and_rid;
+mod archive_vault_storage;
 mod content_loader;
-mod file_content_loader;
+mod content_writer;
+mod file_vault_storage;
 mod filesystem_metadata_loader_impl;
 mod get_path_list;
+mod memory_vault_storage;
+mod scan_options;
+mod storage_content_loader;
+mod storage_content_writer;
+mod storage_resource_mover;
+mod vault_storage;
 
 mod adapter_to_rid_and_content;
 mod filesystem_metadata_loader;
-mod resource_object;
-mod resource_object_map;
-mod resource_object_retriever;
-mod resource_object_translation;
+mod resource_mover;
 
 pub use adapter_to_rid_and_content::adapter_to_rid_and_content;
-pub use adapter_to_ro::adapter_to_ro;
-pub use adapter_to_ro_and_rid::adapter_to_ro_and_rid;
+pub use archive_vault_storage::ArchiveFormat;
+pub use archive_vault_storage::ArchiveVaultStorage;
 pub use content_loader::ContentLoader;
-pub use file_content_loader::FileContentLoader;
+pub use content_writer::ContentWriter;
+pub use file_vault_storage::FileVaultStorage;
 pub use filesystem_metadata_loader::FilesystemMetadataLoader;
 pub use filesystem_metadata_loader_impl::FilesystemMetadataLoaderImpl;
-pub use filesystem_metadata_loader_impl::FsMetadataAccessImpl;
-pub use get_path_list::get_path_list;
-pub use resource_object_map::ResourceObjectMap;
+pub use memory_vault_storage::MemoryVaultStorage;
+pub use resource_mover::ResourceMover;
+pub use scan_options::ScanOptions;
+pub use storage_content_loader::StorageContentLoader;
+pub use storage_content_writer::StorageContentWriter;
+pub use storage_resource_mover::StorageResourceMover;
+pub use vault_storage::StorageMetadata;
+pub use vault_storage::VaultStorage;
 
 #[cfg(test)]
 pub use filesystem_metadata_loader::MockFilesystemMetadataLoader;
-
-#[cfg(test)]
-pub use resource_object_retriever::MockResourceObjectRetriever;
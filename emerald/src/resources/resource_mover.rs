@@ -0,0 +1,7 @@
+use crate::error::Result;
+use crate::types;
+
+pub trait ResourceMover: Send + Sync {
+    /// Moves a resource to a new resource id. Missing directories are created.
+    fn move_resource(&self, rid: &types::ResourceId, new_rid: &types::ResourceId) -> Result<()>;
+}
@@ -0,0 +1,31 @@
+/// Determines which files of the vault directory are loaded.
+///
+/// Files listed in a `.emeraldignore` file of the vault are always skipped. It
+/// uses the same syntax as `.gitignore`.
+#[derive(Debug, Clone)]
+pub struct ScanOptions {
+    /// Skips hidden files and directories like `.git`, `.obsidian` or `.trash`.
+    pub skip_hidden: bool,
+
+    /// Skips the files listed in the `.gitignore` files of the vault.
+    pub use_gitignore: bool,
+
+    /// Only files matching one of these globs are loaded. All files are loaded if
+    /// there is none. Included files are loaded even if they would be skipped
+    /// otherwise.
+    pub include: Vec<String>,
+
+    /// Files matching one of these globs are skipped.
+    pub exclude: Vec<String>,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            skip_hidden: true,
+            use_gitignore: true,
+            include: vec![],
+            exclude: vec![],
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use super::content_loader::ContentLoader;
+use super::vault_storage::VaultStorage;
+use crate::error::Result;
+use crate::types;
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+use std::io;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct StorageContentLoader {
+    storage: Arc<dyn VaultStorage>,
+}
+
+impl StorageContentLoader {
+    pub fn new(storage: Arc<dyn VaultStorage>) -> Self {
+        Self { storage }
+    }
+}
+
+impl ContentLoader for StorageContentLoader {
+    fn load(&self, rid: &types::ResourceId) -> Result<types::Content> {
+        let bytes = self.storage.read(rid)?;
+        let content = String::from_utf8(bytes)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        Ok(content.into())
+    }
+
+    fn load_lossy(
+        &self,
+        rid: &types::ResourceId,
+    ) -> Result<(types::Content, Option<(usize, usize)>)> {
+        let bytes = self.storage.read(rid)?;
+        match String::from_utf8(bytes) {
+            Ok(content) => Ok((content.into(), None)),
+            Err(err) => {
+                let utf8_err = err.utf8_error();
+                let start = utf8_err.valid_up_to();
+                let end = utf8_err
+                    .error_len()
+                    .map_or(err.as_bytes().len(), |len| start + len);
+                let content = String::from_utf8_lossy(err.as_bytes()).into_owned();
+                Ok((content.into(), Some((start, end))))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StorageContentLoader;
+    use crate::resources::{ContentLoader, MemoryVaultStorage};
+    use std::sync::Arc;
+
+    fn create_dut(bytes: &[u8]) -> StorageContentLoader {
+        let storage = MemoryVaultStorage::new();
+        storage.insert("note.md", bytes);
+        StorageContentLoader::new(Arc::new(storage))
+    }
+
+    #[test]
+    fn test_load_lossy_of_valid_utf8() {
+        let dut = create_dut("Grüße".as_bytes());
+        let (content, span) = dut.load_lossy(&"note.md".into()).unwrap();
+
+        assert_eq!(content.0, "Grüße");
+        assert_eq!(span, None);
+    }
+
+    #[test]
+    fn test_load_lossy_replaces_invalid_utf8() {
+        let dut = create_dut(b"ab\xffcd\xfe");
+        assert!(dut.load(&"note.md".into()).is_err());
+        let (content, span) = dut.load_lossy(&"note.md".into()).unwrap();
+
+        assert_eq!(content.0, "ab\u{fffd}cd\u{fffd}");
+        assert_eq!(span, Some((2, 3)));
+    }
+}
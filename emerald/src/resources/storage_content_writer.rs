@@ -0,0 +1,23 @@
+use super::content_writer::ContentWriter;
+use super::vault_storage::VaultStorage;
+use crate::error::Result;
+use crate::types;
+use std::sync::Arc;
+
+/// Writes the content of notes to the storage of the vault.
+#[derive(Clone)]
+pub struct StorageContentWriter {
+    storage: Arc<dyn VaultStorage>,
+}
+
+impl StorageContentWriter {
+    pub fn new(storage: Arc<dyn VaultStorage>) -> Self {
+        Self { storage }
+    }
+}
+
+impl ContentWriter for StorageContentWriter {
+    fn write(&self, rid: &types::ResourceId, content: &str) -> Result<()> {
+        self.storage.write(rid, content.as_bytes())
+    }
+}
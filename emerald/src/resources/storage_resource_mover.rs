@@ -0,0 +1,23 @@
+use super::resource_mover::ResourceMover;
+use super::vault_storage::VaultStorage;
+use crate::error::Result;
+use crate::types;
+use std::sync::Arc;
+
+/// Moves resources within the storage of the vault.
+#[derive(Clone)]
+pub struct StorageResourceMover {
+    storage: Arc<dyn VaultStorage>,
+}
+
+impl StorageResourceMover {
+    pub fn new(storage: Arc<dyn VaultStorage>) -> Self {
+        Self { storage }
+    }
+}
+
+impl ResourceMover for StorageResourceMover {
+    fn move_resource(&self, rid: &types::ResourceId, new_rid: &types::ResourceId) -> Result<()> {
+        self.storage.rename(rid, new_rid)
+    }
+}
@@ -0,0 +1,53 @@
+use crate::error::Result;
+use crate::types;
+use std::path::Path;
+
+/// Size and times of a stored resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StorageMetadata {
+    /// Size in bytes.
+    pub size: u64,
+
+    /// Time of the last modification in seconds since the unix epoch.
+    pub modified: u64,
+
+    /// Time of the creation in seconds since the unix epoch.
+    pub created: u64,
+}
+
+/// Backend which stores the resources of a vault.
+///
+/// Resources are addressed by their vault relative path. All loading and
+/// writing of emerald goes through this trait, so a vault can be kept in a
+/// directory, in memory or anywhere else.
+pub trait VaultStorage: Send + Sync {
+    /// Returns the paths of all resources of the vault.
+    fn list(&self) -> Result<Vec<types::ResourceId>>;
+
+    /// Returns the paths of the resources of [`VaultStorage::list`] which are at or
+    /// below one of the given paths.
+    fn list_below(&self, rids: &[types::ResourceId]) -> Result<Vec<types::ResourceId>> {
+        let is_below = |rid: &types::ResourceId| {
+            rids.iter()
+                .any(|f| Path::new(rid.0.as_ref()).starts_with(f.0.as_ref()))
+        };
+        Ok(self.list()?.into_iter().filter(is_below).collect())
+    }
+
+    /// Returns the raw content of a resource.
+    fn read(&self, rid: &types::ResourceId) -> Result<Vec<u8>>;
+
+    /// Returns the size and the times of a resource. Times are in whole seconds.
+    fn metadata(&self, rid: &types::ResourceId) -> Result<StorageMetadata>;
+
+    /// Replaces the content of a resource. The resource is created if it
+    /// doesn't exist.
+    fn write(&self, rid: &types::ResourceId, content: &[u8]) -> Result<()>;
+
+    /// Moves a resource to a new path. Existing resources are never overwritten.
+    fn rename(&self, rid: &types::ResourceId, new_rid: &types::ResourceId) -> Result<()>;
+
+    /// Returns the directory the resources are stored in, if they are stored in
+    /// the filesystem.
+    fn root(&self) -> Option<&Path>;
+}
@@ -9,6 +9,7 @@ pub fn extract_link_stats(it_src: &impl link::LinksIterSrc) -> VaultLinkStats {
     let mut invalid_backlink_cnt: usize = 0;
     let mut note_valid_backlink_cnt: usize = 0;
     let mut note_invalid_backlink_cnt: usize = 0;
+    let mut anchor_missing_backlink_cnt: usize = 0;
 
     let mut iter_mut = it_src.create_iter();
     let mut opt_last_src: Option<types::ResourceId> = None;
@@ -43,10 +44,23 @@ pub fn extract_link_stats(it_src: &impl link::LinksIterSrc) -> VaultLinkStats {
                 src,
                 link,
                 tgt: None,
+                ..
             } => {
                 note_invalid_backlink_cnt += 1;
                 warn!("Invalid link '{:?}' found in '{:?}'", &link, &src);
             }
+            types::LinkSrc2Tgt {
+                src,
+                link,
+                anchor_missing: true,
+                ..
+            } => {
+                anchor_missing_backlink_cnt += 1;
+                warn!(
+                    "Link '{:?}' with missing anchor found in '{:?}'",
+                    &link, &src
+                );
+            }
             _ => note_valid_backlink_cnt += 1,
         }
 
@@ -56,14 +70,31 @@ pub fn extract_link_stats(it_src: &impl link::LinksIterSrc) -> VaultLinkStats {
     VaultLinkStats {
         valid_backlinks: valid_backlink_cnt,
         invalid_backlinks: invalid_backlink_cnt,
+        anchor_missing_backlinks: anchor_missing_backlink_cnt,
+    }
+}
+
+/// Counts the given links like [`extract_link_stats`] without reporting them.
+pub fn count_links<'a>(it_src: impl IntoIterator<Item = &'a types::LinkSrc2Tgt>) -> VaultLinkStats {
+    let mut link_stats = VaultLinkStats::default();
+    for s2t in it_src.into_iter() {
+        if s2t.tgt.is_none() {
+            link_stats.invalid_backlinks += 1;
+        } else if s2t.anchor_missing {
+            link_stats.anchor_missing_backlinks += 1;
+        } else {
+            link_stats.valid_backlinks += 1;
+        }
     }
+    link_stats
 }
 
 #[cfg(test)]
 mod link_mapper_tests {
-    use super::extract_link_stats;
+    use super::{count_links, extract_link_stats};
     use crate::model::link;
     use crate::types;
+    use types::LinkKind::WikiLink;
     use types::LinkSrc2Tgt;
 
     #[rustfmt::skip]
@@ -72,13 +103,17 @@ mod link_mapper_tests {
         let mut ret = link::MockLinksIterSrc::new();
         ret.expect_create_iter().returning(||
             vec![
-                LinkSrc2Tgt::new("resource_id_0".into(), "link_0".into(), None),
-                LinkSrc2Tgt::new("resource_id_0".into(), "link_1".into(), None),
-                LinkSrc2Tgt::new("resource_id_0".into(), "link_2".into(), Some("resource_id_a".into())),
-                LinkSrc2Tgt::new("resource_id_1".into(), "link_3".into(), Some("resource_id_a".into())),
-                LinkSrc2Tgt::new("resource_id_1".into(), "link_4".into(), Some("resource_id_b".into())),
-                LinkSrc2Tgt::new("resource_id_2".into(), "link_5".into(), None),
-                LinkSrc2Tgt::new("resource_id_2".into(), "link_6".into(), Some("resource_id_b".into())),
+                LinkSrc2Tgt::new("resource_id_0".into(), "link_0".into(), None, WikiLink),
+                LinkSrc2Tgt::new("resource_id_0".into(), "link_1".into(), None, WikiLink),
+                LinkSrc2Tgt::new("resource_id_0".into(), "link_2".into(), Some("resource_id_a".into()), WikiLink),
+                LinkSrc2Tgt::new("resource_id_1".into(), "link_3".into(), Some("resource_id_a".into()), WikiLink),
+                LinkSrc2Tgt::new("resource_id_1".into(), "link_4".into(), Some("resource_id_b".into()), WikiLink),
+                LinkSrc2Tgt::new("resource_id_2".into(), "link_5".into(), None, WikiLink),
+                LinkSrc2Tgt::new("resource_id_2".into(), "link_6".into(), Some("resource_id_b".into()), WikiLink),
+                LinkSrc2Tgt {
+                    anchor_missing: true,
+                    ..LinkSrc2Tgt::new("resource_id_2".into(), "link_7".into(), Some("resource_id_b".into()), WikiLink)
+                },
             ].into_iter()
         );
         ret
@@ -107,4 +142,29 @@ mod link_mapper_tests {
         // assert
         assert_eq!(link_stats.valid_backlinks, 4);
     }
+
+    #[test]
+    fn test_anchor_missing_backlink_count_with_test_data() {
+        // arrange
+        let test_data = create_test_data();
+
+        // act
+        let link_stats = extract_link_stats(&test_data);
+
+        // assert
+        assert_eq!(link_stats.anchor_missing_backlinks, 1);
+    }
+
+    #[test]
+    fn test_count_links_matches_extracted_stats() {
+        // arrange
+        let test_data = create_test_data();
+        let links: Vec<_> = link::LinksIterSrc::create_iter(&test_data).collect();
+
+        // act
+        let link_stats = count_links(&links);
+
+        // assert
+        assert_eq!(link_stats, extract_link_stats(&create_test_data()));
+    }
 }
@@ -5,5 +5,5 @@ mod vault_link_stats;
 mod vault_stats;
 
 pub use extract_file_stats::extract_file_stats;
-pub use extract_link_stats::extract_link_stats;
+pub use extract_link_stats::{count_links, extract_link_stats};
 pub use vault_stats::VaultStats;
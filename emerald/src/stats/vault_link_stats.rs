@@ -1,4 +1,24 @@
+use std::ops::{AddAssign, SubAssign};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub struct VaultLinkStats {
     pub valid_backlinks: usize,
     pub invalid_backlinks: usize,
+    pub anchor_missing_backlinks: usize,
+}
+
+impl AddAssign for VaultLinkStats {
+    fn add_assign(&mut self, rhs: Self) {
+        self.valid_backlinks += rhs.valid_backlinks;
+        self.invalid_backlinks += rhs.invalid_backlinks;
+        self.anchor_missing_backlinks += rhs.anchor_missing_backlinks;
+    }
+}
+
+impl SubAssign for VaultLinkStats {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.valid_backlinks -= rhs.valid_backlinks;
+        self.invalid_backlinks -= rhs.invalid_backlinks;
+        self.anchor_missing_backlinks -= rhs.anchor_missing_backlinks;
+    }
 }
@@ -0,0 +1,26 @@
+use super::direction::Direction;
+use crate::model::link;
+use crate::types;
+
+/// Returns the resources directly linked with `rid` in the given direction.
+/// Links of a resource to itself are left out.
+pub fn adjacent(
+    links: &(impl link::TgtIterRetriever + link::SrcIterRetriever),
+    rid: &types::ResourceId,
+    direction: Direction,
+) -> Vec<types::ResourceId> {
+    let forward = matches!(direction, Direction::Forward | Direction::Both)
+        .then(|| link::TgtIterRetriever::retrieve(links, rid))
+        .flatten()
+        .into_iter()
+        .flatten()
+        .filter_map(|f| f.tgt);
+    let backward = matches!(direction, Direction::Backward | Direction::Both)
+        .then(|| link::SrcIterRetriever::retrieve(links, rid))
+        .flatten()
+        .into_iter()
+        .flatten()
+        .map(|f| f.src);
+
+    forward.chain(backward).filter(|f| f != rid).collect()
+}
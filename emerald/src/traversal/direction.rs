@@ -0,0 +1,13 @@
+/// Determines which links are followed when traversing the link graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Direction {
+    /// From a note to the resources it links to.
+    #[default]
+    Forward,
+
+    /// From a note to the notes linking to it.
+    Backward,
+
+    /// Links are followed in both directions.
+    Both,
+}
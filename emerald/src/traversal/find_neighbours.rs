@@ -0,0 +1,110 @@
+use super::adjacent::adjacent;
+use super::direction::Direction;
+use super::neighbour::Neighbour;
+use crate::model::link;
+use crate::types;
+use std::collections::{HashSet, VecDeque};
+
+/// Returns the resources reachable from `start` by following at most `depth`
+/// links (breadth first). Every resource is returned once together with its
+/// shortest distance to `start`; `start` itself is not part of the result.
+///
+/// # Arguments
+///
+/// * `links`: Links of the vault.
+/// * `start`: Resource id of the note to start from.
+/// * `depth`: Maximal number of links to follow.
+/// * `direction`: Direction of the links to follow.
+pub fn find_neighbours(
+    links: &(impl link::TgtIterRetriever + link::SrcIterRetriever),
+    start: &types::ResourceId,
+    depth: usize,
+    direction: Direction,
+) -> Vec<Neighbour> {
+    let mut visited = HashSet::from([start.clone()]);
+    let mut queue = VecDeque::from([(start.clone(), 0)]);
+    let mut neighbours = vec![];
+
+    while let Some((rid, distance)) = queue.pop_front() {
+        if distance == depth {
+            continue;
+        }
+        for next in adjacent(links, &rid, direction) {
+            if visited.insert(next.clone()) {
+                neighbours.push(Neighbour {
+                    rid: next.clone(),
+                    distance: distance + 1,
+                });
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    neighbours
+}
+
+#[cfg(test)]
+mod tests {
+    use super::find_neighbours;
+    use crate::model::link::DefaultLinkModel;
+    use crate::traversal::{Direction, Neighbour};
+    use crate::types::LinkSrc2Tgt;
+
+    /// a -> b -> c -> a, c -> d, e -> a
+    fn create_links() -> DefaultLinkModel {
+        DefaultLinkModel::new(
+            [
+                ("a", "[[b]]", "b"),
+                ("b", "[[c]]", "c"),
+                ("c", "[[a]]", "a"),
+                ("c", "[[d]]", "d"),
+                ("c", "[[c]]", "c"),
+                ("e", "[[a]]", "a"),
+            ]
+            .map(LinkSrc2Tgt::from),
+        )
+    }
+
+    fn neighbours(start: &str, depth: usize, direction: Direction) -> Vec<(String, usize)> {
+        find_neighbours(&create_links(), &start.into(), depth, direction)
+            .into_iter()
+            .map(|Neighbour { rid, distance }| (rid.0.into(), distance))
+            .collect()
+    }
+
+    #[test]
+    fn test_forward_neighbours() {
+        assert_eq!(
+            neighbours("a", 2, Direction::Forward),
+            [("b".into(), 1), ("c".into(), 2)]
+        );
+    }
+
+    #[test]
+    fn test_neighbours_stop_at_cycles() {
+        assert_eq!(
+            neighbours("a", 10, Direction::Forward),
+            [("b".into(), 1), ("c".into(), 2), ("d".into(), 3)]
+        );
+    }
+
+    #[test]
+    fn test_backward_neighbours() {
+        assert_eq!(
+            neighbours("a", 1, Direction::Backward),
+            [("c".into(), 1), ("e".into(), 1)]
+        );
+    }
+
+    #[test]
+    fn test_neighbours_in_both_directions() {
+        assert_eq!(
+            neighbours("d", 2, Direction::Both),
+            [("c".into(), 1), ("a".into(), 2), ("b".into(), 2)]
+        );
+    }
+
+    #[test]
+    fn test_neighbours_without_depth() {
+        assert!(neighbours("a", 0, Direction::Both).is_empty());
+    }
+}
@@ -0,0 +1,100 @@
+use super::adjacent::adjacent;
+use super::direction::Direction;
+use crate::model::link;
+use crate::types;
+use std::collections::{HashMap, VecDeque};
+
+/// Returns the shortest chain of links from `from` to `to`, including both
+/// ends, or None if `to` can't be reached.
+///
+/// # Arguments
+///
+/// * `links`: Links of the vault.
+/// * `from`: Resource id of the first note.
+/// * `to`: Resource id of the last note.
+/// * `direction`: Direction of the links to follow.
+pub fn find_shortest_path(
+    links: &(impl link::TgtIterRetriever + link::SrcIterRetriever),
+    from: &types::ResourceId,
+    to: &types::ResourceId,
+    direction: Direction,
+) -> Option<Vec<types::ResourceId>> {
+    // maps every visited resource to the one it was reached from
+    let mut predecessors: HashMap<types::ResourceId, Option<types::ResourceId>> =
+        HashMap::from([(from.clone(), None)]);
+    let mut queue = VecDeque::from([from.clone()]);
+
+    while let Some(rid) = queue.pop_front() {
+        if rid == *to {
+            let mut path = vec![rid];
+            while let Some(Some(prev)) = predecessors.get(path.last()?) {
+                path.push(prev.clone());
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for next in adjacent(links, &rid, direction) {
+            if !predecessors.contains_key(&next) {
+                predecessors.insert(next.clone(), Some(rid.clone()));
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::find_shortest_path;
+    use crate::model::link::DefaultLinkModel;
+    use crate::traversal::Direction;
+    use crate::types::LinkSrc2Tgt;
+
+    /// a -> b -> c -> d, a -> x -> d, e -> d
+    fn create_links() -> DefaultLinkModel {
+        DefaultLinkModel::new(
+            [
+                ("a", "[[b]]", "b"),
+                ("b", "[[c]]", "c"),
+                ("c", "[[d]]", "d"),
+                ("a", "[[x]]", "x"),
+                ("x", "[[d]]", "d"),
+                ("e", "[[d]]", "d"),
+            ]
+            .map(LinkSrc2Tgt::from),
+        )
+    }
+
+    fn path(from: &str, to: &str, direction: Direction) -> Option<Vec<String>> {
+        find_shortest_path(&create_links(), &from.into(), &to.into(), direction)
+            .map(|f| f.into_iter().map(|rid| rid.0.into()).collect())
+    }
+
+    #[test]
+    fn test_shortest_path() {
+        assert_eq!(path("a", "d", Direction::Forward).unwrap(), ["a", "x", "d"]);
+    }
+
+    #[test]
+    fn test_shortest_path_against_link_direction() {
+        assert_eq!(path("d", "a", Direction::Forward), None);
+        assert_eq!(
+            path("d", "a", Direction::Backward).unwrap(),
+            ["d", "x", "a"]
+        );
+    }
+
+    #[test]
+    fn test_shortest_path_in_both_directions() {
+        assert_eq!(path("a", "e", Direction::Forward), None);
+        assert_eq!(
+            path("a", "e", Direction::Both).unwrap(),
+            ["a", "x", "d", "e"]
+        );
+    }
+
+    #[test]
+    fn test_shortest_path_to_itself() {
+        assert_eq!(path("a", "a", Direction::Forward).unwrap(), ["a"]);
+    }
+}
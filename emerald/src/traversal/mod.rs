@@ -0,0 +1,10 @@
+mod adjacent;
+mod direction;
+mod find_neighbours;
+mod find_shortest_path;
+mod neighbour;
+
+pub use direction::Direction;
+pub use find_neighbours::find_neighbours;
+pub use find_shortest_path::find_shortest_path;
+pub use neighbour::Neighbour;
@@ -0,0 +1,10 @@
+use crate::types;
+
+/// A resource reachable from a note within a number of links.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Neighbour {
+    pub rid: types::ResourceId,
+
+    /// Minimal number of links between the note and the resource.
+    pub distance: usize,
+}
@@ -0,0 +1,17 @@
+use super::ResourceId;
+
+/// An alias of a note which is shadowed by the filename of another note.
+///
+/// Links are resolved by filename first, so links using the alias never reach the
+/// note which declares it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AliasConflict {
+    /// Alias as written in the frontmatter.
+    pub alias: String,
+
+    /// Note which declares the alias.
+    pub alias_of: ResourceId,
+
+    /// Note whose filename matches the alias.
+    pub shadowed_by: ResourceId,
+}
@@ -0,0 +1,23 @@
+use super::{LinkKind, ResourceId};
+
+/// A link whose target doesn't exist in the vault.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrokenLink {
+    /// Note containing the link.
+    pub src: ResourceId,
+
+    /// Link as written in the note, e.g. `[[missing]]` or `![alt](missing.png)`.
+    pub link: String,
+
+    /// Markdown syntax of the link.
+    pub kind: LinkKind,
+
+    /// Line of the link within the note starting with 1.
+    pub line: usize,
+
+    /// Column (in chars) of the link within its line starting with 1.
+    pub column: usize,
+
+    /// Names of existing notes which are similar to the target, best match first.
+    pub suggestions: Vec<String>,
+}
@@ -0,0 +1,44 @@
+use super::ResourceId;
+
+/// Describes the problem a diagnostic reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiagnosticKind {
+    /// The note isn't valid UTF-8. Invalid sequences were replaced.
+    InvalidUtf8,
+
+    /// The frontmatter can't be parsed. The note is treated as having none.
+    InvalidFrontmatter,
+
+    /// The content of the note can't be read. The note is treated as empty.
+    UnreadableContent,
+
+    /// The filesystem metadata can't be read. The resource is skipped.
+    UnreadableMetadata,
+}
+
+impl DiagnosticKind {
+    /// Returns the name of the kind as used in reports.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiagnosticKind::InvalidUtf8 => "invalid_utf8",
+            DiagnosticKind::InvalidFrontmatter => "invalid_frontmatter",
+            DiagnosticKind::UnreadableContent => "unreadable_content",
+            DiagnosticKind::UnreadableMetadata => "unreadable_metadata",
+        }
+    }
+}
+
+/// A problem with a resource which was found while loading the vault.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Vault relative path of the resource.
+    pub path: ResourceId,
+
+    pub kind: DiagnosticKind,
+
+    /// Description of the problem.
+    pub message: String,
+
+    /// Byte range (start, end) within the file the problem refers to, if known.
+    pub span: Option<(usize, usize)>,
+}
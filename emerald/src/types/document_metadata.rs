@@ -1,9 +1,10 @@
+use super::{FrontmatterTags, FrontmatterUid};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, PartialEq, Serialize, Deserialize, Default)]
 pub struct DocumentMetadata {
-    pub uid: Option<String>,
-    pub tags: Option<String>,
+    pub uid: Option<FrontmatterUid>,
+    pub tags: Option<FrontmatterTags>,
     pub aliases: Option<Vec<String>>,
     pub created: Option<String>,
     pub modified: Option<String>,
@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+
+/// Tags as written in the frontmatter of a note.
+///
+/// Tags are either given as a single string (`tags: "#a, b c"`) or as a list
+/// (`tags: [a, "#b"]`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FrontmatterTags {
+    Text(String),
+    List(Vec<String>),
+}
+
+impl FrontmatterTags {
+    /// Returns the normalised tags.
+    ///
+    /// Tags given as string are separated by commas or whitespaces. The leading `#`
+    /// of a tag is removed and empty tags are skipped.
+    pub fn to_vec(&self) -> Vec<String> {
+        let tags: Vec<&str> = match self {
+            FrontmatterTags::Text(text) => text
+                .split(|ch: char| ch == ',' || ch.is_whitespace())
+                .collect(),
+            FrontmatterTags::List(list) => list.iter().map(|f| f.as_str()).collect(),
+        };
+
+        tags.into_iter()
+            .map(|f| f.trim().trim_start_matches('#'))
+            .filter(|f| !f.is_empty())
+            .map(|f| f.to_owned())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FrontmatterTags;
+
+    #[test]
+    fn test_to_vec_from_single_tag() {
+        let dut = FrontmatterTags::Text("#Typ/Notiz".into());
+        assert_eq!(dut.to_vec(), ["Typ/Notiz"]);
+    }
+
+    #[test]
+    fn test_to_vec_from_separated_string() {
+        let dut = FrontmatterTags::Text("a, #b  c,".into());
+        assert_eq!(dut.to_vec(), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_to_vec_from_list() {
+        let dut = FrontmatterTags::List(vec!["#a".into(), " b/c ".into(), "".into()]);
+        assert_eq!(dut.to_vec(), ["a", "b/c"]);
+    }
+
+    #[test]
+    fn test_deserialize_string_and_list() {
+        let text: FrontmatterTags = serde_yaml::from_str("\"#a b\"").unwrap();
+        let list: FrontmatterTags = serde_yaml::from_str("[a, b]").unwrap();
+
+        assert_eq!(text, FrontmatterTags::Text("#a b".into()));
+        assert_eq!(list, FrontmatterTags::List(vec!["a".into(), "b".into()]));
+    }
+}
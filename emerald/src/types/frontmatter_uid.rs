@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+/// Uid as written in the frontmatter of a note.
+///
+/// Zettelkasten ids like `uid: 202310181530` are parsed by yaml as numbers,
+/// therefore numbers are accepted as well as strings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FrontmatterUid {
+    Text(String),
+    Number(u64),
+}
+
+impl FrontmatterUid {
+    /// Returns the uid as string. Empty uids are treated as not existing.
+    pub fn to_uid_string(&self) -> Option<String> {
+        let uid = match self {
+            FrontmatterUid::Text(text) => text.trim().to_owned(),
+            FrontmatterUid::Number(number) => number.to_string(),
+        };
+
+        (!uid.is_empty()).then_some(uid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FrontmatterUid;
+
+    #[test]
+    fn test_to_uid_string_from_text() {
+        let dut: FrontmatterUid = serde_yaml::from_str("\" 01HCZ \"").unwrap();
+        assert_eq!(dut.to_uid_string(), Some("01HCZ".into()));
+    }
+
+    #[test]
+    fn test_to_uid_string_from_number() {
+        let dut: FrontmatterUid = serde_yaml::from_str("202310181530").unwrap();
+        assert_eq!(dut.to_uid_string(), Some("202310181530".into()));
+    }
+
+    #[test]
+    fn test_to_uid_string_from_empty_text() {
+        let dut = FrontmatterUid::Text("  ".into());
+        assert_eq!(dut.to_uid_string(), None);
+    }
+}
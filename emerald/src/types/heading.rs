@@ -0,0 +1,138 @@
+/// A heading of a markdown document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heading {
+    /// Level of the heading ranging from 1 to 6.
+    pub level: u8,
+
+    /// Text of the heading without any markdown syntax around it.
+    pub text: String,
+
+    /// Byte range (start, end) of the heading within the content of the note.
+    pub span: (usize, usize),
+}
+
+impl Heading {
+    pub fn new(level: u8, text: String, span: (usize, usize)) -> Self {
+        Self { level, text, span }
+    }
+
+    /// Creates a heading from the markdown of an ATX (`## Heading`) or a
+    /// setext (`Heading` underlined by `===` or `---`) heading.
+    ///
+    /// # Arguments
+    ///
+    /// * `md`: Markdown of the heading as found in the content.
+    /// * `start`: Byte position of the heading within the content.
+    pub fn from_md(md: &str, start: usize) -> Self {
+        let span = (start, start + md.len());
+
+        // setext headings consist of the text line and the underline
+        if let Some((text, underline)) = md.split_once('\n') {
+            let level = if underline.trim_start().starts_with('=') {
+                1
+            } else {
+                2
+            };
+            return Self::new(level, text.trim().to_owned(), span);
+        }
+
+        let text = md.trim_start_matches('#');
+        let level = (md.len() - text.len()) as u8;
+
+        // remove the optional closing sequence
+        let text = text.trim();
+        let without_closing = text.trim_end_matches('#');
+        let text = if without_closing.is_empty() || without_closing.ends_with([' ', '\t']) {
+            without_closing.trim_end()
+        } else {
+            text
+        };
+
+        Self::new(level, text.to_owned(), span)
+    }
+
+    /// Returns true if a link section (as in `[[note#Section]]`) refers to this heading.
+    ///
+    /// The section matches if it equals the heading text ignoring the case or if it
+    /// equals the slug of the heading (`my-heading` for `My Heading`) as used by
+    /// standard markdown links.
+    pub fn matches_section(&self, section: &str) -> bool {
+        let section = section.trim().to_lowercase();
+        if self.text.to_lowercase() == section {
+            return true;
+        }
+
+        let slug: String = self
+            .text
+            .to_lowercase()
+            .chars()
+            .filter_map(|ch| match ch {
+                ' ' => Some('-'),
+                '-' | '_' => Some(ch),
+                _ if ch.is_alphanumeric() => Some(ch),
+                _ => None,
+            })
+            .collect();
+        slug == section
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Heading;
+
+    #[test]
+    fn test_from_md_atx_heading() {
+        let dut = Heading::from_md("## Heading", 5);
+        assert_eq!(dut, Heading::new(2, "Heading".into(), (5, 15)));
+    }
+
+    #[test]
+    fn test_from_md_atx_heading_with_closing_sequence() {
+        let dut = Heading::from_md("# Heading ##", 0);
+        assert_eq!(dut.text, "Heading");
+    }
+
+    #[test]
+    fn test_from_md_atx_heading_with_hash_at_end_of_word() {
+        let dut = Heading::from_md("# C#", 0);
+        assert_eq!(dut.text, "C#");
+    }
+
+    #[test]
+    fn test_from_md_empty_atx_heading() {
+        let dut = Heading::from_md("###", 0);
+        assert_eq!(dut, Heading::new(3, "".into(), (0, 3)));
+    }
+
+    #[test]
+    fn test_matches_section_with_text_ignoring_case() {
+        let dut = Heading::new(2, "My Heading".into(), (0, 0));
+        assert!(dut.matches_section("my heading"));
+    }
+
+    #[test]
+    fn test_matches_section_with_slug() {
+        let dut = Heading::new(2, "What's new? (2024)".into(), (0, 0));
+        assert!(dut.matches_section("whats-new-2024"));
+    }
+
+    #[test]
+    fn test_matches_section_with_other_text() {
+        let dut = Heading::new(2, "My Heading".into(), (0, 0));
+        assert!(!dut.matches_section("My Other Heading"));
+    }
+
+    #[test]
+    fn test_from_md_setext_heading_level_1() {
+        let dut = Heading::from_md("Heading\n===", 0);
+        assert_eq!(dut, Heading::new(1, "Heading".into(), (0, 11)));
+    }
+
+    #[test]
+    fn test_from_md_setext_heading_level_2() {
+        let dut = Heading::from_md(" Heading \r\n---", 0);
+        assert_eq!(dut.level, 2);
+        assert_eq!(dut.text, "Heading");
+    }
+}
@@ -1,5 +1,8 @@
 use super::link_comps::LinkComps;
+use super::md_link_comps::MdLinkComps;
+use super::ResourceId;
 use crate::error::{EmeraldError::*, Result};
+use crate::utils;
 
 #[derive(Debug, Clone, PartialEq, Hash)]
 pub struct Link(pub String);
@@ -7,12 +10,12 @@ pub struct Link(pub String);
 impl Link {
     #[inline]
     fn extract_part<'a>(&self, s: &'a str) -> (&'a str, Option<&'a str>) {
-        let end_idx = s.find(|c| c == '|' || c == '#' || c == '^');
+        let end_idx = s.find(['|', '#', '^']);
         if end_idx.is_none() {
             return (s, None);
         }
 
-        let end_idx = end_idx.map_or_else(|| s.len(), |x| x);
+        let end_idx = end_idx.unwrap_or(s.len());
 
         let front = &s[..end_idx];
         let back = &s[end_idx..];
@@ -82,6 +85,173 @@ impl Link {
 
         Ok(LinkComps::new(link, path, label, section, anchor))
     }
+
+    /// Splits a `Link` written as standard markdown link (`[label](target#section)`)
+    /// into its components.
+    pub fn split_md_link(&self) -> Result<MdLinkComps> {
+        let s = &self.0;
+
+        if !s.starts_with('[') || !s.ends_with(')') {
+            return Err(NotAMarkdownLink);
+        }
+
+        let mid = s.find("](").ok_or(NotAMarkdownLink)?;
+        let label = &s[1..mid];
+        let destination = s[(mid + 2)..(s.len() - 1)].trim();
+
+        // the destination is either enclosed in <...> or ends at the first whitespace.
+        // Everything afterwards is an optional title.
+        let destination = if let Some(rest) = destination.strip_prefix('<') {
+            let end = rest.find('>').ok_or(NotAMarkdownLink)?;
+            &rest[..end]
+        } else {
+            destination
+                .split_once(char::is_whitespace)
+                .map_or(destination, |(dest, _)| dest)
+        };
+
+        let (target, section) = match destination.split_once('#') {
+            Some((target, section)) => (target, Some(utils::percent_decode(section))),
+            None => (destination, None),
+        };
+
+        Ok(MdLinkComps::new(
+            label.to_owned(),
+            utils::percent_decode(target),
+            section,
+        ))
+    }
+}
+
+/// Splits a resource id into its directory and its file name.
+fn split_resource_id(rid: &ResourceId) -> (&str, &str) {
+    rid.0.rsplit_once('/').unwrap_or(("", &rid.0))
+}
+
+/// Removes the `.md` extension of a name or a path if there is one.
+fn strip_md_ext(name: &str) -> &str {
+    if name.to_lowercase().ends_with(".md") {
+        &name[..name.len() - 3]
+    } else {
+        name
+    }
+}
+
+/// Returns the path of `to` relative to the directory `from_dir`.
+fn relative_path(from_dir: &str, to: &str) -> String {
+    let from: Vec<&str> = from_dir.split('/').filter(|f| !f.is_empty()).collect();
+    let to: Vec<&str> = to.split('/').collect();
+    let common = from
+        .iter()
+        .zip(&to[..to.len() - 1])
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut parts = vec![".."; from.len() - common];
+    parts.extend(&to[common..]);
+    parts.join("/")
+}
+
+impl Link {
+    /// Returns the wiki link pointing to `tgt` instead of its current target.
+    ///
+    /// Label, section and anchor are kept. A bare link stays bare while a link
+    /// written with a path gets the full path of `tgt`. The `.md` extension is
+    /// only written if the link contained it before.
+    pub fn retarget_wiki_link(&self, tgt: &ResourceId) -> Result<Link> {
+        let inner = self
+            .0
+            .strip_prefix("[[")
+            .and_then(|f| f.strip_suffix("]]"))
+            .ok_or(NotAWikiLink)?;
+        let (target, rest) = self.extract_part(inner);
+
+        let (tgt_dir, tgt_name) = split_resource_id(tgt);
+        let name = if target.trim_end().to_lowercase().ends_with(".md") {
+            tgt_name
+        } else {
+            strip_md_ext(tgt_name)
+        };
+
+        let new_target = if !target.contains('/') {
+            name.to_owned()
+        } else {
+            let root = if target.starts_with('/') { "/" } else { "" };
+            if tgt_dir.is_empty() {
+                format!("{}{}", root, name)
+            } else {
+                format!("{}{}/{}", root, tgt_dir, name)
+            }
+        };
+
+        Ok(Link(format!("[[{}{}]]", new_target, rest.unwrap_or(""))))
+    }
+
+    /// Returns the markdown link pointing to `tgt` instead of its current target.
+    ///
+    /// Label, section and title are kept. The new target is relative to `src` unless
+    /// the link was relative to the vault root before. Destinations enclosed in
+    /// `<...>` stay enclosed, all others are percent encoded. The `.md` extension
+    /// is only written if the link contained it before.
+    ///
+    /// # Arguments
+    ///
+    /// * `src`: Note which contains the link.
+    /// * `tgt`: New target of the link.
+    pub fn retarget_md_link(&self, src: &ResourceId, tgt: &ResourceId) -> Result<Link> {
+        let s = &self.0;
+        if !s.starts_with('[') || !s.ends_with(')') {
+            return Err(NotAMarkdownLink);
+        }
+
+        let dest_start = s.find("](").ok_or(NotAMarkdownLink)? + 2;
+        let inner = &s[dest_start..(s.len() - 1)];
+        let (leading, inner) = inner.split_at(inner.len() - inner.trim_start().len());
+
+        // the destination is either enclosed in <...> or ends at the first whitespace
+        let (dest, trailing, is_enclosed) = if let Some(rest) = inner.strip_prefix('<') {
+            let end = rest.find('>').ok_or(NotAMarkdownLink)?;
+            (&rest[..end], &rest[(end + 1)..], true)
+        } else {
+            let end = inner.find(char::is_whitespace).unwrap_or(inner.len());
+            (&inner[..end], &inner[end..], false)
+        };
+        let (target, section) = dest.split_at(dest.find('#').unwrap_or(dest.len()));
+
+        let tgt_path = if utils::percent_decode(target)
+            .to_lowercase()
+            .ends_with(".md")
+        {
+            tgt.0.as_ref()
+        } else {
+            strip_md_ext(&tgt.0)
+        };
+
+        let new_target = if target.starts_with('/') {
+            format!("/{}", tgt_path)
+        } else {
+            let rel_path = relative_path(split_resource_id(src).0, tgt_path);
+            if target.starts_with("./") && !rel_path.starts_with("../") {
+                format!("./{}", rel_path)
+            } else {
+                rel_path
+            }
+        };
+
+        let new_dest = if is_enclosed {
+            format!("<{}{}>", new_target, section)
+        } else {
+            format!("{}{}", utils::percent_encode(&new_target), section)
+        };
+
+        Ok(Link(format!(
+            "{}{}{}{})",
+            &s[..dest_start],
+            leading,
+            new_dest,
+            trailing
+        )))
+    }
 }
 
 // ALlows to use a string as a link
@@ -105,6 +275,7 @@ impl From<LinkComps> for Link {
 impl Eq for Link {}
 
 #[cfg(test)]
+#[allow(clippy::bool_comparison)]
 mod tests {
     use super::Link;
 
@@ -222,6 +393,56 @@ mod tests {
         assert_eq!(section, "");
     }
 
+    #[test]
+    fn test_md_link_split_target() {
+        let test_link: Link = "[label](folder/note.md)".into();
+        let res = test_link.split_md_link().unwrap();
+        assert_eq!(res.target, "folder/note.md");
+    }
+
+    #[test]
+    fn test_md_link_split_label() {
+        let test_link: Link = "[label](folder/note.md)".into();
+        let res = test_link.split_md_link().unwrap();
+        assert_eq!(res.label, "label");
+    }
+
+    #[test]
+    fn test_md_link_split_percent_encoded_target() {
+        let test_link: Link = "[label](My%20Note.md)".into();
+        let res = test_link.split_md_link().unwrap();
+        assert_eq!(res.target, "My Note.md");
+    }
+
+    #[test]
+    fn test_md_link_split_section() {
+        let test_link: Link = "[label](note.md#My%20Heading)".into();
+        let res = test_link.split_md_link().unwrap();
+        assert_eq!(res.target, "note.md");
+        assert_eq!(res.section.unwrap(), "My Heading");
+    }
+
+    #[test]
+    fn test_md_link_split_with_title() {
+        let test_link: Link = "[label](note.md \"Title\")".into();
+        let res = test_link.split_md_link().unwrap();
+        assert_eq!(res.target, "note.md");
+    }
+
+    #[test]
+    fn test_md_link_split_with_angle_brackets() {
+        let test_link: Link = "[label](<My Note.md>)".into();
+        let res = test_link.split_md_link().unwrap();
+        assert_eq!(res.target, "My Note.md");
+    }
+
+    #[test]
+    fn test_md_link_split_wiki_link_fails() {
+        let test_link: Link = "[[note]]".into();
+        let res = test_link.split_md_link();
+        assert!(res.is_err());
+    }
+
     #[test]
     fn test_link_with_leading_undescore() {
         let test_link: Link = "[[_test_link]]".into();
@@ -229,4 +450,53 @@ mod tests {
         let link_components = res.unwrap();
         assert!(link_components.name == "_test_link");
     }
+
+    #[test]
+    fn test_retarget_bare_wiki_link_keeps_label_and_section() {
+        let link: Link = "[[old#Section|Label]]".into();
+        let res = link.retarget_wiki_link(&"dir/new.md".into()).unwrap();
+        assert_eq!(res, "[[new#Section|Label]]".into());
+    }
+
+    #[test]
+    fn test_retarget_wiki_link_keeps_extension_and_anchor() {
+        let link: Link = "[[old.md^block]]".into();
+        let res = link.retarget_wiki_link(&"new.md".into()).unwrap();
+        assert_eq!(res, "[[new.md^block]]".into());
+    }
+
+    #[test]
+    fn test_retarget_wiki_link_with_path() {
+        let link: Link = "[[sub/old]]".into();
+        let res = link.retarget_wiki_link(&"other dir/new.md".into()).unwrap();
+        assert_eq!(res, "[[other dir/new]]".into());
+
+        let link: Link = "[[/sub/old]]".into();
+        let res = link.retarget_wiki_link(&"new.md".into()).unwrap();
+        assert_eq!(res, "[[/new]]".into());
+    }
+
+    #[test]
+    fn test_retarget_md_link_relative_to_source() {
+        let link: Link = "[Label](../old.md#Section \"Title\")".into();
+        let res = link
+            .retarget_md_link(&"a/src.md".into(), &"b/new note.md".into())
+            .unwrap();
+        assert_eq!(res, "[Label](../b/new%20note.md#Section \"Title\")".into());
+    }
+
+    #[test]
+    fn test_retarget_md_link_keeps_style() {
+        let link: Link = "[Label](<./old>)".into();
+        let res = link
+            .retarget_md_link(&"a/src.md".into(), &"a/new note.md".into())
+            .unwrap();
+        assert_eq!(res, "[Label](<./new note>)".into());
+
+        let link: Link = "[Label](/a/old.md)".into();
+        let res = link
+            .retarget_md_link(&"a/src.md".into(), &"b/new.md".into())
+            .unwrap();
+        assert_eq!(res, "[Label](/b/new.md)".into());
+    }
 }
@@ -1,15 +1,33 @@
-use super::{Link, ResourceId};
+use super::{Link, LinkKind, ResourceId};
 
 #[derive(Debug, Clone, PartialEq)]
 // Structs holds a link and the resource id of the links target if existant.
 pub struct Link2Tgt {
     pub link: Link,
     pub tgt: Option<ResourceId>,
+    pub kind: LinkKind,
+
+    /// True if the target exists but doesn't contain the section or block the link
+    /// points to.
+    pub anchor_missing: bool,
 }
 
 impl Link2Tgt {
-    pub fn new(link: Link, tgt: Option<ResourceId>) -> Self {
-        Self { link, tgt }
+    pub fn new(link: Link, tgt: Option<ResourceId>, kind: LinkKind) -> Self {
+        Self {
+            link,
+            tgt,
+            kind,
+            anchor_missing: false,
+        }
+    }
+
+    /// Marks the section or block the link points to as missing in the target.
+    pub fn with_missing_anchor(self) -> Self {
+        Self {
+            anchor_missing: true,
+            ..self
+        }
     }
     /*
     pub fn new_without_target(link: Link) -> Self {
@@ -1,14 +1,15 @@
-use super::{Link, ResourceId};
+use super::{Link, LinkKind, ResourceId};
 
 #[derive(Debug, Clone, PartialEq)]
 // Structs holds a link and the resource id from which the links stems.
 pub struct LinkFrmSrc {
     pub link: Link,
     pub src: ResourceId,
+    pub kind: LinkKind,
 }
 
 impl LinkFrmSrc {
-    pub fn new(link: Link, src: ResourceId) -> Self {
-        Self { src, link }
+    pub fn new(link: Link, src: ResourceId, kind: LinkKind) -> Self {
+        Self { src, link, kind }
     }
 }
@@ -0,0 +1,32 @@
+/// Describes the markdown syntax a link was written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LinkKind {
+    /// A wiki link like `[[note]]`.
+    WikiLink,
+
+    /// A standard markdown link like `[text](note.md)`.
+    MdLink,
+
+    /// A wiki link embedding its target like `![[note]]`.
+    WikiEmbed,
+
+    /// A standard markdown link embedding its target like `![alt](image.png)`.
+    MdEmbed,
+}
+
+impl LinkKind {
+    /// Returns true if the target is embedded (transcluded) instead of referenced.
+    pub fn is_embed(&self) -> bool {
+        matches!(self, LinkKind::WikiEmbed | LinkKind::MdEmbed)
+    }
+
+    /// Returns the name of the kind as used in reports and exports.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LinkKind::WikiLink => "wiki_link",
+            LinkKind::MdLink => "md_link",
+            LinkKind::WikiEmbed => "wiki_embed",
+            LinkKind::MdEmbed => "md_embed",
+        }
+    }
+}
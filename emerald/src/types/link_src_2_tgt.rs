@@ -1,4 +1,4 @@
-use super::{Link, Link2Tgt, LinkFrmSrc, ResourceId};
+use super::{Link, Link2Tgt, LinkFrmSrc, LinkKind, ResourceId};
 
 #[derive(Debug, Clone)]
 /// This struct holds the source of a link and its target (the place where it points to).
@@ -8,23 +8,37 @@ pub struct LinkSrc2Tgt {
     pub src: ResourceId,
     pub link: Link,
     pub tgt: Option<ResourceId>,
+    pub kind: LinkKind,
+    pub anchor_missing: bool,
 }
 
 impl LinkSrc2Tgt {
-    pub fn new(src: ResourceId, link: Link, tgt: Option<ResourceId>) -> Self {
-        Self { src, link, tgt }
+    pub fn new(src: ResourceId, link: Link, tgt: Option<ResourceId>, kind: LinkKind) -> Self {
+        Self {
+            src,
+            link,
+            tgt,
+            kind,
+            anchor_missing: false,
+        }
     }
 
     pub fn from_link_to_target(src: ResourceId, link_2_tgt: Link2Tgt) -> Self {
-        Self::new(src, link_2_tgt.link, link_2_tgt.tgt)
+        Self {
+            anchor_missing: link_2_tgt.anchor_missing,
+            ..Self::new(src, link_2_tgt.link, link_2_tgt.tgt, link_2_tgt.kind)
+        }
     }
 
     pub fn get_link_to_target(&self) -> Link2Tgt {
-        Link2Tgt::new(self.link.clone(), self.tgt.clone())
+        Link2Tgt {
+            anchor_missing: self.anchor_missing,
+            ..Link2Tgt::new(self.link.clone(), self.tgt.clone(), self.kind)
+        }
     }
 
     pub fn get_link_from_source(&self) -> LinkFrmSrc {
-        LinkFrmSrc::new(self.link.clone(), self.src.clone())
+        LinkFrmSrc::new(self.link.clone(), self.src.clone(), self.kind)
     }
 }
 
@@ -32,6 +46,11 @@ impl LinkSrc2Tgt {
 /// (source, link, target)
 impl From<(&str, &str, &str)> for LinkSrc2Tgt {
     fn from(value: (&str, &str, &str)) -> Self {
-        LinkSrc2Tgt::new(value.0.into(), value.1.into(), Some(value.2.into()))
+        LinkSrc2Tgt::new(
+            value.0.into(),
+            value.1.into(),
+            Some(value.2.into()),
+            LinkKind::WikiLink,
+        )
     }
 }
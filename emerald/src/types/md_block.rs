@@ -2,6 +2,25 @@
 pub enum MdBlock<'a> {
     WikiLink(&'a str),
     Link(&'a str),
+    Embed(&'a str),
+    Heading(&'a str),
+    BlockId(&'a str),
+    Tag(&'a str),
+
+    /// A task list item like `- [ ] task 📅 2026-10-20`.
+    Task {
+        /// True if the task is done (`[x]`).
+        checked: bool,
+
+        /// Text of the task following the checkbox.
+        text: &'a str,
+
+        /// Line number (starting with 1) of the task within the markdown.
+        line: usize,
+
+        /// Due date (`YYYY-MM-DD`) given as `📅 2026-10-20` or `due:: 2026-10-20`.
+        due: Option<&'a str>,
+    },
     CodeBlock(&'a str),
     YamlFrontmatter(&'a str),
 }
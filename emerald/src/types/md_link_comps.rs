@@ -0,0 +1,166 @@
+use super::link_comps::LinkComps;
+use super::ResourceId;
+use crate::error::{EmeraldError::*, Result};
+
+/// Components of a standard markdown link `[label](target#section)`.
+///
+/// The `target` and the `section` are already percent decoded.
+#[derive(Debug, PartialEq)]
+pub struct MdLinkComps {
+    pub label: String,
+    pub target: String,
+    pub section: Option<String>,
+}
+
+impl MdLinkComps {
+    pub fn new(label: String, target: String, section: Option<String>) -> Self {
+        Self {
+            label,
+            target,
+            section,
+        }
+    }
+
+    /// Returns true if the target is an url with a scheme (`https://`, `mailto:`, ...)
+    /// and therefore doesn't point to a resource inside the vault.
+    pub fn is_external(&self) -> bool {
+        let Some((scheme, _)) = self.target.split_once(':') else {
+            return false;
+        };
+
+        let mut chars = scheme.chars();
+        let Some(first) = chars.next() else {
+            return false;
+        };
+
+        first.is_ascii_alphabetic()
+            && chars.all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '+' | '-' | '.'))
+    }
+
+    /// Converts the markdown link into the components of an equivalent wiki link.
+    ///
+    /// Markdown links are relative to the note they are contained in. The target is
+    /// therefore resolved against the directory of `src` and the resulting path is
+    /// relative to the vault root. Targets starting with `/` are treated as relative
+    /// to the vault root. A link consisting only of a section points to `src` itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `src`: Resource id of the note which contains the link.
+    pub fn to_link_comps(&self, src: &ResourceId) -> Result<LinkComps> {
+        let is_sep = |ch: char| ch == '/' || ch == '\\';
+
+        let mut parts: Vec<&str> = if self.target.is_empty() {
+            // link into the same note
+            src.0.split(is_sep).collect()
+        } else if self.target.starts_with(is_sep) {
+            vec![]
+        } else {
+            // start from the directory of the source
+            let mut src_parts: Vec<&str> = src.0.split(is_sep).collect();
+            src_parts.pop();
+            src_parts
+        };
+
+        if !self.target.is_empty() {
+            for part in self.target.split(is_sep) {
+                match part {
+                    "" | "." => (),
+                    ".." => {
+                        if parts.pop().is_none() {
+                            // the link points outside of the vault
+                            return Err(LinkNotFound(self.target.clone()));
+                        }
+                    }
+                    _ => parts.push(part),
+                }
+            }
+        }
+
+        let name = parts.pop().ok_or(LinkNotFound(self.target.clone()))?;
+
+        Ok(LinkComps::new(
+            name.to_owned(),
+            Some(parts.join("/")),
+            None,
+            self.section.clone(),
+            None,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MdLinkComps;
+
+    fn create_dut(target: &str, section: Option<&str>) -> MdLinkComps {
+        MdLinkComps::new("label".into(), target.into(), section.map(|f| f.into()))
+    }
+
+    #[test]
+    fn test_is_external_with_https() {
+        let dut = create_dut("https://example.com/note.md", None);
+        assert!(dut.is_external());
+    }
+
+    #[test]
+    fn test_is_external_with_mailto() {
+        let dut = create_dut("mailto:someone@example.com", None);
+        assert!(dut.is_external());
+    }
+
+    #[test]
+    fn test_is_external_with_relative_path() {
+        let dut = create_dut("../note.md", None);
+        assert!(!dut.is_external());
+    }
+
+    #[test]
+    fn test_to_link_comps_from_root_note() {
+        let dut = create_dut("note.md", None);
+        let res = dut.to_link_comps(&"root.md".into()).unwrap();
+        assert_eq!(res.to_string(), "[[/note.md]]");
+    }
+
+    #[test]
+    fn test_to_link_comps_from_sub_folder() {
+        let dut = create_dut("note.md", None);
+        let res = dut.to_link_comps(&"a/b/src.md".into()).unwrap();
+        assert_eq!(res.to_string(), "[[a/b/note.md]]");
+    }
+
+    #[test]
+    fn test_to_link_comps_with_dot_segments() {
+        let dut = create_dut("./../c/./note.md", None);
+        let res = dut.to_link_comps(&"a/b/src.md".into()).unwrap();
+        assert_eq!(res.to_string(), "[[a/c/note.md]]");
+    }
+
+    #[test]
+    fn test_to_link_comps_with_vault_absolute_path() {
+        let dut = create_dut("/c/note.md", None);
+        let res = dut.to_link_comps(&"a/b/src.md".into()).unwrap();
+        assert_eq!(res.to_string(), "[[c/note.md]]");
+    }
+
+    #[test]
+    fn test_to_link_comps_with_section() {
+        let dut = create_dut("note.md", Some("Heading"));
+        let res = dut.to_link_comps(&"a/src.md".into()).unwrap();
+        assert_eq!(res.to_string(), "[[a/note.md#Heading]]");
+    }
+
+    #[test]
+    fn test_to_link_comps_with_section_only_points_to_source() {
+        let dut = create_dut("", Some("Heading"));
+        let res = dut.to_link_comps(&"a/src.md".into()).unwrap();
+        assert_eq!(res.to_string(), "[[a/src.md#Heading]]");
+    }
+
+    #[test]
+    fn test_to_link_comps_outside_of_vault_fails() {
+        let dut = create_dut("../../note.md", None);
+        let res = dut.to_link_comps(&"a/src.md".into());
+        assert!(res.is_err());
+    }
+}
@@ -0,0 +1,21 @@
+use super::ResourceId;
+
+/// An occurrence of the title or an alias of a note in the text of another note
+/// which isn't part of a link.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mention {
+    /// Note containing the mention.
+    pub src: ResourceId,
+
+    /// Mentioned title or alias as written in the note.
+    pub text: String,
+
+    /// Line of the mention starting with 1.
+    pub line: usize,
+
+    /// Column of the mention in chars starting with 1.
+    pub column: usize,
+
+    /// Byte range (start, end) of the mention within the content of the note.
+    pub span: (usize, usize),
+}
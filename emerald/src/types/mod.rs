@@ -1,23 +1,50 @@
+mod alias_conflict;
+mod broken_link;
 mod content;
+mod diagnostic;
 mod document_metadata;
 mod filesystem_metadata;
+mod frontmatter_tags;
+mod frontmatter_uid;
+mod heading;
 mod link;
 mod link_2_tgt;
 mod link_comps;
 mod link_frm_src;
+mod link_kind;
 mod link_src_2_tgt;
 mod md_block;
+mod md_link_comps;
+mod mention;
+mod outline_node;
 mod resource_id;
 mod resource_type;
+mod task;
+mod task_filter;
+mod vault_changes;
 
+pub use self::alias_conflict::AliasConflict;
+pub use self::broken_link::BrokenLink;
 pub use self::content::Content;
+pub use self::diagnostic::Diagnostic;
+pub use self::diagnostic::DiagnosticKind;
 pub use self::document_metadata::DocumentMetadata;
 pub use self::filesystem_metadata::FilesystemMetadata;
 pub use self::filesystem_metadata::FilesystemMetadataBuilder;
+pub use self::frontmatter_tags::FrontmatterTags;
+pub use self::frontmatter_uid::FrontmatterUid;
+pub use self::heading::Heading;
 pub use self::link::Link;
 pub use self::link_2_tgt::Link2Tgt;
+pub use self::link_comps::LinkComps;
 pub use self::link_frm_src::LinkFrmSrc;
+pub use self::link_kind::LinkKind;
 pub use self::link_src_2_tgt::LinkSrc2Tgt;
 pub use self::md_block::MdBlock;
+pub use self::mention::Mention;
+pub use self::outline_node::OutlineNode;
 pub use self::resource_id::ResourceId;
 pub use self::resource_type::ResourceType;
+pub use self::task::Task;
+pub use self::task_filter::TaskFilter;
+pub use self::vault_changes::VaultChanges;
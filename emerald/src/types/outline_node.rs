@@ -0,0 +1,100 @@
+use super::heading::Heading;
+
+/// A node of the outline of a note.
+///
+/// Every heading becomes a node. All following headings with a higher level are
+/// children of this node until a heading of the same or a lower level is found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutlineNode {
+    pub heading: Heading,
+    pub children: Vec<OutlineNode>,
+}
+
+impl OutlineNode {
+    pub fn new(heading: Heading) -> Self {
+        Self {
+            heading,
+            children: vec![],
+        }
+    }
+
+    /// Creates the outline tree from headings in document order.
+    ///
+    /// Returns the top level nodes of the tree.
+    pub fn create_outline(headings: impl IntoIterator<Item = Heading>) -> Vec<OutlineNode> {
+        fn attach(stack: &mut [OutlineNode], roots: &mut Vec<OutlineNode>, node: OutlineNode) {
+            match stack.last_mut() {
+                Some(parent) => parent.children.push(node),
+                None => roots.push(node),
+            }
+        }
+
+        let mut roots = vec![];
+        let mut stack: Vec<OutlineNode> = vec![];
+        for heading in headings {
+            while stack
+                .last()
+                .is_some_and(|node| node.heading.level >= heading.level)
+            {
+                let node = stack.pop().unwrap();
+                attach(&mut stack, &mut roots, node);
+            }
+            stack.push(OutlineNode::new(heading));
+        }
+
+        while let Some(node) = stack.pop() {
+            attach(&mut stack, &mut roots, node);
+        }
+        roots
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OutlineNode;
+    use crate::types::Heading;
+
+    fn heading(level: u8, text: &str) -> Heading {
+        Heading::new(level, text.into(), (0, 0))
+    }
+
+    fn texts(nodes: &[OutlineNode]) -> Vec<&str> {
+        nodes.iter().map(|f| f.heading.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_create_outline_empty() {
+        let out = OutlineNode::create_outline(vec![]);
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn test_create_outline_nested() {
+        let out = OutlineNode::create_outline(vec![
+            heading(1, "a"),
+            heading(2, "a.1"),
+            heading(3, "a.1.1"),
+            heading(2, "a.2"),
+            heading(1, "b"),
+        ]);
+
+        assert_eq!(texts(&out), ["a", "b"]);
+        assert_eq!(texts(&out[0].children), ["a.1", "a.2"]);
+        assert_eq!(texts(&out[0].children[0].children), ["a.1.1"]);
+        assert!(out[1].children.is_empty());
+    }
+
+    #[test]
+    fn test_create_outline_skipped_levels() {
+        let out = OutlineNode::create_outline(vec![
+            heading(2, "a"),
+            heading(4, "a.1"),
+            heading(1, "b"),
+            heading(3, "b.1"),
+        ]);
+
+        assert_eq!(texts(&out), ["a", "b"]);
+        assert_eq!(texts(&out[0].children), ["a.1"]);
+        assert_eq!(texts(&out[1].children), ["b.1"]);
+    }
+}
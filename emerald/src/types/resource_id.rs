@@ -27,6 +27,13 @@ impl From<Box<str>> for ResourceId {
     }
 }
 
+/// Implements `AsRef<str>` for `ResourceId`, giving access to the path of the resource.
+impl AsRef<str> for ResourceId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
 /// Enables equality comparisons for `ResourceId`.
 ///
 /// `ResourceId` instances are considered equal if their inner boxed strings are equal.
@@ -5,6 +5,17 @@ pub enum ResourceType {
     NoType(), // No resource type available
 }
 
+impl ResourceType {
+    /// Determines the resource type from the extension of a file.
+    pub fn from_extension(ext: &str) -> Self {
+        match ext {
+            "md" => ResourceType::Markdown(),
+            "markdown" => ResourceType::Markdown(),
+            _ => ResourceType::Unknown(),
+        }
+    }
+}
+
 impl Default for ResourceType {
     fn default() -> Self {
         Self::NoType()
@@ -0,0 +1,36 @@
+/// A task list item of a note.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Task {
+    /// Text of the task following the checkbox.
+    pub text: String,
+
+    /// True if the task is done.
+    pub checked: bool,
+
+    /// Line number (starting with 1) of the task within the note.
+    pub line: usize,
+
+    /// Due date formatted as `YYYY-MM-DD`.
+    pub due: Option<String>,
+
+    /// Inline tags of the task without their leading `#`.
+    pub tags: Vec<String>,
+}
+
+impl Task {
+    pub fn new(
+        text: String,
+        checked: bool,
+        line: usize,
+        due: Option<String>,
+        tags: Vec<String>,
+    ) -> Self {
+        Self {
+            text,
+            checked,
+            line,
+            due,
+            tags,
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use super::Task;
+
+/// Criteria to select tasks. Unset criteria match every task.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TaskFilter {
+    /// Only tasks with this checked state.
+    pub checked: Option<bool>,
+
+    /// Only tasks due before (excluding) this date given as `YYYY-MM-DD`.
+    pub due_before: Option<String>,
+
+    /// Only tasks with this tag or one of its child tags. The frontmatter tags of
+    /// the note which contains the task are taken into account as well.
+    pub tag: Option<String>,
+}
+
+impl TaskFilter {
+    /// Returns true if the task matches all criteria.
+    ///
+    /// # Arguments
+    ///
+    /// * `task`: Task to check.
+    /// * `note_tags`: Frontmatter tags of the note which contains the task.
+    pub fn matches(&self, task: &Task, note_tags: &[String]) -> bool {
+        if self.checked.is_some_and(|checked| checked != task.checked) {
+            return false;
+        }
+
+        if let Some(due_before) = &self.due_before {
+            // dates formatted as YYYY-MM-DD can be compared as strings
+            if task.due.as_ref().is_none_or(|due| due >= due_before) {
+                return false;
+            }
+        }
+
+        if let Some(tag) = &self.tag {
+            let tag = tag.trim_start_matches('#').to_lowercase();
+            let child_prefix = format!("{}/", tag);
+            let has_tag = task.tags.iter().chain(note_tags.iter()).any(|f| {
+                let f = f.to_lowercase();
+                f == tag || f.starts_with(&child_prefix)
+            });
+            if !has_tag {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TaskFilter;
+    use crate::types::Task;
+
+    fn create_task(checked: bool, due: Option<&str>, tags: &[&str]) -> Task {
+        Task::new(
+            "task".into(),
+            checked,
+            1,
+            due.map(|f| f.into()),
+            tags.iter().map(|f| f.to_string()).collect(),
+        )
+    }
+
+    #[test]
+    fn test_matches_with_default_filter() {
+        let dut = TaskFilter::default();
+        assert!(dut.matches(&create_task(true, None, &[]), &[]));
+    }
+
+    #[test]
+    fn test_matches_open_tasks() {
+        let dut = TaskFilter {
+            checked: Some(false),
+            ..Default::default()
+        };
+        assert!(dut.matches(&create_task(false, None, &[]), &[]));
+        assert!(!dut.matches(&create_task(true, None, &[]), &[]));
+    }
+
+    #[test]
+    fn test_matches_due_before() {
+        let dut = TaskFilter {
+            due_before: Some("2026-10-20".into()),
+            ..Default::default()
+        };
+        assert!(dut.matches(&create_task(false, Some("2026-10-19"), &[]), &[]));
+        assert!(!dut.matches(&create_task(false, Some("2026-10-20"), &[]), &[]));
+        assert!(!dut.matches(&create_task(false, None, &[]), &[]));
+    }
+
+    #[test]
+    fn test_matches_tag_of_task() {
+        let dut = TaskFilter {
+            tag: Some("#Work".into()),
+            ..Default::default()
+        };
+        assert!(dut.matches(&create_task(false, None, &["work/calls"]), &[]));
+        assert!(!dut.matches(&create_task(false, None, &["workshop"]), &[]));
+    }
+
+    #[test]
+    fn test_matches_tag_of_note() {
+        let dut = TaskFilter {
+            tag: Some("work".into()),
+            ..Default::default()
+        };
+        assert!(dut.matches(&create_task(false, None, &[]), &["work".into()]));
+    }
+}
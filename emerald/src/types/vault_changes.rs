@@ -0,0 +1,25 @@
+use super::ResourceId;
+
+/// Changes which were found while refreshing a vault.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VaultChanges {
+    /// Resources which were created since the last load.
+    pub added: Vec<ResourceId>,
+
+    /// Resources whose size or modification time changed.
+    pub modified: Vec<ResourceId>,
+
+    /// Resources which were deleted.
+    pub removed: Vec<ResourceId>,
+
+    /// Notes whose outgoing links changed, including links which now resolve to
+    /// another target.
+    pub links_changed: Vec<ResourceId>,
+}
+
+impl VaultChanges {
+    /// Returns true if no resource was added, modified or removed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.modified.is_empty() && self.removed.is_empty()
+    }
+}
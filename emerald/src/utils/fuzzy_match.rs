@@ -0,0 +1,108 @@
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Folds a string for a loose comparison. The string is lowercased and all
+/// diacritics are removed, e.g. `Café` becomes `cafe`.
+pub fn fold_str(inp: &str) -> String {
+    inp.nfkd()
+        .filter(|ch| !is_combining_mark(*ch))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Returns the levenshtein distance of two strings counted in chars.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ch_a) in a.chars().enumerate() {
+        let mut diag = row[0];
+        row[0] = i + 1;
+        for (j, ch_b) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ch_a == *ch_b {
+                diag
+            } else {
+                1 + diag.min(above).min(row[j])
+            };
+            diag = above;
+        }
+    }
+    row[b.len()]
+}
+
+/// Returns the candidates closest to the given name, best match first.
+///
+/// Name and candidates are compared after folding them. Candidates which are
+/// equal after folding come first, followed by candidates within an edit
+/// distance of a third of the name length (at least 1).
+///
+/// # Arguments
+///
+/// * `name`: Name to find matches for.
+/// * `candidates`: Names to choose from.
+/// * `max_count`: Maximum number of returned candidates.
+pub fn closest_matches<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+    max_count: usize,
+) -> Vec<&'a str> {
+    let folded_name = fold_str(name);
+    let max_distance = (folded_name.chars().count() / 3).max(1);
+
+    let mut matches: Vec<(usize, &str)> = candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(&folded_name, &fold_str(candidate)), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect();
+    matches.sort();
+    matches.dedup_by(|a, b| a.1 == b.1);
+
+    matches
+        .into_iter()
+        .take(max_count)
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{closest_matches, edit_distance, fold_str};
+
+    #[test]
+    fn test_fold_str() {
+        assert_eq!(fold_str("Café Ünïcode"), "cafe unicode");
+    }
+
+    #[test]
+    fn test_fold_str_of_decomposed_umlaut() {
+        // Attention: The "ö" is in NFD representation
+        assert_eq!(fold_str("Nöte"), "note");
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("abc", "abc"), 0);
+        assert_eq!(edit_distance("äb", "ab"), 1);
+    }
+
+    #[test]
+    fn test_closest_matches_prefers_folded_match() {
+        let candidates = ["Notes", "Nöte", "Other"];
+        assert_eq!(closest_matches("note", candidates, 3), ["Nöte", "Notes"]);
+    }
+
+    #[test]
+    fn test_closest_matches_skips_distant_candidates() {
+        let candidates = ["completely different"];
+        assert!(closest_matches("note", candidates, 3).is_empty());
+    }
+
+    #[test]
+    fn test_closest_matches_limits_count() {
+        let candidates = ["note1", "note2", "note3"];
+        assert_eq!(closest_matches("note", candidates, 2), ["note1", "note2"]);
+    }
+}
@@ -1,4 +1,9 @@
+mod fuzzy_match;
 mod normalize_string;
+mod percent_decode;
 
+pub use fuzzy_match::closest_matches;
 pub use normalize_string::normalize_str;
 pub use normalize_string::normalize_str_iter;
+pub use percent_decode::percent_decode;
+pub use percent_decode::percent_encode;
@@ -0,0 +1,79 @@
+/// Decodes percent-encoded characters (`%20`, `%C3%A4`, ...) of an url path.
+///
+/// Invalid escape sequences are kept as they are. Byte sequences which do not
+/// form valid utf-8 are replaced by the unicode replacement character.
+pub fn percent_decode(inp: &str) -> String {
+    let bytes = inp.as_bytes();
+    let mut out = Vec::<u8>::with_capacity(bytes.len());
+    let mut idx = 0;
+
+    while idx < bytes.len() {
+        if bytes[idx] == b'%' && idx + 2 < bytes.len() {
+            let hi = (bytes[idx + 1] as char).to_digit(16);
+            let lo = (bytes[idx + 2] as char).to_digit(16);
+            if let (Some(hi), Some(lo)) = (hi, lo) {
+                out.push((hi * 16 + lo) as u8);
+                idx += 3;
+                continue;
+            }
+        }
+        out.push(bytes[idx]);
+        idx += 1;
+    }
+
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Percent encodes the characters of an url path which would end or break a
+/// markdown link destination.
+pub fn percent_encode(inp: &str) -> String {
+    let mut out = String::with_capacity(inp.len());
+    for ch in inp.chars() {
+        match ch {
+            '%' => out.push_str("%25"),
+            ' ' => out.push_str("%20"),
+            '(' => out.push_str("%28"),
+            ')' => out.push_str("%29"),
+            '<' => out.push_str("%3C"),
+            '>' => out.push_str("%3E"),
+            ch => out.push(ch),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{percent_decode, percent_encode};
+
+    #[test]
+    fn test_percent_encode_round_trip() {
+        let path = "folder (1)/100% note.md";
+        assert_eq!(percent_encode(path), "folder%20%281%29/100%25%20note.md");
+        assert_eq!(percent_decode(&percent_encode(path)), path);
+    }
+
+    #[test]
+    fn check_identity() {
+        let result = percent_decode("abc.md");
+        assert_eq!(result, "abc.md");
+    }
+
+    #[test]
+    fn check_space() {
+        let result = percent_decode("My%20Note.md");
+        assert_eq!(result, "My Note.md");
+    }
+
+    #[test]
+    fn check_multibyte_utf8() {
+        let result = percent_decode("N%C3%B6te.md");
+        assert_eq!(result, "Nöte.md");
+    }
+
+    #[test]
+    fn check_invalid_escape_is_kept() {
+        let result = percent_decode("100%zz%2");
+        assert_eq!(result, "100%zz%2");
+    }
+}
@@ -0,0 +1,180 @@
+use notify::event::{ModifyKind, RenameMode};
+use notify::{Event, EventKind};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+/// A burst ends at the latest after this many debounce delays, even if events
+/// keep coming in.
+const MAX_BURST_DELAYS: u32 = 10;
+
+/// Accesses are ignored since reloading the vault reads the files itself.
+fn is_relevant(event: &Event) -> bool {
+    !matches!(event.kind, EventKind::Access(_))
+}
+
+/// Paths touched by a burst of events.
+#[derive(Default)]
+struct Burst {
+    paths: Vec<PathBuf>,
+
+    /// Trackers of renames whose target wasn't seen yet.
+    pending_renames: HashSet<usize>,
+}
+
+impl Burst {
+    fn add(&mut self, event: notify::Result<Event>) {
+        let event = match event {
+            Ok(event) if is_relevant(&event) => event,
+            Ok(_) => return,
+            Err(err) => {
+                warn!("Watching the vault failed: {:?}", err);
+                return;
+            }
+        };
+        trace!("Watch event {:?}", &event);
+
+        match (event.kind, event.attrs.tracker()) {
+            (EventKind::Modify(ModifyKind::Name(RenameMode::From)), Some(tracker)) => {
+                self.pending_renames.insert(tracker);
+            }
+            (EventKind::Modify(ModifyKind::Name(_)), Some(tracker)) => {
+                self.pending_renames.remove(&tracker);
+            }
+            _ => (),
+        }
+        self.paths.extend(event.paths);
+    }
+}
+
+/// Waits for the next burst of file system events and returns the paths which
+/// were touched by it. A burst ends once no event arrived for `delay`. If only
+/// the first half of a rename was seen, the burst is extended once to wait for
+/// the second half. Returns None once the watcher was dropped.
+///
+/// # Arguments
+///
+/// * `rx`: Events of the file system watcher.
+/// * `delay`: Time without events which ends a burst.
+pub fn collect_burst(
+    rx: &Receiver<notify::Result<Event>>,
+    delay: Duration,
+) -> Option<Vec<PathBuf>> {
+    let mut burst = Burst::default();
+
+    // block until the burst starts
+    while burst.paths.is_empty() {
+        burst.add(rx.recv().ok()?);
+    }
+
+    let deadline = Instant::now() + delay * MAX_BURST_DELAYS;
+    let mut extended = false;
+    while Instant::now() < deadline {
+        match rx.recv_timeout(delay) {
+            Ok(event) => burst.add(event),
+            Err(RecvTimeoutError::Timeout) if !burst.pending_renames.is_empty() && !extended => {
+                extended = true;
+            }
+            Err(_) => break,
+        }
+    }
+
+    let mut paths = burst.paths;
+    paths.sort();
+    paths.dedup();
+    Some(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::collect_burst;
+    use notify::event::{AccessKind, CreateKind, ModifyKind, RenameMode};
+    use notify::{Event, EventKind};
+    use std::path::PathBuf;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    fn event(kind: EventKind, path: &str) -> notify::Result<Event> {
+        Ok(Event::new(kind).add_path(PathBuf::from(path)))
+    }
+
+    #[test]
+    fn test_burst_collects_paths_once() {
+        let (tx, rx) = mpsc::channel();
+        tx.send(event(EventKind::Create(CreateKind::File), "a.md"))
+            .unwrap();
+        tx.send(event(EventKind::Modify(ModifyKind::Any), "b.md"))
+            .unwrap();
+        tx.send(event(EventKind::Modify(ModifyKind::Any), "a.md"))
+            .unwrap();
+
+        let paths = collect_burst(&rx, Duration::from_millis(10)).unwrap();
+        assert_eq!(paths, vec![PathBuf::from("a.md"), PathBuf::from("b.md")]);
+    }
+
+    #[test]
+    fn test_burst_ignores_accesses() {
+        let (tx, rx) = mpsc::channel();
+        tx.send(event(EventKind::Access(AccessKind::Any), "a.md"))
+            .unwrap();
+        tx.send(event(EventKind::Modify(ModifyKind::Any), "b.md"))
+            .unwrap();
+
+        let paths = collect_burst(&rx, Duration::from_millis(10)).unwrap();
+        assert_eq!(paths, vec![PathBuf::from("b.md")]);
+    }
+
+    #[test]
+    fn test_burst_contains_both_paths_of_rename() {
+        let (tx, rx) = mpsc::channel();
+        let from = Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::From)))
+            .add_path(PathBuf::from("old.md"))
+            .set_tracker(1);
+        let to = Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::To)))
+            .add_path(PathBuf::from("new.md"))
+            .set_tracker(1);
+        tx.send(Ok(from)).unwrap();
+        tx.send(Ok(to)).unwrap();
+
+        let paths = collect_burst(&rx, Duration::from_millis(10)).unwrap();
+        assert_eq!(
+            paths,
+            vec![PathBuf::from("new.md"), PathBuf::from("old.md")]
+        );
+    }
+
+    #[test]
+    fn test_burst_waits_for_second_half_of_rename() {
+        let (tx, rx) = mpsc::channel();
+        let from = Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::From)))
+            .add_path(PathBuf::from("old.md"))
+            .set_tracker(1);
+        tx.send(Ok(from)).unwrap();
+        let sender = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(150));
+            let to = Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::To)))
+                .add_path(PathBuf::from("new.md"))
+                .set_tracker(1);
+            tx.send(Ok(to)).unwrap();
+        });
+
+        let paths = collect_burst(&rx, Duration::from_millis(100)).unwrap();
+        assert_eq!(
+            paths,
+            vec![PathBuf::from("new.md"), PathBuf::from("old.md")]
+        );
+        sender.join().unwrap();
+    }
+
+    #[test]
+    fn test_burst_ends_when_watcher_is_dropped() {
+        let (tx, rx) = mpsc::channel::<notify::Result<Event>>();
+        drop(tx);
+
+        assert!(collect_burst(&rx, Duration::from_millis(10)).is_none());
+    }
+}
@@ -0,0 +1,6 @@
+mod collect_burst;
+mod vault_event;
+mod vault_watcher;
+
+pub use vault_event::VaultEvent;
+pub use vault_watcher::VaultWatcher;
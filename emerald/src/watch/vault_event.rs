@@ -0,0 +1,110 @@
+use crate::types;
+use std::path::Path;
+
+/// Change of a note which was detected while watching a vault.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VaultEvent {
+    /// A new note was created.
+    NoteAdded(types::ResourceId),
+
+    /// The content of a note changed.
+    NoteChanged(types::ResourceId),
+
+    /// The outgoing links of a note changed or point to other targets now.
+    LinksChanged(types::ResourceId),
+
+    /// A note was deleted.
+    NoteRemoved(types::ResourceId),
+}
+
+fn is_note(rid: &types::ResourceId) -> bool {
+    let ext = Path::new(rid.0.as_ref())
+        .extension()
+        .and_then(|f| f.to_str());
+    matches!(
+        ext.map(types::ResourceType::from_extension),
+        Some(types::ResourceType::Markdown())
+    )
+}
+
+impl VaultEvent {
+    /// Converts the changes of a refresh into events. Changes of resources which
+    /// aren't notes are skipped. A renamed note is reported as removed and added.
+    pub fn from_changes(changes: &types::VaultChanges) -> Vec<VaultEvent> {
+        let notes = |rids: &[types::ResourceId]| -> Vec<types::ResourceId> {
+            rids.iter().filter(|f| is_note(f)).cloned().collect()
+        };
+
+        let mut events = Vec::new();
+        events.extend(
+            notes(&changes.removed)
+                .into_iter()
+                .map(VaultEvent::NoteRemoved),
+        );
+        events.extend(notes(&changes.added).into_iter().map(VaultEvent::NoteAdded));
+        events.extend(
+            notes(&changes.modified)
+                .into_iter()
+                .map(VaultEvent::NoteChanged),
+        );
+        events.extend(
+            changes
+                .links_changed
+                .iter()
+                .cloned()
+                .map(VaultEvent::LinksChanged),
+        );
+        events
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VaultEvent::NoteAdded(_) => "NoteAdded",
+            VaultEvent::NoteChanged(_) => "NoteChanged",
+            VaultEvent::LinksChanged(_) => "LinksChanged",
+            VaultEvent::NoteRemoved(_) => "NoteRemoved",
+        }
+    }
+
+    /// Returns the note the event refers to.
+    pub fn rid(&self) -> &types::ResourceId {
+        match self {
+            VaultEvent::NoteAdded(rid)
+            | VaultEvent::NoteChanged(rid)
+            | VaultEvent::LinksChanged(rid)
+            | VaultEvent::NoteRemoved(rid) => rid,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VaultEvent;
+    use crate::types::VaultChanges;
+
+    #[test]
+    fn test_from_changes_skips_attachments() {
+        let changes = VaultChanges {
+            added: vec!["new.md".into(), "image.png".into()],
+            modified: vec!["changed.md".into()],
+            removed: vec!["old.md".into(), "assets/no_extension_file".into()],
+            links_changed: vec!["new.md".into(), "other.md".into()],
+        };
+
+        assert_eq!(
+            VaultEvent::from_changes(&changes),
+            vec![
+                VaultEvent::NoteRemoved("old.md".into()),
+                VaultEvent::NoteAdded("new.md".into()),
+                VaultEvent::NoteChanged("changed.md".into()),
+                VaultEvent::LinksChanged("new.md".into()),
+                VaultEvent::LinksChanged("other.md".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_empty_changes() {
+        assert!(VaultEvent::from_changes(&VaultChanges::default()).is_empty());
+    }
+}
@@ -0,0 +1,138 @@
+use super::collect_burst::collect_burst;
+use super::vault_event::VaultEvent;
+use crate::emerald::{DefaultEmerald, Emerald};
+use crate::error::{EmeraldError, Result};
+use crate::types;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::iter::once;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread;
+use std::time::Duration;
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+type Subscribers = Arc<Mutex<Vec<Sender<VaultEvent>>>>;
+
+/// Keeps a loaded vault up to date while its files are edited.
+///
+/// File system events are collected until the vault stayed quiet for the
+/// debounce delay. The touched paths are reloaded afterwards and the resulting
+/// events are sent to all subscribers. Watching stops when the watcher is
+/// dropped.
+pub struct VaultWatcher {
+    _watcher: RecommendedWatcher,
+    subscribers: Subscribers,
+}
+
+impl VaultWatcher {
+    /// Starts watching the directory of the vault. Fails for vaults which
+    /// aren't stored in the filesystem.
+    ///
+    /// # Arguments
+    ///
+    /// * `emerald`: Loaded vault which is updated on changes.
+    /// * `delay`: Time without file system events after which the touched paths
+    ///   are reloaded.
+    pub fn new(emerald: Arc<RwLock<DefaultEmerald>>, delay: Duration) -> Result<Self> {
+        let vault_path = emerald
+            .read()
+            .expect("Vault lock is poisoned")
+            .vault_path()
+            .ok_or(EmeraldError::VaultNotFound)?
+            .to_path_buf();
+
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(tx)?;
+        watcher.watch(&vault_path, RecursiveMode::Recursive)?;
+
+        let subscribers = Subscribers::default();
+        let thread_subscribers = subscribers.clone();
+        // events may report the canonical path of the vault
+        let roots: Vec<_> = once(vault_path.clone())
+            .chain(vault_path.canonicalize().ok())
+            .collect();
+        thread::spawn(move || {
+            while let Some(paths) = collect_burst(&rx, delay) {
+                let rids = to_resource_ids(&roots, &paths);
+                debug!("Reload of {:?}", rids);
+                if rids.is_empty() {
+                    continue;
+                }
+                let reloaded = emerald
+                    .write()
+                    .expect("Vault lock is poisoned")
+                    .reload_paths(&rids);
+                let changes = match reloaded {
+                    Ok(changes) => changes,
+                    Err(err) => {
+                        error!("Reload of {:?} failed: {:?}", rids, err);
+                        continue;
+                    }
+                };
+
+                let events = VaultEvent::from_changes(&changes);
+                let mut subscribers = thread_subscribers.lock().expect("Lock is poisoned");
+                // drop subscribers whose receiver is gone
+                subscribers.retain(|subscriber| {
+                    events
+                        .iter()
+                        .all(|event| subscriber.send(event.clone()).is_ok())
+                });
+            }
+            debug!("Stopped watching {:?}", vault_path);
+        });
+
+        Ok(Self {
+            _watcher: watcher,
+            subscribers,
+        })
+    }
+
+    /// Returns a receiver for the events of all following reloads.
+    pub fn subscribe(&self) -> Receiver<VaultEvent> {
+        let (tx, rx) = mpsc::channel();
+        self.subscribers.lock().expect("Lock is poisoned").push(tx);
+        rx
+    }
+}
+
+/// Converts the paths of a burst into vault relative resource ids. The vault
+/// directory itself and paths outside of the vault are skipped.
+fn to_resource_ids(roots: &[PathBuf], paths: &[PathBuf]) -> Vec<types::ResourceId> {
+    paths
+        .iter()
+        .filter_map(|path| {
+            let rel_path = roots.iter().find_map(|f| path.strip_prefix(f).ok())?;
+            let rel_path = rel_path.to_str()?;
+            (!rel_path.is_empty()).then(|| rel_path.into())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::to_resource_ids;
+    use crate::types::ResourceId;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_to_resource_ids() {
+        let roots = [PathBuf::from("/vault"), PathBuf::from("/private/vault")];
+        let paths = [
+            "/vault",
+            "/vault/a.md",
+            "/private/vault/dir/b.md",
+            "/other/c.md",
+        ]
+        .map(PathBuf::from);
+
+        let rids = to_resource_ids(&roots, &paths);
+        assert_eq!(
+            rids,
+            [ResourceId::from("a.md"), ResourceId::from("dir/b.md")]
+        );
+    }
+}
@@ -1 +1,2 @@
+#[allow(clippy::needless_borrow)]
 mod serde_yaml_tests;
@@ -0,0 +1,233 @@
+//! Golden file tests of the frontmatter updater.
+//!
+//! The notes in `golden_vault` are updated and compared to the expected results in
+//! `golden`. Run the tests with `UPDATE_GOLDEN=1` to regenerate the expected results.
+use emerald::{DefaultEmerald, Emerald, FrontmatterValue, NoteUpdateCommand, ResourceId};
+use std::fs;
+use std::path::PathBuf;
+
+fn load_golden_vault() -> DefaultEmerald {
+    let vault_path = PathBuf::from("./tests/golden_vault");
+    DefaultEmerald::new(&vault_path).unwrap()
+}
+
+fn text(value: &str) -> FrontmatterValue {
+    FrontmatterValue::Text(value.into())
+}
+
+fn assert_golden(emerald: &DefaultEmerald, note: &str, case: &str, cmd: NoteUpdateCommand) {
+    let rid = ResourceId::from(format!("{}.md", note));
+    let updated = emerald.update_note_with(&rid, cmd).unwrap();
+
+    let golden_path = PathBuf::from(format!("./tests/golden/{}.{}.md", note, case));
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(&golden_path, &updated).unwrap();
+    }
+    let expected = fs::read_to_string(&golden_path).unwrap();
+    assert_eq!(updated, expected, "golden file {:?}", golden_path);
+}
+
+#[test]
+fn test_unchanged_notes_round_trip() {
+    let emerald = load_golden_vault();
+
+    for note in emerald.flat_iter() {
+        let rid = emerald.get_resource_id(&note).unwrap();
+        let content = emerald.get_content(&rid);
+
+        let updated = emerald
+            .update_note_with(&rid, NoteUpdateCommand::DoNothing)
+            .unwrap();
+        assert_eq!(updated, content, "round trip of {:?}", rid);
+    }
+}
+
+#[test]
+fn test_setting_existing_values_round_trips() {
+    let emerald = load_golden_vault();
+    let rid = ResourceId::from("comments.md");
+    let content = emerald.get_content(&rid);
+
+    for (key, value) in [
+        ("uid", text("202310181530")),
+        ("title", text("Quoted title")),
+        (
+            "tags",
+            FrontmatterValue::List(vec![text("alpha"), text("beta")]),
+        ),
+    ] {
+        let cmd = NoteUpdateCommand::UpdateOrInsert {
+            key: key.into(),
+            value,
+        };
+        assert_eq!(emerald.update_note_with(&rid, cmd).unwrap(), content);
+    }
+}
+
+#[test]
+fn test_edit_entries_with_comments() {
+    let emerald = load_golden_vault();
+
+    assert_golden(
+        &emerald,
+        "comments",
+        "set_title",
+        NoteUpdateCommand::UpdateOrInsert {
+            key: "title".into(),
+            value: text("New: title"),
+        },
+    );
+    assert_golden(
+        &emerald,
+        "comments",
+        "append_tag",
+        NoteUpdateCommand::AppendToList {
+            key: "tags".into(),
+            value: text("gamma"),
+        },
+    );
+    assert_golden(
+        &emerald,
+        "comments",
+        "remove_alias",
+        NoteUpdateCommand::RemoveFromList {
+            key: "aliases".into(),
+            value: text("Second: alias"),
+        },
+    );
+    assert_golden(
+        &emerald,
+        "comments",
+        "rename_aliases",
+        NoteUpdateCommand::Rename {
+            key: "aliases".into(),
+            new_key: "alias".into(),
+        },
+    );
+    assert_golden(
+        &emerald,
+        "comments",
+        "remove_uid",
+        NoteUpdateCommand::Remove { key: "uid".into() },
+    );
+}
+
+#[test]
+fn test_edit_block_values() {
+    let emerald = load_golden_vault();
+
+    assert_golden(
+        &emerald,
+        "block_scalars",
+        "set_summary",
+        NoteUpdateCommand::UpdateOrInsert {
+            key: "summary".into(),
+            value: text("Short summary"),
+        },
+    );
+    assert_golden(
+        &emerald,
+        "block_scalars",
+        "append_keyword",
+        NoteUpdateCommand::AppendToList {
+            key: "keywords".into(),
+            value: text("three"),
+        },
+    );
+    assert_golden(
+        &emerald,
+        "block_scalars",
+        "insert_list",
+        NoteUpdateCommand::UpdateOrInsert {
+            key: "reviewers".into(),
+            value: FrontmatterValue::List(vec![text("alice"), text("bob")]),
+        },
+    );
+    assert_golden(
+        &emerald,
+        "block_scalars",
+        "set_draft",
+        NoteUpdateCommand::UpdateOrInsert {
+            key: "draft".into(),
+            value: FrontmatterValue::Bool(true),
+        },
+    );
+}
+
+#[test]
+fn test_edit_keeps_line_endings() {
+    let emerald = load_golden_vault();
+
+    assert_golden(
+        &emerald,
+        "crlf",
+        "append_tag",
+        NoteUpdateCommand::AppendToList {
+            key: "tags".into(),
+            value: text("linux"),
+        },
+    );
+    assert_golden(
+        &emerald,
+        "crlf",
+        "insert_key",
+        NoteUpdateCommand::UpdateOrInsert {
+            key: "rating".into(),
+            value: FrontmatterValue::Integer(5),
+        },
+    );
+}
+
+#[test]
+fn test_edit_notes_without_entries() {
+    let emerald = load_golden_vault();
+
+    assert_golden(
+        &emerald,
+        "no_frontmatter",
+        "create",
+        NoteUpdateCommand::CreateFrontmatter,
+    );
+    assert_golden(
+        &emerald,
+        "no_frontmatter",
+        "insert_key",
+        NoteUpdateCommand::UpdateOrInsert {
+            key: "uid".into(),
+            value: text("0"),
+        },
+    );
+    assert_golden(
+        &emerald,
+        "empty_frontmatter",
+        "insert_key",
+        NoteUpdateCommand::UpdateOrInsert {
+            key: "created".into(),
+            value: FrontmatterValue::Date("2023-10-18".into()),
+        },
+    );
+}
+
+#[test]
+fn test_edit_unicode_and_nested_entries() {
+    let emerald = load_golden_vault();
+
+    assert_golden(
+        &emerald,
+        "unicode",
+        "rename_key",
+        NoteUpdateCommand::Rename {
+            key: "key with spaces".into(),
+            new_key: "clé".into(),
+        },
+    );
+    assert_golden(
+        &emerald,
+        "unicode",
+        "set_nested",
+        NoteUpdateCommand::UpdateOrInsert {
+            key: "nested".into(),
+            value: text("flat"),
+        },
+    );
+}
@@ -1,21 +1,1264 @@
-use emerald::{DefaultEmerald, Emerald};
-use std::path::PathBuf;
+use emerald::{
+    AliasConflict, ArchiveVaultStorage, BrokenLink, DefaultEmerald, DiagnosticKind, Direction,
+    Emerald, EmeraldError, EmeraldOptions, FrontmatterValue, GraphFormat, GraphNodeKind,
+    GraphOptions, LinkKind, MemoryVaultStorage, NoteTypes, NoteUpdateCommand, OutlineNode,
+    ResourceId, ScanOptions, TaskFilter, UidStrategy, VaultEvent, VaultWatcher,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
 
 #[test]
 fn test_read_in_test_vault() {
     let vault_path = PathBuf::from("./tests/test_vault");
     let emerald = DefaultEmerald::new(&vault_path).unwrap();
 
-    assert_eq!(emerald.file_count(), 11, "check number of files");
-    assert_eq!(emerald.md_file_count(), 10, "check number of md files");
+    assert_eq!(emerald.file_count(), 20, "check number of files");
+    assert_eq!(emerald.md_file_count(), 19, "check number of md files");
     assert_eq!(
         emerald.valid_backlink_count(),
-        14,
+        29,
         "check number of valid backlinks"
     );
     assert_eq!(
         emerald.invalid_backlink_count(),
-        1,
+        2,
         "check number of invalid backlinks"
     );
+    assert_eq!(
+        emerald.anchor_missing_backlink_count(),
+        2,
+        "check number of backlinks with missing anchors"
+    );
+}
+
+#[test]
+fn test_md_links_are_part_of_the_link_graph() {
+    let vault_path = PathBuf::from("./tests/test_vault");
+    let emerald = DefaultEmerald::new(&vault_path).unwrap();
+
+    let note = emerald
+        .flat_iter()
+        .find(|note| note.title == "folder_note_3_with_md_links")
+        .unwrap();
+
+    let mut links: Vec<_> = emerald
+        .get_links_of(&note)
+        .filter_map(|f| match f {
+            NoteTypes::Note(note) | NoteTypes::AnchorMissing(note) => Some(note.fs_metadata.path),
+            NoteTypes::ResourceRef(_) => None,
+        })
+        .collect();
+    links.sort();
+
+    assert_eq!(
+        links,
+        vec![
+            "./tests/test_vault/folder 2/duplicate_note.md",
+            "./tests/test_vault/folder/folder_note_1.md",
+            "./tests/test_vault/root_note_2.md",
+        ]
+    );
+}
+
+#[test]
+fn test_ambiguous_links_resolve_to_nearest_note() {
+    let vault_path = PathBuf::from("./tests/test_vault");
+    let emerald = DefaultEmerald::new(&vault_path).unwrap();
+
+    let note = emerald
+        .flat_iter()
+        .find(|note| note.title == "links_to_neighbours")
+        .unwrap();
+
+    let mut links: Vec<_> = emerald
+        .get_links_of(&note)
+        .filter_map(|f| match f {
+            NoteTypes::Note(note) | NoteTypes::AnchorMissing(note) => Some(note.fs_metadata.path),
+            NoteTypes::ResourceRef(_) => None,
+        })
+        .collect();
+    links.sort();
+
+    assert_eq!(
+        links,
+        vec![
+            "./tests/test_vault/folder 2/duplicate_note.md",
+            "./tests/test_vault/folder/sub/nested_note.md",
+        ]
+    );
+}
+
+#[test]
+fn test_links_resolve_aliases() {
+    let vault_path = PathBuf::from("./tests/test_vault");
+    let emerald = DefaultEmerald::new(&vault_path).unwrap();
+
+    let note = emerald
+        .flat_iter()
+        .find(|note| note.title == "aliases")
+        .unwrap();
+
+    let links: Vec<_> = emerald
+        .get_links_of(&note)
+        .filter_map(|f| match f {
+            NoteTypes::Note(note) | NoteTypes::AnchorMissing(note) => Some(note.fs_metadata.path),
+            NoteTypes::ResourceRef(_) => None,
+        })
+        .collect();
+
+    assert_eq!(
+        links,
+        vec![
+            "./tests/test_vault/aliases.md",
+            "./tests/test_vault/root_note_1.md",
+        ]
+    );
+
+    assert_eq!(
+        emerald.get_alias_conflicts(),
+        vec![AliasConflict {
+            alias: "root_note_1".into(),
+            alias_of: ResourceId::from("aliases.md"),
+            shadowed_by: ResourceId::from("root_note_1.md"),
+        }]
+    );
+}
+
+#[test]
+fn test_broken_links_are_reported() {
+    let vault_path = PathBuf::from("./tests/test_vault");
+    let emerald = DefaultEmerald::new(&vault_path).unwrap();
+
+    assert_eq!(
+        emerald.get_broken_links(),
+        vec![
+            BrokenLink {
+                src: ResourceId::from("folder 2/links_to_neighbours.md"),
+                link: "[[root_nte_1]]".into(),
+                kind: LinkKind::WikiLink,
+                line: 3,
+                column: 1,
+                suggestions: vec![
+                    "root_note_1".into(),
+                    "RooT_NoTe_3".into(),
+                    "root_note_2".into()
+                ],
+            },
+            BrokenLink {
+                src: ResourceId::from("root_note_2.md"),
+                link: "[[Does not exist]]".into(),
+                kind: LinkKind::WikiLink,
+                line: 1,
+                column: 35,
+                suggestions: vec![],
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_link_graph_of_vault() {
+    let vault_path = PathBuf::from("./tests/test_vault");
+    let emerald = DefaultEmerald::new(&vault_path).unwrap();
+
+    let graph = emerald.get_graph(&GraphOptions::default());
+    assert_eq!(graph.nodes.len(), 19);
+    assert!(graph.nodes.iter().all(|f| f.kind == GraphNodeKind::Note));
+
+    let edge_of = |src: &str, tgt: &str| {
+        graph
+            .edges
+            .iter()
+            .find(|f| graph.nodes[f.source].path == src && graph.nodes[f.target].path == tgt)
+    };
+    let embed = edge_of("embeds.md", "root_note_2.md").unwrap();
+    assert_eq!(embed.kind, LinkKind::WikiEmbed);
+    let labelled = edge_of("aliases.md", "aliases.md").unwrap();
+    assert_eq!(labelled.label.as_deref(), Some("Link to itself"));
+
+    let tags = graph.nodes.iter().find(|f| f.path == "tags.md").unwrap();
+    assert!(!tags.tags.is_empty());
+    assert!(tags.size > 0);
+
+    let dot = graph.write(GraphFormat::Dot);
+    assert!(dot.contains("\"embeds.md\" -> \"root_note_2.md\" [kind=\"wiki_embed\""));
+}
+
+#[test]
+fn test_link_graph_with_attachments_and_broken_links() {
+    let vault_path = PathBuf::from("./tests/test_vault");
+    let emerald = DefaultEmerald::new(&vault_path).unwrap();
+
+    let graph = emerald.get_graph(&GraphOptions {
+        include_attachments: true,
+        include_broken_links: true,
+    });
+
+    let attachments: Vec<_> = graph
+        .nodes
+        .iter()
+        .filter(|f| f.kind == GraphNodeKind::Attachment)
+        .map(|f| f.path.as_str())
+        .collect();
+    assert_eq!(attachments, ["assets/no_extension_file"]);
+
+    let mut broken: Vec<_> = graph
+        .nodes
+        .iter()
+        .filter(|f| f.kind == GraphNodeKind::Broken)
+        .map(|f| f.title.as_str())
+        .collect();
+    broken.sort();
+    assert_eq!(broken, ["Does not exist", "root_nte_1"]);
+
+    // the added nodes are targets of the links pointing to them
+    for node in ["assets/no_extension_file", "broken:Does not exist"] {
+        assert!(graph.edges.iter().any(|f| graph.nodes[f.target].id == node));
+    }
+}
+
+#[test]
+fn test_link_graph_analytics() {
+    let vault_path = PathBuf::from("./tests/test_vault");
+    let emerald = DefaultEmerald::new(&vault_path).unwrap();
+    let analytics = emerald.get_analytics();
+
+    assert_eq!(analytics.orphans(), [ResourceId::from("tags.md")]);
+    assert!(analytics
+        .dead_ends()
+        .contains(&ResourceId::from("headings.md")));
+
+    let components = analytics.weak_components();
+    assert_eq!(components.len(), 3);
+    assert_eq!(
+        components[1],
+        [
+            ResourceId::from("anchors.md"),
+            ResourceId::from("headings.md")
+        ]
+    );
+    assert!(analytics.strong_components().iter().all(|f| f.len() == 1));
+
+    let root_note_1 = analytics.get(&ResourceId::from("root_note_1.md")).unwrap();
+    assert_eq!(root_note_1.in_degree, 4);
+    assert_eq!(root_note_1.out_degree, 2);
+    assert_eq!(root_note_1.component, 0);
+
+    let ranking = analytics.page_rank_ranking();
+    assert_eq!(ranking[0].0, ResourceId::from("root_note_1.md"));
+    let total: f64 = ranking.iter().map(|(_, f)| f.page_rank).sum();
+    assert!((total - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_neighbours_of_note() {
+    let vault_path = PathBuf::from("./tests/test_vault");
+    let emerald = DefaultEmerald::new(&vault_path).unwrap();
+    let rid = ResourceId::from("root_note_1.md");
+
+    let mut backlinks: Vec<_> = emerald
+        .get_neighbours(&rid, 1, Direction::Backward)
+        .into_iter()
+        .map(|f| f.rid.0.to_string())
+        .collect();
+    backlinks.sort();
+    assert_eq!(
+        backlinks,
+        ["aliases.md", "embeds.md", "same_link.md", "tasks.md"]
+    );
+
+    let neighbours = emerald.get_neighbours(&rid, 3, Direction::Both);
+    let embeds = neighbours
+        .iter()
+        .find(|f| f.rid == ResourceId::from("embeds.md"))
+        .unwrap();
+    assert_eq!(embeds.distance, 1);
+    assert!(neighbours.iter().all(|f| f.rid != rid));
+}
+
+#[test]
+fn test_shortest_path_between_notes() {
+    let vault_path = PathBuf::from("./tests/test_vault");
+    let emerald = DefaultEmerald::new(&vault_path).unwrap();
+    let from = ResourceId::from("tasks.md");
+    let to = ResourceId::from("root_note_2.md");
+
+    let path = emerald.get_shortest_path(&from, &to, Direction::Forward);
+    assert_eq!(
+        path.unwrap(),
+        [from.clone(), ResourceId::from("root_note_1.md"), to.clone()]
+    );
+    assert_eq!(
+        emerald.get_shortest_path(&to, &from, Direction::Forward),
+        None
+    );
+    assert_eq!(
+        emerald
+            .get_shortest_path(&to, &from, Direction::Both)
+            .unwrap()
+            .len(),
+        3
+    );
+}
+
+#[test]
+fn test_unlinked_mentions_of_note() {
+    let vault_path = PathBuf::from("./tests/test_vault");
+    let emerald = DefaultEmerald::new(&vault_path).unwrap();
+
+    let mentions = emerald
+        .get_unlinked_mentions(&ResourceId::from("headings.md"))
+        .unwrap();
+    let found: Vec<_> = mentions
+        .iter()
+        .map(|f| (f.src.0.as_ref(), f.text.as_str(), f.line, f.column))
+        .collect();
+    assert_eq!(
+        found,
+        [
+            ("anchors.md", "headings", 15, 22),
+            ("anchors.md", "Headings", 15, 35)
+        ]
+    );
+
+    let updates = emerald
+        .plan_link_mentions(&ResourceId::from("aliases.md"))
+        .unwrap();
+    assert_eq!(updates.len(), 1);
+    assert_eq!(updates[0].0, ResourceId::from("anchors.md"));
+    assert!(updates[0]
+        .1
+        .ends_with("See the [[aliases|note with aliases]].\n"));
+
+    assert!(emerald
+        .get_unlinked_mentions(&ResourceId::from("missing.md"))
+        .is_err());
+}
+
+#[test]
+fn test_embeds_are_distinguished_from_links() {
+    let vault_path = PathBuf::from("./tests/test_vault");
+    let emerald = DefaultEmerald::new(&vault_path).unwrap();
+
+    let note = emerald
+        .flat_iter()
+        .find(|note| note.title == "embeds")
+        .unwrap();
+
+    let embeds: Vec<_> = emerald
+        .get_embeds_of(&note)
+        .map(|f| match f {
+            NoteTypes::Note(note) | NoteTypes::AnchorMissing(note) => note.title,
+            NoteTypes::ResourceRef(rid) => rid.0.to_string(),
+        })
+        .collect();
+
+    assert_eq!(embeds, vec!["root_note_2", "assets/no_extension_file"]);
+    assert_eq!(emerald.get_links_of(&note).count(), 3);
+}
+
+#[test]
+fn test_outline_of_note() {
+    let vault_path = PathBuf::from("./tests/test_vault");
+    let emerald = DefaultEmerald::new(&vault_path).unwrap();
+
+    let note = emerald
+        .flat_iter()
+        .find(|note| note.title == "headings")
+        .unwrap();
+
+    let outline = emerald.get_outline_of(&note);
+    let titles = |nodes: &[OutlineNode]| -> Vec<String> {
+        nodes.iter().map(|f| f.heading.text.clone()).collect()
+    };
+
+    assert_eq!(titles(&outline), ["Chapter 1", "Chapter 2"]);
+    assert_eq!(titles(&outline[0].children), ["Section A", "Section B"]);
+    assert_eq!(titles(&outline[0].children[0].children), ["Subsection A.1"]);
+    assert_eq!(titles(&outline[1].children), ["Section C"]);
+    assert_eq!(outline[1].heading.level, 1);
+    assert_eq!(outline[0].children[1].heading.level, 2);
+}
+
+#[test]
+fn test_links_with_missing_anchors_are_reported() {
+    let vault_path = PathBuf::from("./tests/test_vault");
+    let emerald = DefaultEmerald::new(&vault_path).unwrap();
+
+    let note = emerald
+        .flat_iter()
+        .find(|note| note.title == "anchors")
+        .unwrap();
+
+    let links: Vec<_> = emerald
+        .get_links_of(&note)
+        .map(|f| match f {
+            NoteTypes::Note(note) => format!("note: {}", note.title),
+            NoteTypes::AnchorMissing(note) => format!("anchor missing: {}", note.title),
+            NoteTypes::ResourceRef(rid) => format!("resource: {}", rid.0),
+        })
+        .collect();
+
+    assert_eq!(
+        links,
+        vec![
+            "note: headings",
+            "note: headings",
+            "note: anchors",
+            "note: headings",
+            "anchor missing: headings",
+            "anchor missing: anchors",
+        ]
+    );
+}
+
+#[test]
+fn test_tags_of_note() {
+    let vault_path = PathBuf::from("./tests/test_vault");
+    let emerald = DefaultEmerald::new(&vault_path).unwrap();
+
+    let note = emerald
+        .flat_iter()
+        .find(|note| note.title == "tags")
+        .unwrap();
+
+    assert_eq!(note.doc_metadata.tags, ["project/alpha", "todo"]);
+    assert_eq!(
+        emerald.get_tags_of(&note),
+        ["project/alpha", "todo", "project/beta"]
+    );
+}
+
+#[test]
+fn test_tag_index() {
+    let vault_path = PathBuf::from("./tests/test_vault");
+    let emerald = DefaultEmerald::new(&vault_path).unwrap();
+
+    let notes: Vec<_> = emerald
+        .get_notes_with_tag("#Project")
+        .map(|note| note.title)
+        .collect();
+    assert_eq!(notes, ["tags"]);
+
+    assert_eq!(
+        emerald.get_tag_counts(),
+        [
+            ("calls".to_owned(), 1),
+            ("outline".to_owned(), 1),
+            ("project".to_owned(), 1),
+            ("project/alpha".to_owned(), 1),
+            ("project/beta".to_owned(), 1),
+            ("todo".to_owned(), 2),
+            ("travel".to_owned(), 1),
+        ]
+    );
+}
+
+#[test]
+fn test_tasks_of_note() {
+    let vault_path = PathBuf::from("./tests/test_vault");
+    let emerald = DefaultEmerald::new(&vault_path).unwrap();
+
+    let note = emerald
+        .flat_iter()
+        .find(|note| note.title == "tasks")
+        .unwrap();
+
+    let tasks: Vec<_> = emerald
+        .get_tasks_of(&note)
+        .into_iter()
+        .map(|task| (task.line, task.checked, task.due))
+        .collect();
+
+    assert_eq!(
+        tasks,
+        [
+            (6, false, Some("2026-10-20".to_owned())),
+            (7, true, None),
+            (8, false, Some("2026-11-02".to_owned())),
+            (9, false, None),
+        ]
+    );
+}
+
+#[test]
+fn test_query_tasks() {
+    let vault_path = PathBuf::from("./tests/test_vault");
+    let emerald = DefaultEmerald::new(&vault_path).unwrap();
+
+    let texts = |filter: TaskFilter| -> Vec<String> {
+        emerald
+            .get_tasks(&filter)
+            .into_iter()
+            .map(|(_, task)| task.text)
+            .collect()
+    };
+
+    assert_eq!(
+        texts(TaskFilter {
+            checked: Some(true),
+            ..Default::default()
+        }),
+        ["Call [[root_note_1]] #calls"]
+    );
+    assert_eq!(
+        texts(TaskFilter {
+            checked: Some(false),
+            due_before: Some("2026-11-01".into()),
+            ..Default::default()
+        }),
+        ["Write the report 📅 2026-10-20"]
+    );
+    assert_eq!(
+        texts(TaskFilter {
+            tag: Some("travel".into()),
+            ..Default::default()
+        }),
+        ["Plan the trip [due:: 2026-11-02] #travel"]
+    );
+    assert_eq!(
+        texts(TaskFilter {
+            tag: Some("todo".into()),
+            ..Default::default()
+        })
+        .len(),
+        4
+    );
+}
+
+#[test]
+fn test_frontmatter_uid_is_used() {
+    let vault_path = PathBuf::from("./tests/test_vault");
+    let emerald = DefaultEmerald::new(&vault_path).unwrap();
+
+    let note = emerald
+        .flat_iter()
+        .find(|note| note.title == "headings")
+        .unwrap();
+    assert_eq!(note.uid.0.as_ref(), "202310181530");
+
+    let rid = emerald.get_resource_id(&note).unwrap();
+    assert_eq!(rid.0.as_ref(), "headings.md");
+}
+
+#[test]
+fn test_uids_are_minted_with_strategy() {
+    let vault_path = PathBuf::from("./tests/test_vault");
+    let options = EmeraldOptions {
+        uid_strategy: UidStrategy::TimestampZettel,
+        ..Default::default()
+    };
+    let emerald = DefaultEmerald::with_options(&vault_path, &options).unwrap();
+
+    let mut uids: Vec<String> = emerald.flat_iter().map(|f| f.uid.0.into()).collect();
+    // notes created in the same minute are told apart by a suffix
+    assert!(uids.iter().all(|f| {
+        let (minute, suffix) = f.split_once('-').unwrap_or((f, "0000"));
+        minute.len() == 12
+            && minute.chars().all(|ch| ch.is_ascii_digit())
+            && suffix.len() == 4
+            && suffix.chars().all(|ch| ch.is_ascii_hexdigit())
+    }));
+
+    uids.sort();
+    uids.dedup();
+    assert_eq!(uids.len(), emerald.md_file_count());
+}
+
+#[test]
+fn test_minted_ulids_are_stable_across_loads() {
+    let vault_path = PathBuf::from("./tests/test_vault");
+    let options = EmeraldOptions {
+        uid_strategy: UidStrategy::Ulid,
+        ..Default::default()
+    };
+    let load_uids = || {
+        let emerald = DefaultEmerald::with_options(&vault_path, &options).unwrap();
+        let mut uids: Vec<(String, String)> = emerald
+            .flat_iter()
+            .map(|f| (f.title.clone(), f.uid.0.into()))
+            .collect();
+        uids.sort();
+        uids
+    };
+
+    let first = load_uids();
+    assert!(first.iter().any(|(_, uid)| uid.len() == 26));
+    assert_eq!(first, load_uids());
+}
+
+#[test]
+fn test_default_uids_dont_change_when_a_note_is_added() {
+    let vault_path = std::env::temp_dir().join(format!("emerald_uids_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&vault_path);
+    copy_dir(Path::new("./tests/test_vault"), &vault_path);
+    let uids_of = |emerald: &DefaultEmerald| {
+        let mut uids: Vec<(String, String)> = emerald
+            .flat_iter()
+            .map(|f| {
+                (
+                    emerald.get_resource_id(&f).unwrap().0.into(),
+                    f.uid.0.into(),
+                )
+            })
+            .collect();
+        uids.sort();
+        uids
+    };
+
+    let mut emerald = DefaultEmerald::new(&vault_path).unwrap();
+    let before = uids_of(&emerald);
+
+    // sorts before all other notes
+    fs::write(vault_path.join("0 first.md"), "A new note.\n").unwrap();
+    emerald.refresh().unwrap();
+    let loaded = DefaultEmerald::new(&vault_path).unwrap();
+
+    for after in [uids_of(&emerald), uids_of(&loaded)] {
+        let after: Vec<_> = after
+            .into_iter()
+            .filter(|(rid, _)| rid != "0 first.md")
+            .collect();
+        assert_eq!(after, before);
+    }
+    fs::remove_dir_all(&vault_path).unwrap();
+}
+
+#[test]
+fn test_duplicate_frontmatter_uids_are_reported() {
+    let vault_path = PathBuf::from("./tests/test_vault_duplicate_uids");
+    let res = DefaultEmerald::new(&vault_path);
+
+    assert!(matches!(res, Err(EmeraldError::DuplicateUid(uid, _, _)) if uid == "zk-1"));
+}
+
+#[test]
+fn test_content_of_note() {
+    let vault_path = PathBuf::from("./tests/test_vault");
+    let emerald = DefaultEmerald::new(&vault_path).unwrap();
+
+    let rid = ResourceId::from("headings.md");
+    assert!(emerald
+        .get_content(&rid)
+        .starts_with("---\nuid: 202310181530\n"));
+}
+
+#[test]
+fn test_update_frontmatter_of_note() {
+    let vault_path = PathBuf::from("./tests/test_vault");
+    let emerald = DefaultEmerald::new(&vault_path).unwrap();
+
+    let rid = ResourceId::from("headings.md");
+    let renamed = emerald
+        .update_note_with(
+            &rid,
+            NoteUpdateCommand::Rename {
+                key: "uid".into(),
+                new_key: "id".into(),
+            },
+        )
+        .unwrap();
+    assert!(renamed.starts_with("---\nid: 202310181530\n"));
+
+    let appended = emerald
+        .update_note_with(
+            &rid,
+            NoteUpdateCommand::AppendToList {
+                key: "tags".into(),
+                value: FrontmatterValue::Text("draft".into()),
+            },
+        )
+        .unwrap();
+    assert!(appended.contains("tags: [outline, draft]\n"));
+}
+
+fn copy_dir(src: &Path, dst: &Path) {
+    fs::create_dir_all(dst).unwrap();
+    for entry in fs::read_dir(src).unwrap() {
+        let entry = entry.unwrap();
+        let dst_path = dst.join(entry.file_name());
+        if entry.file_type().unwrap().is_dir() {
+            copy_dir(&entry.path(), &dst_path);
+        } else {
+            fs::copy(entry.path(), dst_path).unwrap();
+        }
+    }
+}
+
+#[test]
+fn test_scan_skips_hidden_and_ignored_files() {
+    let vault_path = std::env::temp_dir().join(format!("emerald_scan_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&vault_path);
+    copy_dir(Path::new("./tests/test_vault"), &vault_path);
+    for file in [
+        ".obsidian/workspace.md",
+        ".trash/deleted.md",
+        "node_modules/pkg/readme.md",
+        "drafts/draft.md",
+    ] {
+        let path = vault_path.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, "[[headings]]\n").unwrap();
+    }
+    fs::write(vault_path.join(".gitignore"), "node_modules/\n").unwrap();
+    fs::write(vault_path.join(".emeraldignore"), "drafts/\n").unwrap();
+
+    let emerald = DefaultEmerald::new(&vault_path).unwrap();
+    assert_eq!(emerald.file_count(), 20);
+    assert_eq!(emerald.md_file_count(), 19);
+    assert_eq!(emerald.valid_backlink_count(), 29);
+
+    let options = EmeraldOptions {
+        scan: ScanOptions {
+            skip_hidden: false,
+            include: vec!["*.md".into()],
+            exclude: vec!["folder/**".into()],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let emerald = DefaultEmerald::with_options(&vault_path, &options).unwrap();
+    fs::remove_dir_all(&vault_path).unwrap();
+
+    let rids: Vec<_> = emerald
+        .flat_iter()
+        .filter_map(|f| emerald.get_resource_id(&f))
+        .collect();
+    let rid = |rid: &str| rids.contains(&ResourceId::from(rid));
+    assert!(rid(".obsidian/workspace.md"));
+    assert!(rid(".trash/deleted.md"));
+    assert!(!rid("node_modules/pkg/readme.md"));
+    assert!(!rid("drafts/draft.md"));
+    assert!(!rid("folder/folder_note_1.md"));
+    assert!(rid("headings.md"));
+    assert_eq!(emerald.file_count(), emerald.md_file_count());
+}
+
+#[test]
+fn test_lenient_load_reports_diagnostics() {
+    let vault_path = std::env::temp_dir().join(format!("emerald_lenient_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&vault_path);
+    copy_dir(Path::new("./tests/test_vault"), &vault_path);
+    fs::write(vault_path.join("latin1.md"), b"Gr\xfc\xdfe [[headings]]\n").unwrap();
+    fs::write(
+        vault_path.join("broken yaml.md"),
+        "---\naliases: [unclosed\n---\n# Broken yaml\n",
+    )
+    .unwrap();
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(
+        vault_path.join("missing.md"),
+        vault_path.join("dangling.md"),
+    )
+    .unwrap();
+
+    assert!(DefaultEmerald::new(&vault_path).is_err());
+
+    let options = EmeraldOptions {
+        lenient: true,
+        ..Default::default()
+    };
+    let mut emerald = DefaultEmerald::with_options(&vault_path, &options).unwrap();
+    assert_eq!(emerald.md_file_count(), 21);
+    assert!(emerald
+        .get_content(&ResourceId::from("latin1.md"))
+        .starts_with("Gr\u{fffd}\u{fffd}e"));
+
+    let diagnostics: Vec<_> = emerald
+        .get_diagnostics()
+        .iter()
+        .map(|f| (f.path.0.as_ref(), f.kind, f.span))
+        .collect();
+    let mut expected = vec![
+        (
+            "broken yaml.md",
+            DiagnosticKind::InvalidFrontmatter,
+            Some((4, 22)),
+        ),
+        ("latin1.md", DiagnosticKind::InvalidUtf8, Some((2, 3))),
+    ];
+    if cfg!(unix) {
+        expected.insert(1, ("dangling.md", DiagnosticKind::UnreadableMetadata, None));
+    }
+    assert_eq!(diagnostics, expected);
+
+    // diagnostics of fixed notes are dropped on refresh
+    fs::write(vault_path.join("latin1.md"), "Grüße [[headings]]\n").unwrap();
+    emerald.refresh().unwrap();
+    let kinds: Vec<_> = emerald.get_diagnostics().iter().map(|f| f.kind).collect();
+    fs::remove_dir_all(&vault_path).unwrap();
+
+    assert!(!kinds.contains(&DiagnosticKind::InvalidUtf8));
+    assert!(kinds.contains(&DiagnosticKind::InvalidFrontmatter));
+}
+
+#[test]
+fn test_vault_in_memory() {
+    let storage = Arc::new(MemoryVaultStorage::new());
+    storage.insert(
+        "a.md",
+        "---\naliases: [first]\n---\nSee [[b]] and [[c#Missing]].\n",
+    );
+    storage.insert("folder/b.md", "# B\nBack to [[first]].\n");
+    storage.insert("image.png", vec![0x89, 0x50]);
+
+    let mut emerald =
+        DefaultEmerald::with_storage(storage.clone(), &EmeraldOptions::default()).unwrap();
+    assert!(emerald.vault_path().is_none());
+    assert_eq!(emerald.file_count(), 3);
+    assert_eq!(emerald.md_file_count(), 2);
+    assert_eq!(emerald.valid_backlink_count(), 2);
+    assert_eq!(emerald.invalid_backlink_count(), 1);
+
+    // writes go to the storage and are picked up by a refresh
+    storage.insert("c.md", "# C\n");
+    emerald
+        .rename_note(&ResourceId::from("folder/b.md"), &ResourceId::from("b2.md"))
+        .unwrap();
+    let changes = emerald.refresh().unwrap();
+
+    assert_eq!(
+        changes.added,
+        vec![ResourceId::from("b2.md"), ResourceId::from("c.md")]
+    );
+    assert_eq!(changes.removed, vec![ResourceId::from("folder/b.md")]);
+    assert_eq!(
+        emerald.get_content(&ResourceId::from("a.md")),
+        "---\naliases: [first]\n---\nSee [[b2]] and [[c#Missing]].\n"
+    );
+    assert_eq!(emerald.invalid_backlink_count(), 0);
+    assert_eq!(emerald.anchor_missing_backlink_count(), 1);
+}
+
+#[test]
+fn test_vault_in_archive() {
+    let archive_path =
+        std::env::temp_dir().join(format!("emerald_archive_{}.tar.gz", std::process::id()));
+    let file = fs::File::create(&archive_path).unwrap();
+    let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+        file,
+        flate2::Compression::default(),
+    ));
+    builder
+        .append_dir_all("test_vault", "./tests/test_vault")
+        .unwrap();
+    builder.into_inner().unwrap().finish().unwrap();
+
+    let storage = ArchiveVaultStorage::open(&archive_path, &ScanOptions::default()).unwrap();
+    let emerald =
+        DefaultEmerald::with_storage(Arc::new(storage), &EmeraldOptions::default()).unwrap();
+    fs::remove_file(&archive_path).unwrap();
+
+    assert!(emerald.vault_path().is_none());
+    assert_eq!(emerald.file_count(), 20);
+    assert_eq!(emerald.md_file_count(), 19);
+    assert_eq!(emerald.valid_backlink_count(), 29);
+    assert_eq!(emerald.invalid_backlink_count(), 2);
+
+    let rid = ResourceId::from("test_vault/aliases.md");
+    assert_eq!(
+        emerald.get_content(&rid),
+        fs::read_to_string("./tests/test_vault/aliases.md").unwrap()
+    );
+    assert!(matches!(
+        emerald.rename_note(&rid, &ResourceId::from("moved.md")),
+        Err(EmeraldError::ReadOnlyStorage)
+    ));
+}
+
+#[test]
+fn test_plan_rename_of_note() {
+    let vault_path = PathBuf::from("./tests/test_vault");
+    let emerald = DefaultEmerald::new(&vault_path).unwrap();
+
+    let mut updates = emerald
+        .plan_rename(
+            &ResourceId::from("folder/folder_note_1.md"),
+            &ResourceId::from("archive/renamed note.md"),
+        )
+        .unwrap();
+    updates.sort_by(|a, b| a.0 .0.cmp(&b.0 .0));
+
+    assert_eq!(updates.len(), 2);
+    assert_eq!(
+        updates[0].0,
+        ResourceId::from("folder/folder_note_2_with_code_block.md")
+    );
+    assert!(updates[0].1.starts_with("Link ok: [[renamed note]]\n"));
+    assert_eq!(
+        updates[1].0,
+        ResourceId::from("folder/folder_note_3_with_md_links.md")
+    );
+    assert!(updates[1]
+        .1
+        .contains("[folder note](../archive/renamed%20note.md#Heading)"));
+}
+
+#[test]
+fn test_rename_to_ambiguous_name_is_refused() {
+    let vault_path = PathBuf::from("./tests/test_vault");
+    let emerald = DefaultEmerald::new(&vault_path).unwrap();
+    let rid = ResourceId::from("root_note_1.md");
+
+    for new_rid in ["folder/duplicate_note.md", "Note with aliases.md"] {
+        let err = emerald
+            .plan_rename(&rid, &ResourceId::from(new_rid))
+            .unwrap_err();
+        assert!(
+            matches!(err, EmeraldError::AmbiguousName(_, _)),
+            "{:?}",
+            err
+        );
+    }
+
+    let err = emerald
+        .plan_rename(&rid, &ResourceId::from("root_note_2.md"))
+        .unwrap_err();
+    assert!(matches!(err, EmeraldError::ResourceAlreadyExists(_)));
+}
+
+#[test]
+fn test_rename_note_keeps_links_valid() {
+    let vault_path = std::env::temp_dir().join(format!("emerald_rename_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&vault_path);
+    copy_dir(Path::new("./tests/test_vault"), &vault_path);
+
+    let emerald = DefaultEmerald::new(&vault_path).unwrap();
+    emerald
+        .rename_note(
+            &ResourceId::from("folder/folder_note_1.md"),
+            &ResourceId::from("archive/renamed note.md"),
+        )
+        .unwrap();
+
+    let renamed = DefaultEmerald::new(&vault_path).unwrap();
+    assert!(!vault_path.join("folder/folder_note_1.md").exists());
+    assert!(vault_path.join("archive/renamed note.md").exists());
+    assert_eq!(
+        renamed.valid_backlink_count(),
+        emerald.valid_backlink_count()
+    );
+    assert_eq!(
+        renamed.anchor_missing_backlink_count(),
+        emerald.anchor_missing_backlink_count()
+    );
+    assert_eq!(renamed.get_broken_links(), emerald.get_broken_links());
+    fs::remove_dir_all(&vault_path).unwrap();
+}
+
+#[test]
+fn test_emerald_is_shared_between_threads() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<DefaultEmerald>();
+
+    let vault_path = PathBuf::from("./tests/test_vault");
+    let emerald = std::sync::Arc::new(DefaultEmerald::new(&vault_path).unwrap());
+
+    let handles: Vec<_> = ["root_note_1.md", "headings.md", "anchors.md"]
+        .into_iter()
+        .map(|rid| {
+            let emerald = emerald.clone();
+            std::thread::spawn(move || {
+                let rid = ResourceId::from(rid);
+                let backlinks = emerald.get_neighbours(&rid, 1, Direction::Backward);
+                let content = emerald.get_content(&rid).to_string();
+                (rid, content, backlinks)
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        let (rid, content, backlinks) = handle.join().unwrap();
+        assert_eq!(content, emerald.get_content(&rid));
+        assert_eq!(
+            backlinks,
+            emerald.get_neighbours(&rid, 1, Direction::Backward)
+        );
+    }
+}
+
+#[test]
+fn test_parallel_load_matches_sequential_load() {
+    let vault_path = PathBuf::from("./tests/test_vault");
+    let sequential = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .unwrap()
+        .install(|| DefaultEmerald::new(&vault_path).unwrap());
+    let parallel = rayon::ThreadPoolBuilder::new()
+        .num_threads(4)
+        .build()
+        .unwrap()
+        .install(|| DefaultEmerald::new(&vault_path).unwrap());
+
+    assert_eq!(parallel.md_file_count(), sequential.md_file_count());
+    assert_eq!(
+        parallel.valid_backlink_count(),
+        sequential.valid_backlink_count()
+    );
+    assert_eq!(parallel.get_broken_links(), sequential.get_broken_links());
+    for note in sequential.flat_iter() {
+        let rid = sequential.get_resource_id(&note).unwrap();
+        assert_eq!(parallel.get_content(&rid), sequential.get_content(&rid));
+        assert_eq!(
+            parallel.get_neighbours(&rid, 1, Direction::Both),
+            sequential.get_neighbours(&rid, 1, Direction::Both)
+        );
+    }
+}
+
+fn sorted_neighbours(emerald: &DefaultEmerald, rid: &ResourceId) -> Vec<(ResourceId, usize)> {
+    let mut neighbours: Vec<_> = emerald
+        .get_neighbours(rid, 1, Direction::Both)
+        .into_iter()
+        .map(|f| (f.rid, f.distance))
+        .collect();
+    neighbours.sort_by(|a, b| a.0 .0.cmp(&b.0 .0));
+    neighbours
+}
+
+#[test]
+fn test_refresh_reloads_changed_files() {
+    let vault_path = std::env::temp_dir().join(format!("emerald_refresh_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&vault_path);
+    copy_dir(Path::new("./tests/test_vault"), &vault_path);
+
+    let mut emerald = DefaultEmerald::new(&vault_path).unwrap();
+    assert!(emerald.refresh().unwrap().is_empty());
+
+    fs::write(
+        vault_path.join("Does not exist.md"),
+        "Created later, see [[headings#Missing section]] and #later.\n",
+    )
+    .unwrap();
+    let mut headings = fs::read_to_string(vault_path.join("headings.md")).unwrap();
+    headings.push_str("\n## Missing section\n");
+    fs::write(vault_path.join("headings.md"), headings).unwrap();
+    fs::remove_file(vault_path.join("tags.md")).unwrap();
+
+    let changes = emerald.refresh().unwrap();
+    assert_eq!(changes.added, vec![ResourceId::from("Does not exist.md")]);
+    assert_eq!(changes.modified, vec![ResourceId::from("headings.md")]);
+    assert_eq!(changes.removed, vec![ResourceId::from("tags.md")]);
+    assert!(changes
+        .links_changed
+        .contains(&ResourceId::from("root_note_2.md")));
+
+    assert_eq!(emerald.invalid_backlink_count(), 1);
+
+    let loaded = DefaultEmerald::new(&vault_path).unwrap();
+    assert_eq!(emerald.md_file_count(), loaded.md_file_count());
+    assert_eq!(
+        emerald.valid_backlink_count(),
+        loaded.valid_backlink_count()
+    );
+    assert_eq!(
+        emerald.invalid_backlink_count(),
+        loaded.invalid_backlink_count()
+    );
+    assert_eq!(
+        emerald.anchor_missing_backlink_count(),
+        loaded.anchor_missing_backlink_count()
+    );
+    assert_eq!(emerald.get_broken_links(), loaded.get_broken_links());
+    assert_eq!(emerald.get_tag_counts(), loaded.get_tag_counts());
+    assert_eq!(
+        emerald.get_analytics().orphans(),
+        loaded.get_analytics().orphans()
+    );
+    for note in loaded.flat_iter() {
+        let rid = loaded.get_resource_id(&note).unwrap();
+        assert_eq!(emerald.get_content(&rid), loaded.get_content(&rid));
+        assert_eq!(
+            sorted_neighbours(&emerald, &rid),
+            sorted_neighbours(&loaded, &rid)
+        );
+    }
+
+    assert!(emerald.refresh().unwrap().is_empty());
+    fs::remove_dir_all(&vault_path).unwrap();
+}
+
+#[test]
+fn test_refresh_resolves_links_to_changed_alias() {
+    let vault_path = std::env::temp_dir().join(format!("emerald_alias_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&vault_path);
+    copy_dir(Path::new("./tests/test_vault"), &vault_path);
+    fs::write(vault_path.join("old_alias.md"), "[[Note with aliases]]\n").unwrap();
+    fs::write(vault_path.join("new_alias.md"), "[[Renamed alias]]\n").unwrap();
+
+    let mut emerald = DefaultEmerald::new(&vault_path).unwrap();
+    let aliases = fs::read_to_string(vault_path.join("aliases.md")).unwrap();
+    fs::write(
+        vault_path.join("aliases.md"),
+        aliases.replace("- Note with aliases", "- Renamed alias"),
+    )
+    .unwrap();
+
+    let changes = emerald.refresh().unwrap();
+    assert_eq!(changes.modified, vec![ResourceId::from("aliases.md")]);
+    assert!(changes
+        .links_changed
+        .contains(&ResourceId::from("old_alias.md")));
+    assert!(changes
+        .links_changed
+        .contains(&ResourceId::from("new_alias.md")));
+
+    let loaded = DefaultEmerald::new(&vault_path).unwrap();
+    assert_eq!(emerald.get_broken_links(), loaded.get_broken_links());
+    assert_eq!(
+        emerald.valid_backlink_count(),
+        loaded.valid_backlink_count()
+    );
+    for rid in ["old_alias.md", "new_alias.md", "aliases.md"].map(ResourceId::from) {
+        assert_eq!(
+            sorted_neighbours(&emerald, &rid),
+            sorted_neighbours(&loaded, &rid)
+        );
+    }
+    fs::remove_dir_all(&vault_path).unwrap();
+}
+
+/// Writes a file and sets its modification time, so that a change can't be seen
+/// in the metadata.
+fn write_with_mtime(path: &Path, content: &str, modified: SystemTime) {
+    fs::write(path, content).unwrap();
+    fs::File::options()
+        .write(true)
+        .open(path)
+        .unwrap()
+        .set_modified(modified)
+        .unwrap();
+}
+
+#[test]
+fn test_refresh_detects_same_size_edit_within_same_second() {
+    let vault_path = std::env::temp_dir().join(format!("emerald_racy_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&vault_path);
+    copy_dir(Path::new("./tests/test_vault"), &vault_path);
+
+    // the edit shares size and modification time with the loaded note
+    let modified = SystemTime::now() + Duration::from_secs(10);
+    let racy = vault_path.join("racy.md");
+    write_with_mtime(&racy, "Links to [[root_note_1]].\n", modified);
+    let mut emerald = DefaultEmerald::new(&vault_path).unwrap();
+    write_with_mtime(&racy, "Links to [[root_note_2]].\n", modified);
+
+    let changes = emerald.refresh().unwrap();
+    assert_eq!(changes.modified, vec![ResourceId::from("racy.md")]);
+    assert_eq!(changes.links_changed, vec![ResourceId::from("racy.md")]);
+    assert_eq!(
+        emerald.get_content(&ResourceId::from("racy.md")),
+        "Links to [[root_note_2]].\n"
+    );
+    assert_eq!(
+        emerald.get_neighbours(&ResourceId::from("racy.md"), 1, Direction::Forward)[0].rid,
+        ResourceId::from("root_note_2.md")
+    );
+
+    // unchanged contents aren't reported again
+    assert!(emerald.refresh().unwrap().is_empty());
+    fs::remove_dir_all(&vault_path).unwrap();
+}
+
+#[test]
+fn test_reload_paths_reloads_given_resources() {
+    let vault_path = std::env::temp_dir().join(format!("emerald_reload_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&vault_path);
+    copy_dir(Path::new("./tests/test_vault"), &vault_path);
+    fs::write(vault_path.join("edited.md"), "Links to [[root_note_1]].\n").unwrap();
+
+    let mut emerald = DefaultEmerald::new(&vault_path).unwrap();
+
+    // the paths are reloaded no matter what their metadata says
+    fs::write(vault_path.join("edited.md"), "Links to [[root_note_2]].\n").unwrap();
+    let changes = emerald
+        .reload_paths(&[ResourceId::from("edited.md")])
+        .unwrap();
+    assert_eq!(changes.modified, vec![ResourceId::from("edited.md")]);
+    assert_eq!(changes.links_changed, vec![ResourceId::from("edited.md")]);
+    assert_eq!(
+        emerald.get_neighbours(&ResourceId::from("edited.md"), 1, Direction::Forward)[0].rid,
+        ResourceId::from("root_note_2.md")
+    );
+
+    // both paths of a rename are reloaded
+    fs::rename(vault_path.join("edited.md"), vault_path.join("moved.md")).unwrap();
+    let changes = emerald
+        .reload_paths(&[ResourceId::from("edited.md"), ResourceId::from("moved.md")])
+        .unwrap();
+    assert_eq!(changes.added, vec![ResourceId::from("moved.md")]);
+    assert_eq!(changes.removed, vec![ResourceId::from("edited.md")]);
+
+    // new files below a directory are added
+    fs::create_dir(vault_path.join("new_dir")).unwrap();
+    fs::write(vault_path.join("new_dir/inner.md"), "[[moved]]\n").unwrap();
+    let changes = emerald
+        .reload_paths(&[ResourceId::from("new_dir")])
+        .unwrap();
+    assert_eq!(changes.added, vec![ResourceId::from("new_dir/inner.md")]);
+
+    // paths skipped by the scan are ignored
+    fs::create_dir(vault_path.join(".git")).unwrap();
+    fs::write(vault_path.join(".git/index"), "").unwrap();
+    fs::write(vault_path.join(".hidden.md"), "[[moved]]\n").unwrap();
+    let changes = emerald
+        .reload_paths(&[
+            ResourceId::from(".git/index"),
+            ResourceId::from(".hidden.md"),
+        ])
+        .unwrap();
+    assert!(changes.is_empty());
+
+    let loaded = DefaultEmerald::new(&vault_path).unwrap();
+    assert_eq!(emerald.md_file_count(), loaded.md_file_count());
+    assert_eq!(
+        emerald.valid_backlink_count(),
+        loaded.valid_backlink_count()
+    );
+    assert_eq!(emerald.get_broken_links(), loaded.get_broken_links());
+    for rid in ["moved.md", "new_dir/inner.md", "root_note_2.md"].map(ResourceId::from) {
+        assert_eq!(
+            sorted_neighbours(&emerald, &rid),
+            sorted_neighbours(&loaded, &rid)
+        );
+    }
+    fs::remove_dir_all(&vault_path).unwrap();
+}
+
+#[test]
+fn test_watcher_reports_changed_notes() {
+    let vault_path = std::env::temp_dir().join(format!("emerald_watch_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&vault_path);
+    copy_dir(Path::new("./tests/test_vault"), &vault_path);
+
+    let emerald = Arc::new(RwLock::new(DefaultEmerald::new(&vault_path).unwrap()));
+    let watcher = VaultWatcher::new(emerald.clone(), Duration::from_millis(100)).unwrap();
+    let events = watcher.subscribe();
+
+    fs::write(vault_path.join("watched.md"), "Links to [[root_note_1]].\n").unwrap();
+    fs::remove_file(vault_path.join("tags.md")).unwrap();
+
+    // the changes might be reported by more than one refresh
+    let expected = [
+        VaultEvent::NoteRemoved(ResourceId::from("tags.md")),
+        VaultEvent::NoteAdded(ResourceId::from("watched.md")),
+        VaultEvent::LinksChanged(ResourceId::from("watched.md")),
+    ];
+    let mut received = vec![];
+    while !expected.iter().all(|f| received.contains(f)) {
+        received.push(events.recv_timeout(Duration::from_secs(10)).unwrap());
+    }
+
+    let emerald = emerald.read().unwrap();
+    assert_eq!(emerald.md_file_count(), 19);
+    assert_eq!(
+        emerald.get_neighbours(&ResourceId::from("watched.md"), 1, Direction::Forward)[0].rid,
+        ResourceId::from("root_note_1.md")
+    );
+    drop(emerald);
+    drop(watcher);
+    fs::remove_dir_all(&vault_path).unwrap();
 }
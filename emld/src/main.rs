@@ -1,19 +1,38 @@
 mod format_option_parser;
 mod note_table_printer;
-use clap::{Parser, Subcommand};
+use chrono::NaiveDate;
+use clap::{Parser, Subcommand, ValueEnum};
 use emerald::DefaultEmerald;
+use emerald::Direction;
+use emerald::EmeraldOptions;
 use format_option_parser::{FormatOptionParser, FormatOptions};
 use note_table_printer::NoteTablePrinter;
+use note_table_printer::PlaceholderFilter;
+use similar::TextDiff;
 use std::fs;
 use std::fs::File;
 use std::io::Write;
 use std::path::PathBuf;
-use std::time::Instant;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 
+use emerald::ArchiveFormat;
+use emerald::ArchiveVaultStorage;
+use emerald::BrokenLink;
+use emerald::Diagnostic;
 use emerald::Emerald;
 use emerald::EmeraldError;
+use emerald::FrontmatterValue;
+use emerald::GraphFormat;
+use emerald::GraphOptions;
+use emerald::NoteTypes;
+use emerald::NoteUpdateCommand;
 use emerald::ResourceId;
 use emerald::Result;
+use emerald::ScanOptions;
+use emerald::TaskFilter;
+use emerald::UidStrategy;
+use emerald::VaultWatcher;
 
 #[allow(unused_imports)]
 use log::{debug, error, info, trace, warn};
@@ -23,10 +42,35 @@ use crate::note_table_printer::NoteTablePrinterConfig;
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
-    /// Path to note vault
+    /// Path to note vault, either a directory or a zip, tar or tar.gz archive
     #[arg(required = true)]
     vault_path: Option<PathBuf>,
 
+    /// Strategy to mint uids for notes without a uid in their frontmatter.
+    #[arg(long, global = true, value_enum, default_value_t = UidStrategyArg::Ulid)]
+    uid_strategy: UidStrategyArg,
+
+    /// Only load files matching this glob (can be repeated).
+    #[arg(long, global = true, value_name = "GLOB")]
+    include: Vec<String>,
+
+    /// Skip files matching this glob (can be repeated).
+    #[arg(long, global = true, value_name = "GLOB")]
+    exclude: Vec<String>,
+
+    /// Load hidden files and directories like .obsidian or .trash.
+    #[arg(long, global = true)]
+    hidden: bool,
+
+    /// Load files listed in .gitignore files of the vault.
+    #[arg(long, global = true)]
+    no_gitignore: bool,
+
+    /// Skip or degrade unreadable files instead of failing. The problems are
+    /// listed by `stats` and `check diagnostics`.
+    #[arg(long, global = true)]
+    lenient: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -36,10 +80,209 @@ enum Commands {
     /// Return various statistics
     Stats {},
 
+    /// Writes the uid of every note into its frontmatter.
     Update {
-        ///  If set, the output table will not include a header.
-        #[arg(short = 'o', required = true)]
-        output_folder: String,
+        /// Folder to which the updated notes are written.
+        #[arg(
+            short = 'o',
+            required_unless_present_any = ["in_place", "dry_run"],
+            conflicts_with = "in_place"
+        )]
+        output_folder: Option<String>,
+
+        /// Updates the notes in the vault itself. Unchanged notes are not touched.
+        #[arg(long, required = false, default_value_t = false)]
+        in_place: bool,
+
+        /// Prints a unified diff of the changes instead of writing them.
+        #[arg(long, required = false, default_value_t = false)]
+        dry_run: bool,
+    },
+
+    /// Sets a frontmatter entry of a note.
+    Set {
+        /// Vault relative path of the note.
+        note: String,
+
+        /// Key of the frontmatter entry.
+        key: String,
+
+        /// Value of the entry. Several values are stored as list.
+        #[arg(required = true)]
+        values: Vec<String>,
+
+        /// Type of the values.
+        #[arg(long = "type", value_enum, default_value_t = ValueType::Text)]
+        value_type: ValueType,
+
+        /// Stores a single value as list.
+        #[arg(long, required = false, default_value_t = false)]
+        list: bool,
+
+        /// Prints a unified diff of the change instead of writing it.
+        #[arg(long, required = false, default_value_t = false)]
+        dry_run: bool,
+    },
+
+    /// Removes a frontmatter entry of a note or a value from a frontmatter list.
+    Unset {
+        /// Vault relative path of the note.
+        note: String,
+
+        /// Key of the frontmatter entry.
+        key: String,
+
+        /// If given, only this value is removed from the list.
+        value: Option<String>,
+
+        /// Type of the value.
+        #[arg(long = "type", value_enum, default_value_t = ValueType::Text)]
+        value_type: ValueType,
+
+        /// Prints a unified diff of the change instead of writing it.
+        #[arg(long, required = false, default_value_t = false)]
+        dry_run: bool,
+    },
+
+    /// Appends a value to a frontmatter list of a note.
+    Add {
+        /// Vault relative path of the note.
+        note: String,
+
+        /// Key of the frontmatter list.
+        key: String,
+
+        /// Value to append.
+        value: String,
+
+        /// Type of the value.
+        #[arg(long = "type", value_enum, default_value_t = ValueType::Text)]
+        value_type: ValueType,
+
+        /// Prints a unified diff of the change instead of writing it.
+        #[arg(long, required = false, default_value_t = false)]
+        dry_run: bool,
+    },
+
+    /// Moves or renames a note and rewrites every link pointing to it.
+    Mv {
+        /// Vault relative path of the note.
+        note: String,
+
+        /// New vault relative path of the note. The `.md` extension is optional.
+        /// A path ending with `/` moves the note into this folder.
+        new_path: String,
+
+        /// Prints a unified diff of the rewritten links instead of writing them.
+        #[arg(long, required = false, default_value_t = false)]
+        dry_run: bool,
+    },
+
+    /// Lists which notes embed other notes or attachments.
+    Embeds {},
+
+    /// Prints the tag hierarchy together with the number of tagged notes.
+    Tags {},
+
+    /// Lists the tasks of all notes.
+    Tasks {
+        /// Only list open tasks.
+        #[arg(
+            long,
+            required = false,
+            default_value_t = false,
+            conflicts_with = "done"
+        )]
+        open: bool,
+
+        /// Only list done tasks.
+        #[arg(long, required = false, default_value_t = false)]
+        done: bool,
+
+        /// Only list tasks due before the given date (YYYY-MM-DD).
+        #[arg(long, required = false, value_parser = parse_date)]
+        due_before: Option<String>,
+
+        /// Only list tasks with the given tag or one of its child tags.
+        #[arg(long, required = false)]
+        tag: Option<String>,
+    },
+
+    /// Lists the places where a note is mentioned by its title or an alias
+    /// without being linked.
+    Mentions {
+        /// Vault relative path of the note.
+        note: String,
+
+        /// Turns the mentions into wiki links.
+        #[arg(long, required = false, default_value_t = false)]
+        link: bool,
+
+        /// Prints a unified diff of the new links instead of writing them.
+        #[arg(long, required = false, default_value_t = false, requires = "link")]
+        dry_run: bool,
+    },
+
+    /// Prints the shortest chain of links from one note to another. Exits with
+    /// status 1 if there is none.
+    Path {
+        /// Vault relative path of the first note.
+        from: String,
+
+        /// Vault relative path of the last note.
+        to: String,
+
+        /// Direction of the links to follow.
+        #[arg(long, value_enum, default_value_t = DirectionArg::Forward)]
+        direction: DirectionArg,
+    },
+
+    /// Lists the notes and attachments reachable from a note together with
+    /// their distance.
+    Neighbours {
+        /// Vault relative path of the note.
+        note: String,
+
+        /// Maximal number of links to follow.
+        #[arg(long, default_value_t = 1)]
+        depth: usize,
+
+        /// Direction of the links to follow.
+        #[arg(long, value_enum, default_value_t = DirectionArg::Forward)]
+        direction: DirectionArg,
+    },
+
+    /// Watches the vault and prints every change of a note as one JSON object
+    /// per line until interrupted.
+    Watch {
+        /// Milliseconds without file changes after which the changed files are reloaded.
+        #[arg(long, default_value_t = 200)]
+        debounce: u64,
+    },
+
+    /// Exports the link graph of the vault.
+    Graph {
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = GraphFormatArg::Dot)]
+        format: GraphFormatArg,
+
+        /// Adds attachments to the graph.
+        #[arg(long, required = false, default_value_t = false)]
+        attachments: bool,
+
+        /// Adds a placeholder node for each target of a broken link.
+        #[arg(long, required = false, default_value_t = false)]
+        broken: bool,
+
+        /// Writes the graph to a file instead of stdout.
+        #[arg(short = 'o', long, required = false)]
+        output: Option<PathBuf>,
+    },
+
+    /// Checks the vault for problems.
+    Check {
+        #[command(subcommand)]
+        check: CheckCommands,
     },
 
     /// Lists all notes as a table.
@@ -56,12 +299,125 @@ enum Commands {
         #[arg(short = 'f', long, required = false, default_value_t = 0)]
         follow_links: u32,
 
-        /// A regular expression to filter the notes.
+        /// A regular expression to filter the notes by their title.
         #[arg(short = 'r', long, required = false)]
         regex: Option<String>,
+
+        /// Only lists notes whose placeholder value matches a regular expression,
+        /// e.g. `orphan=true` or `component=0` (can be repeated).
+        #[arg(long = "where", value_name = "PLACEHOLDER=REGEX")]
+        filters: Vec<PlaceholderFilter>,
+    },
+}
+
+#[derive(Subcommand)]
+enum CheckCommands {
+    /// Lists all links whose target doesn't exist. Exits with status 1 if there
+    /// are any.
+    Links {
+        /// Output format of the report.
+        #[arg(long, value_enum, default_value_t = ReportFormat::Table)]
+        format: ReportFormat,
+    },
+
+    /// Lists all problems found while loading the vault, e.g. invalid
+    /// frontmatter. Exits with status 1 if there are any.
+    Diagnostics {
+        /// Output format of the report.
+        #[arg(long, value_enum, default_value_t = ReportFormat::Table)]
+        format: ReportFormat,
     },
 }
 
+/// Strategies to mint uids given on the command line.
+#[derive(Clone, Copy, ValueEnum)]
+enum UidStrategyArg {
+    /// Consecutive numbers in the order in which the notes were created. Adding a
+    /// note can change the uids of the other notes.
+    Sequential,
+
+    /// ULID derived from the creation time and the path of the note.
+    Ulid,
+
+    /// Zettelkasten timestamp (`YYYYMMDDhhmm`) of the creation time. Notes created
+    /// in the same minute get a suffix derived from their path.
+    Zettel,
+
+    /// Hash of the content of the note.
+    Hash,
+}
+
+impl From<UidStrategyArg> for UidStrategy {
+    fn from(value: UidStrategyArg) -> Self {
+        match value {
+            UidStrategyArg::Sequential => UidStrategy::Sequential,
+            UidStrategyArg::Ulid => UidStrategy::Ulid,
+            UidStrategyArg::Zettel => UidStrategy::TimestampZettel,
+            UidStrategyArg::Hash => UidStrategy::ContentHash,
+        }
+    }
+}
+
+/// Directions of the links to follow given on the command line.
+#[derive(Clone, Copy, ValueEnum)]
+enum DirectionArg {
+    /// From a note to the resources it links to.
+    Forward,
+
+    /// From a note to the notes linking to it.
+    Backward,
+
+    /// Links are followed in both directions.
+    Both,
+}
+
+impl From<DirectionArg> for Direction {
+    fn from(value: DirectionArg) -> Self {
+        match value {
+            DirectionArg::Forward => Direction::Forward,
+            DirectionArg::Backward => Direction::Backward,
+            DirectionArg::Both => Direction::Both,
+        }
+    }
+}
+
+/// Output formats of the graph export given on the command line.
+#[derive(Clone, Copy, ValueEnum)]
+enum GraphFormatArg {
+    /// Graphviz DOT language.
+    Dot,
+
+    /// GraphML xml format.
+    Graphml,
+
+    /// Mermaid flowchart.
+    Mermaid,
+
+    /// JSON document with a node and an edge list.
+    Json,
+}
+
+impl From<GraphFormatArg> for GraphFormat {
+    fn from(value: GraphFormatArg) -> Self {
+        match value {
+            GraphFormatArg::Dot => GraphFormat::Dot,
+            GraphFormatArg::Graphml => GraphFormat::GraphMl,
+            GraphFormatArg::Mermaid => GraphFormat::Mermaid,
+            GraphFormatArg::Json => GraphFormat::Json,
+        }
+    }
+}
+
+/// Output formats of reports.
+#[derive(Clone, Copy, ValueEnum)]
+enum ReportFormat {
+    /// Human readable table.
+    Table,
+
+    /// JSON document for further processing, e.g. in CI pipelines.
+    Json,
+}
+
 fn uc_stats(emerald: &dyn Emerald) -> Result<()> {
     info!("Execute usecase: Stats");
 
@@ -74,21 +430,304 @@ fn uc_stats(emerald: &dyn Emerald) -> Result<()> {
         emerald.invalid_backlink_count()
     );
 
+    println!(
+        "Backlinks with missing anchor count: {:?}",
+        emerald.anchor_missing_backlink_count()
+    );
+
+    let alias_conflicts = emerald.get_alias_conflicts();
+    println!("Alias conflict count: {:?}", alias_conflicts.len());
+    for conflict in alias_conflicts {
+        println!(
+            "  alias \"{}\" of {} is shadowed by {}",
+            conflict.alias, conflict.alias_of.0, conflict.shadowed_by.0
+        );
+    }
+
+    let diagnostics = emerald.get_diagnostics();
+    println!("Diagnostic count: {:?}", diagnostics.len());
+    for diagnostic in diagnostics {
+        println!(
+            "  {}: {} ({})",
+            diagnostic.path.0,
+            diagnostic.kind.as_str(),
+            diagnostic.message
+        );
+    }
+
+    Ok(())
+}
+fn uc_embeds(emerald: &dyn Emerald) -> Result<()> {
+    info!("Execute usecase: Embeds");
+
+    for note in emerald.flat_iter() {
+        for embed in emerald.get_embeds_of(&note) {
+            match embed {
+                NoteTypes::Note(tgt) => println!("{} -> note: {}", note.title, tgt.title),
+                NoteTypes::AnchorMissing(tgt) => {
+                    println!("{} -> note (anchor missing): {}", note.title, tgt.title)
+                }
+                NoteTypes::ResourceRef(rid) => {
+                    println!("{} -> attachment: {}", note.title, rid.0)
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn uc_tags(emerald: &dyn Emerald) -> Result<()> {
+    info!("Execute usecase: Tags");
+
+    for (tag, count) in emerald.get_tag_counts() {
+        let depth = tag.matches('/').count();
+        let name = tag.rsplit('/').next().unwrap_or(&tag);
+        println!("{}{} ({})", "  ".repeat(depth), name, count);
+    }
+
+    Ok(())
+}
+
+fn parse_date(date: &str) -> std::result::Result<String, String> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map(|f| f.format("%Y-%m-%d").to_string())
+        .map_err(|err| format!("expected a date like 2026-10-20: {}", err))
+}
+
+fn uc_tasks(emerald: &dyn Emerald, filter: &TaskFilter) -> Result<()> {
+    info!("Execute usecase: Tasks");
+
+    for (note, task) in emerald.get_tasks(filter) {
+        let state = if task.checked { 'x' } else { ' ' };
+        println!(
+            "{}:{} [{}] {}",
+            note.fs_metadata.path, task.line, state, task.text
+        );
+    }
+
     Ok(())
 }
-fn uc_update(emerald: &dyn Emerald, output_folder: &String) -> Result<()> {
-    fs::create_dir(output_folder)?;
+
+fn uc_update(
+    emerald: &dyn Emerald,
+    output_folder: &Option<String>,
+    in_place: bool,
+    dry_run: bool,
+) -> Result<()> {
+    info!("Execute usecase: Update");
+    if let (Some(output_folder), false) = (output_folder, dry_run) {
+        fs::create_dir(output_folder)?;
+    }
+
     for note in emerald.flat_iter() {
         let rid: ResourceId = emerald
             .get_resource_id(&note)
             .ok_or(EmeraldError::ValueError)?;
 
-        let mut file_path = PathBuf::new();
-        file_path.push(output_folder);
-        file_path.push(note.title + ".md");
-        let updated_note = emerald.update_note(&rid, &note.uid.0);
-        let mut file = File::create(file_path)?;
-        file.write_all(updated_note.as_bytes())?;
+        let content = emerald.get_content(&rid);
+        let updated_note = emerald.update_note(&rid, &note.uid.0)?;
+        if dry_run {
+            print_diff(&rid, content, &updated_note);
+        } else if in_place {
+            if updated_note == content {
+                debug!("Skip unchanged note {:?}", rid);
+                continue;
+            }
+            emerald.write_note(&rid, &updated_note)?;
+        } else if let Some(output_folder) = output_folder {
+            let mut file_path = PathBuf::new();
+            file_path.push(output_folder);
+            file_path.push(note.title + ".md");
+            let mut file = File::create(file_path)?;
+            file.write_all(updated_note.as_bytes())?;
+        }
+    }
+    Ok(())
+}
+
+/// Prints the changes between the original and the updated content as unified diff.
+fn print_diff(rid: &ResourceId, original: &str, updated: &str) {
+    if original == updated {
+        return;
+    }
+    let path = &rid.0;
+    let diff = TextDiff::from_lines(original, updated);
+    print!(
+        "{}",
+        diff.unified_diff()
+            .header(&format!("a/{}", path), &format!("b/{}", path))
+    );
+}
+
+/// Type of frontmatter values given on the command line.
+#[derive(Clone, Copy, ValueEnum)]
+enum ValueType {
+    Text,
+    Bool,
+    Number,
+    Date,
+}
+
+fn parse_value(value: &str, value_type: ValueType) -> Result<FrontmatterValue> {
+    let parsed = match value_type {
+        ValueType::Text => Some(FrontmatterValue::Text(value.to_owned())),
+        ValueType::Bool => value.parse().ok().map(FrontmatterValue::Bool),
+        ValueType::Number => value
+            .parse()
+            .map(FrontmatterValue::Integer)
+            .or_else(|_| value.parse().map(FrontmatterValue::Float))
+            .ok(),
+        ValueType::Date => parse_date(value).ok().map(FrontmatterValue::Date),
+    };
+
+    parsed.ok_or_else(|| {
+        error!("{:?} is not a valid value of the given type", value);
+        EmeraldError::ValueError
+    })
+}
+
+/// Returns the resource id of the note with the given vault relative path.
+fn find_note(emerald: &dyn Emerald, path: &str) -> Result<ResourceId> {
+    let rid = ResourceId::from(path);
+    emerald
+        .flat_iter()
+        .filter_map(|note| emerald.get_resource_id(&note))
+        .find(|f| *f == rid)
+        .ok_or(EmeraldError::ResourceObjectNotFound)
+}
+
+fn uc_edit_frontmatter(
+    emerald: &dyn Emerald,
+    path: &str,
+    cmd: NoteUpdateCommand,
+    dry_run: bool,
+) -> Result<()> {
+    info!("Execute usecase: EditFrontmatter");
+    let rid = find_note(emerald, path)?;
+    let content = emerald.get_content(&rid);
+    let updated_note = emerald.update_note_with(&rid, cmd)?;
+
+    if dry_run {
+        print_diff(&rid, content, &updated_note);
+    } else if updated_note != content {
+        emerald.write_note(&rid, &updated_note)?;
+    }
+    Ok(())
+}
+
+/// Returns the resource id a note is moved to.
+fn target_of_move(rid: &ResourceId, new_path: &str) -> ResourceId {
+    let new_path = new_path.trim_start_matches("./");
+    if let Some(folder) = new_path.strip_suffix('/') {
+        let name = rid.0.rsplit('/').next().unwrap_or_default();
+        return ResourceId::from(format!("{}/{}", folder, name).trim_start_matches('/'));
+    }
+    if new_path.ends_with(".md") {
+        ResourceId::from(new_path)
+    } else {
+        ResourceId::from(format!("{}.md", new_path))
+    }
+}
+
+fn uc_mv(emerald: &dyn Emerald, path: &str, new_path: &str, dry_run: bool) -> Result<()> {
+    info!("Execute usecase: Mv");
+    let rid = find_note(emerald, path)?;
+    let new_rid = target_of_move(&rid, new_path);
+
+    if dry_run {
+        let updates = emerald.plan_rename(&rid, &new_rid)?;
+        println!("rename {} -> {}", rid.0, new_rid.0);
+        for (src, updated) in updates {
+            print_diff(&src, emerald.get_content(&src), &updated);
+        }
+    } else {
+        emerald.rename_note(&rid, &new_rid)?;
+    }
+    Ok(())
+}
+
+fn uc_mentions(emerald: &dyn Emerald, path: &str, link: bool, dry_run: bool) -> Result<()> {
+    info!("Execute usecase: Mentions");
+    let rid = find_note(emerald, path)?;
+
+    if !link {
+        for mention in emerald.get_unlinked_mentions(&rid)? {
+            println!(
+                "{}:{}:{} {}",
+                mention.src.0, mention.line, mention.column, mention.text
+            );
+        }
+        return Ok(());
+    }
+
+    for (src, updated) in emerald.plan_link_mentions(&rid)? {
+        if dry_run {
+            print_diff(&src, emerald.get_content(&src), &updated);
+        } else {
+            emerald.write_note(&src, &updated)?;
+        }
+    }
+    Ok(())
+}
+
+fn uc_path(emerald: &dyn Emerald, from: &str, to: &str, direction: Direction) -> Result<()> {
+    info!("Execute usecase: Path");
+    let from = find_note(emerald, from)?;
+    let to = find_note(emerald, to)?;
+
+    let Some(path) = emerald.get_shortest_path(&from, &to, direction) else {
+        eprintln!("No link path from {} to {}", from.0, to.0);
+        std::process::exit(1);
+    };
+    let names: Vec<_> = path.iter().map(|f| f.0.as_ref()).collect();
+    println!("{}", names.join(" -> "));
+    Ok(())
+}
+
+fn uc_neighbours(
+    emerald: &dyn Emerald,
+    path: &str,
+    depth: usize,
+    direction: Direction,
+) -> Result<()> {
+    info!("Execute usecase: Neighbours");
+    let rid = find_note(emerald, path)?;
+
+    for neighbour in emerald.get_neighbours(&rid, depth, direction) {
+        println!("{} {}", neighbour.distance, neighbour.rid.0);
+    }
+    Ok(())
+}
+
+fn uc_watch(emerald: DefaultEmerald, debounce: u64) -> Result<()> {
+    info!("Execute usecase: Watch");
+    let emerald = Arc::new(RwLock::new(emerald));
+    let watcher = VaultWatcher::new(emerald, Duration::from_millis(debounce))?;
+
+    for event in watcher.subscribe() {
+        let line = serde_json::json!({
+            "event": event.as_str(),
+            "note": event.rid().0.as_ref(),
+        });
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+fn uc_graph(
+    emerald: &dyn Emerald,
+    format: GraphFormat,
+    options: &GraphOptions,
+    output: &Option<PathBuf>,
+) -> Result<()> {
+    info!("Execute usecase: Graph");
+    let graph = emerald.get_graph(options);
+    let document = graph.write(format);
+
+    match output {
+        Some(path) => fs::write(path, document)?,
+        None => print!("{}", document),
     }
     Ok(())
 }
@@ -99,6 +738,7 @@ fn uc_list(
     print_header: bool,
     follow_links: u32,
     title_regex_predicate: &Option<String>,
+    filters: &[PlaceholderFilter],
 ) -> Result<()> {
     info!("Execute usecase: List");
     let format_string = match format_opt {
@@ -134,6 +774,7 @@ fn uc_list(
         print_header,
         follow_links,
         title_regex_predicate: title_regex_predicate.clone(),
+        filters: filters.to_vec(),
     };
     let pt = NoteTablePrinter {
         emerald,
@@ -144,6 +785,144 @@ fn uc_list(
     Ok(())
 }
 
+fn print_broken_links_table(broken_links: &[BrokenLink]) {
+    let locations: Vec<_> = broken_links
+        .iter()
+        .map(|f| format!("{}:{}:{}", f.src.0, f.line, f.column))
+        .collect();
+    let loc_width = locations
+        .iter()
+        .map(|f| f.chars().count())
+        .max()
+        .unwrap_or(0);
+    let link_width = broken_links
+        .iter()
+        .map(|f| f.link.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    for (location, broken_link) in locations.iter().zip(broken_links) {
+        let suggestions = if broken_link.suggestions.is_empty() {
+            String::new()
+        } else {
+            format!("did you mean: {}", broken_link.suggestions.join(", "))
+        };
+        let line = format!(
+            "{:loc_width$}  {:link_width$}  {}",
+            location, broken_link.link, suggestions
+        );
+        println!("{}", line.trim_end());
+    }
+    println!("{} broken link(s)", broken_links.len());
+}
+
+fn print_broken_links_json(broken_links: &[BrokenLink]) {
+    let entries: Vec<_> = broken_links
+        .iter()
+        .map(|f| {
+            serde_json::json!({
+                "source": f.src.0.as_ref(),
+                "line": f.line,
+                "column": f.column,
+                "link": f.link,
+                "kind": f.kind.as_str(),
+                "suggestions": f.suggestions,
+            })
+        })
+        .collect();
+    let report = serde_json::json!({
+        "broken_links": entries,
+        "count": broken_links.len(),
+    });
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&report).expect("JSON serialization failed")
+    );
+}
+
+fn print_diagnostics_table(diagnostics: &[Diagnostic]) {
+    let locations: Vec<_> = diagnostics
+        .iter()
+        .map(|f| match f.span {
+            Some((start, end)) => format!("{}:{}-{}", f.path.0, start, end),
+            None => f.path.0.to_string(),
+        })
+        .collect();
+    let loc_width = locations
+        .iter()
+        .map(|f| f.chars().count())
+        .max()
+        .unwrap_or(0);
+    let kind_width = diagnostics
+        .iter()
+        .map(|f| f.kind.as_str().len())
+        .max()
+        .unwrap_or(0);
+
+    for (location, diagnostic) in locations.iter().zip(diagnostics) {
+        let line = format!(
+            "{:loc_width$}  {:kind_width$}  {}",
+            location,
+            diagnostic.kind.as_str(),
+            diagnostic.message.replace('\n', " ")
+        );
+        println!("{}", line.trim_end());
+    }
+    println!("{} diagnostic(s)", diagnostics.len());
+}
+
+fn print_diagnostics_json(diagnostics: &[Diagnostic]) {
+    let entries: Vec<_> = diagnostics
+        .iter()
+        .map(|f| {
+            serde_json::json!({
+                "path": f.path.0.as_ref(),
+                "kind": f.kind.as_str(),
+                "message": f.message,
+                "span": f.span.map(|(start, end)| [start, end]),
+            })
+        })
+        .collect();
+    let report = serde_json::json!({
+        "diagnostics": entries,
+        "count": diagnostics.len(),
+    });
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&report).expect("JSON serialization failed")
+    );
+}
+
+fn uc_check_diagnostics(emerald: &dyn Emerald, format: ReportFormat) -> Result<()> {
+    info!("Execute usecase: Check diagnostics");
+
+    let diagnostics = emerald.get_diagnostics();
+    match format {
+        ReportFormat::Table => print_diagnostics_table(diagnostics),
+        ReportFormat::Json => print_diagnostics_json(diagnostics),
+    }
+
+    if !diagnostics.is_empty() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn uc_check_links(emerald: &dyn Emerald, format: ReportFormat) -> Result<()> {
+    info!("Execute usecase: Check links");
+
+    let broken_links = emerald.get_broken_links();
+    match format {
+        ReportFormat::Table => print_broken_links_table(&broken_links),
+        ReportFormat::Json => print_broken_links_json(&broken_links),
+    }
+
+    if !broken_links.is_empty() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
 fn main() -> Result<()> {
     env_logger::init();
 
@@ -154,22 +933,144 @@ fn main() -> Result<()> {
 
     let vault_path = cli.vault_path.unwrap();
 
-    if !vault_path.is_dir() {
+    let options = EmeraldOptions {
+        uid_strategy: cli.uid_strategy.into(),
+        scan: ScanOptions {
+            skip_hidden: !cli.hidden,
+            use_gitignore: !cli.no_gitignore,
+            include: cli.include.clone(),
+            exclude: cli.exclude.clone(),
+        },
+        lenient: cli.lenient,
+    };
+    let emerald = if vault_path.is_dir() {
+        DefaultEmerald::with_options(&vault_path, &options)?
+    } else if vault_path.is_file() && ArchiveFormat::from_path(&vault_path).is_some() {
+        let storage = ArchiveVaultStorage::open(&vault_path, &options.scan)?;
+        DefaultEmerald::with_storage(Arc::new(storage), &options)?
+    } else {
         return Err(EmeraldError::VaultNotFound);
-    }
-
-    let emerald = DefaultEmerald::new(&vault_path)?;
+    };
 
     // execute use-cases
     match &cli.command {
         Commands::Stats {} => uc_stats(&emerald)?,
-        Commands::Update { output_folder } => uc_update(&emerald, output_folder)?,
+        Commands::Mv {
+            note,
+            new_path,
+            dry_run,
+        } => uc_mv(&emerald, note, new_path, *dry_run)?,
+        Commands::Embeds {} => uc_embeds(&emerald)?,
+        Commands::Tags {} => uc_tags(&emerald)?,
+        Commands::Tasks {
+            open,
+            done,
+            due_before,
+            tag,
+        } => {
+            let filter = TaskFilter {
+                checked: (*open || *done).then_some(*done),
+                due_before: due_before.clone(),
+                tag: tag.clone(),
+            };
+            uc_tasks(&emerald, &filter)?
+        }
+        Commands::Update {
+            output_folder,
+            in_place,
+            dry_run,
+        } => uc_update(&emerald, output_folder, *in_place, *dry_run)?,
+        Commands::Set {
+            note,
+            key,
+            values,
+            value_type,
+            list,
+            dry_run,
+        } => {
+            let mut values = values
+                .iter()
+                .map(|f| parse_value(f, *value_type))
+                .collect::<Result<Vec<_>>>()?;
+            let value = if values.len() > 1 || *list {
+                FrontmatterValue::List(values)
+            } else {
+                values.remove(0)
+            };
+            let cmd = NoteUpdateCommand::UpdateOrInsert {
+                key: key.clone(),
+                value,
+            };
+            uc_edit_frontmatter(&emerald, note, cmd, *dry_run)?
+        }
+        Commands::Unset {
+            note,
+            key,
+            value,
+            value_type,
+            dry_run,
+        } => {
+            let cmd = match value {
+                Some(value) => NoteUpdateCommand::RemoveFromList {
+                    key: key.clone(),
+                    value: parse_value(value, *value_type)?,
+                },
+                None => NoteUpdateCommand::Remove { key: key.clone() },
+            };
+            uc_edit_frontmatter(&emerald, note, cmd, *dry_run)?
+        }
+        Commands::Add {
+            note,
+            key,
+            value,
+            value_type,
+            dry_run,
+        } => {
+            let cmd = NoteUpdateCommand::AppendToList {
+                key: key.clone(),
+                value: parse_value(value, *value_type)?,
+            };
+            uc_edit_frontmatter(&emerald, note, cmd, *dry_run)?
+        }
+        Commands::Mentions {
+            note,
+            link,
+            dry_run,
+        } => uc_mentions(&emerald, note, *link, *dry_run)?,
+        Commands::Path {
+            from,
+            to,
+            direction,
+        } => uc_path(&emerald, from, to, (*direction).into())?,
+        Commands::Neighbours {
+            note,
+            depth,
+            direction,
+        } => uc_neighbours(&emerald, note, *depth, (*direction).into())?,
+        Commands::Graph {
+            format,
+            attachments,
+            broken,
+            output,
+        } => {
+            let options = GraphOptions {
+                include_attachments: *attachments,
+                include_broken_links: *broken,
+            };
+            uc_graph(&emerald, (*format).into(), &options, output)?
+        }
+        Commands::Watch { debounce } => uc_watch(emerald, *debounce)?,
+        Commands::Check { check } => match check {
+            CheckCommands::Links { format } => uc_check_links(&emerald, *format)?,
+            CheckCommands::Diagnostics { format } => uc_check_diagnostics(&emerald, *format)?,
+        },
         Commands::List {
             format,
             no_header,
             regex,
             follow_links,
-        } => uc_list(&emerald, format, !no_header, *follow_links, regex)?,
+            filters,
+        } => uc_list(&emerald, format, !no_header, *follow_links, regex, filters)?,
     }
     debug!("User set vault path to {:?}", vault_path);
 
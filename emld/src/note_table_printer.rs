@@ -1,10 +1,11 @@
 use chrono::prelude::*;
-use emerald::{Emerald, Note, NoteTypes, Uid};
+use emerald::{Emerald, Note, NoteAnalytics, NoteTypes, Uid};
 use formatify::{Formatify, PlaceholderFormatter};
 use regex::Regex;
 use std::collections::HashMap;
+use std::str::FromStr;
 
-#[derive(PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 enum NoteProperty {
     Uid,
     Depth,
@@ -15,10 +16,17 @@ enum NoteProperty {
     Size,
     LinkCnt,
     BackLinkCnt,
+    EmbedCnt,
     Path,
     Markdown,
     Aliases,
     Keywords,
+    PageRank,
+    Component,
+    Orphan,
+    DeadEnd,
+    InDegree,
+    OutDegree,
     Undefined,
 }
 
@@ -36,8 +44,15 @@ impl NoteProperty {
             NoteProperty::Size => "size",
             NoteProperty::LinkCnt => "linkcnt",
             NoteProperty::BackLinkCnt => "backlinkcnt",
+            NoteProperty::EmbedCnt => "embedcnt",
             NoteProperty::Path => "path",
             NoteProperty::Markdown => "markdown",
+            NoteProperty::PageRank => "pagerank",
+            NoteProperty::Component => "component",
+            NoteProperty::Orphan => "orphan",
+            NoteProperty::DeadEnd => "deadend",
+            NoteProperty::InDegree => "indegree",
+            NoteProperty::OutDegree => "outdegree",
             NoteProperty::Undefined => panic!("undefined property"),
         }
     }
@@ -54,13 +69,25 @@ impl NoteProperty {
             "size" => NoteProperty::Size,
             "linkcnt" => NoteProperty::LinkCnt,
             "backlinkcnt" => NoteProperty::BackLinkCnt,
+            "embedcnt" => NoteProperty::EmbedCnt,
             "path" => NoteProperty::Path,
             "markdown" => NoteProperty::Markdown,
+            "pagerank" => NoteProperty::PageRank,
+            "component" => NoteProperty::Component,
+            "orphan" => NoteProperty::Orphan,
+            "deadend" => NoteProperty::DeadEnd,
+            "indegree" => NoteProperty::InDegree,
+            "outdegree" => NoteProperty::OutDegree,
             _ => NoteProperty::Undefined,
         }
     }
 }
 
+fn note_analytics(note: &Note<Uid>, vault: &dyn Emerald) -> NoteAnalytics {
+    let rid = vault.get_resource_id(note).expect("Should exist");
+    *vault.get_analytics().get(&rid).expect("Should exist")
+}
+
 fn note_property_to_str(
     element: &NoteProperty,
     note: &Note<Uid>,
@@ -91,10 +118,49 @@ fn note_property_to_str(
         NoteProperty::Size => note.fs_metadata.size.to_string(),
         NoteProperty::LinkCnt => vault.get_links_of(note).count().to_string(),
         NoteProperty::BackLinkCnt => vault.get_backlinks_of(note).count().to_string(),
+        NoteProperty::EmbedCnt => vault.get_embeds_of(note).count().to_string(),
+        NoteProperty::PageRank => format!("{:.6}", note_analytics(note, vault).page_rank),
+        NoteProperty::Component => note_analytics(note, vault).component.to_string(),
+        NoteProperty::Orphan => note_analytics(note, vault).is_orphan().to_string(),
+        NoteProperty::DeadEnd => note_analytics(note, vault).is_dead_end().to_string(),
+        NoteProperty::InDegree => note_analytics(note, vault).in_degree.to_string(),
+        NoteProperty::OutDegree => note_analytics(note, vault).out_degree.to_string(),
         NoteProperty::Undefined => panic!("Undefined property"),
     }
 }
 
+/// Condition on the value of a placeholder, given as `<placeholder>=<regex>`
+/// (e.g. `orphan=true`). The regular expression has to match the whole value.
+#[derive(Debug, Clone)]
+pub struct PlaceholderFilter {
+    property: NoteProperty,
+    regex: Regex,
+}
+
+impl PlaceholderFilter {
+    /// Returns true if the value of the placeholder for `note` matches.
+    fn is_match(&self, note: &Note<Uid>, vault: &dyn Emerald) -> bool {
+        let value = note_property_to_str(&self.property, note, vault, 0);
+        self.regex.is_match(&value)
+    }
+}
+
+impl FromStr for PlaceholderFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((key, pattern)) = s.split_once('=') else {
+            return Err(format!("expected <placeholder>=<regex>, got '{}'", s));
+        };
+        let property = NoteProperty::from(key);
+        if property == NoteProperty::Undefined {
+            return Err(format!("unknown placeholder '{}'", key));
+        }
+        let regex = Regex::new(&format!("^(?:{})$", pattern)).map_err(|err| err.to_string())?;
+        Ok(Self { property, regex })
+    }
+}
+
 struct NoteLinkTraversal<'a> {
     vault: &'a dyn Emerald,
     used_props: &'a Vec<NoteProperty>,
@@ -104,10 +170,17 @@ struct NoteLinkTraversal<'a> {
 
 impl<'a> NoteLinkTraversal<'a> {
     fn print(&self, parent_note: &Note<Uid>, depth: u32) {
+        self.print_children(parent_note, depth, &mut vec![parent_note.uid.clone()]);
+    }
+
+    /// Prints the linked notes of `parent_note`. Notes which are already part of
+    /// the current chain of links (`ancestors`) are printed but not followed
+    /// again, so that cycles end.
+    fn print_children(&self, parent_note: &Note<Uid>, depth: u32, ancestors: &mut Vec<Uid>) {
         let mut key_value_store = HashMap::<&str, String>::new();
         let expr_parser = Formatify::new();
         for note_types in self.vault.get_links_of(parent_note) {
-            let NoteTypes::Note(child) = note_types else {
+            let (NoteTypes::Note(child) | NoteTypes::AnchorMissing(child)) = note_types else {
                 continue;
             };
             self.used_props.iter().for_each(|property| {
@@ -119,8 +192,10 @@ impl<'a> NoteLinkTraversal<'a> {
                 "{}",
                 expr_parser.replace_placeholders(&key_value_store, self.format_string)
             );
-            if self.follow_links > depth {
-                self.print(&child, depth + 1);
+            if self.follow_links > depth && !ancestors.contains(&child.uid) {
+                ancestors.push(child.uid.clone());
+                self.print_children(&child, depth + 1, ancestors);
+                ancestors.pop();
             }
         }
     }
@@ -140,11 +215,14 @@ impl<'a> NoteLinkTraversal<'a> {
 ///   and printed.
 /// - `title_regex_predicate`: An optional string containing a regex pattern used to filter notes
 ///   by their titles. Only notes with titles matching the pattern will be printed.
+/// - `filters`: Conditions on placeholder values. Only notes matching all of them will be
+///   printed.
 pub struct NoteTablePrinterConfig {
     pub format_string: String,
     pub print_header: bool,
     pub follow_links: u32,
     pub title_regex_predicate: Option<String>,
+    pub filters: Vec<PlaceholderFilter>,
 }
 
 /// `NoteTablePrinter` - A utility for printing information about notes in a table format.
@@ -157,7 +235,6 @@ pub struct NoteTablePrinterConfig {
 /// # Fields
 /// - `vault`: A reference to an object implementing the `Vault` trait, used for accessing notes.
 /// - `config`: Configuration settings for printing, including the format string, header printing option, link-following depth, and title regex filter.
-
 pub struct NoteTablePrinter<'a> {
     pub emerald: &'a dyn Emerald,
     pub config: NoteTablePrinterConfig,
@@ -215,22 +292,9 @@ impl<'a> NoteTablePrinter<'a> {
             );
         }
 
-        let mut opt_regex: Option<Regex> = None;
-        if let Some(title_regex_predicate) = self.config.title_regex_predicate.clone() {
-            // Try to create a new Regex object and assign it to opt_regex
-            opt_regex = Regex::new(&title_regex_predicate).ok()
-        }
-
         // # print content - use valid placeholders for it
         let mut key_value_store = HashMap::<&str, String>::new();
-        for i in self.emerald.flat_iter() {
-            // Check if opt_regex is Some and if the regex matches the title of the current element
-            if let Some(ref regex) = opt_regex {
-                if !regex.is_match(&i.title) {
-                    continue;
-                }
-            }
-
+        for i in self.filtered_notes() {
             used_props.iter().for_each(|property| {
                 let ref_cell = note_property_to_str(property, &i, self.emerald, 0);
                 let out_str = ref_cell;
@@ -254,4 +318,94 @@ impl<'a> NoteTablePrinter<'a> {
             }
         }
     }
+
+    /// Returns the notes of the vault matching the title regex and the placeholder
+    /// filters of the configuration.
+    fn filtered_notes(&self) -> Vec<Note<Uid>> {
+        let mut opt_regex: Option<Regex> = None;
+        if let Some(title_regex_predicate) = self.config.title_regex_predicate.clone() {
+            // Try to create a new Regex object and assign it to opt_regex
+            opt_regex = Regex::new(&title_regex_predicate).ok()
+        }
+
+        self.emerald
+            .flat_iter()
+            .filter(|note| {
+                // Check if opt_regex is Some and if the regex matches the title of the note
+                opt_regex
+                    .as_ref()
+                    .is_none_or(|regex| regex.is_match(&note.title))
+            })
+            .filter(|note| {
+                self.config
+                    .filters
+                    .iter()
+                    .all(|filter| filter.is_match(note, self.emerald))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NoteTablePrinter, NoteTablePrinterConfig, PlaceholderFilter};
+    use emerald::{DefaultEmerald, EmeraldOptions, MemoryVaultStorage};
+    use std::sync::Arc;
+
+    fn create_emerald() -> DefaultEmerald {
+        let storage = Arc::new(MemoryVaultStorage::new());
+        storage.insert("hub.md", "See [[leaf]] and [[other]].\n");
+        storage.insert("leaf.md", "No links.\n");
+        storage.insert("other.md", "Back to [[hub]].\n");
+        storage.insert("orphan.md", "Alone.\n");
+        DefaultEmerald::with_storage(storage, &EmeraldOptions::default()).unwrap()
+    }
+
+    fn filtered_titles(emerald: &DefaultEmerald, filters: &[&str]) -> Vec<String> {
+        let printer = NoteTablePrinter {
+            emerald,
+            config: NoteTablePrinterConfig {
+                format_string: "%(title)".to_string(),
+                print_header: false,
+                follow_links: 0,
+                title_regex_predicate: None,
+                filters: filters.iter().map(|f| f.parse().unwrap()).collect(),
+            },
+        };
+        let mut titles: Vec<_> = printer
+            .filtered_notes()
+            .into_iter()
+            .map(|f| f.title)
+            .collect();
+        titles.sort();
+        titles
+    }
+
+    #[test]
+    fn test_filter_by_analytics_placeholders() {
+        let emerald = create_emerald();
+
+        assert_eq!(filtered_titles(&emerald, &["orphan=true"]), ["orphan"]);
+        assert_eq!(filtered_titles(&emerald, &["deadend=true"]), ["leaf"]);
+        assert_eq!(filtered_titles(&emerald, &["outdegree=2"]), ["hub"]);
+        assert_eq!(
+            filtered_titles(&emerald, &["orphan=false", "indegree=1"]),
+            ["hub", "leaf", "other"]
+        );
+    }
+
+    #[test]
+    fn test_filter_matches_whole_value() {
+        let emerald = create_emerald();
+
+        assert!(filtered_titles(&emerald, &["title=rph"]).is_empty());
+        assert_eq!(filtered_titles(&emerald, &["title=.*rph.*"]), ["orphan"]);
+    }
+
+    #[test]
+    fn test_parse_invalid_filter() {
+        assert!("orphan".parse::<PlaceholderFilter>().is_err());
+        assert!("unknown=true".parse::<PlaceholderFilter>().is_err());
+        assert!("title=(".parse::<PlaceholderFilter>().is_err());
+    }
 }